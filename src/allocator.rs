@@ -0,0 +1,249 @@
+use crate::{Bound, Range, RangeSet};
+
+/// Hands out ranges of `u64` offsets from a space, like a suballocator for a heap or an address space
+///
+/// The free parts of the space are kept in a [`RangeSet`](RangeSet), so freed ranges merge with their free
+/// neighbours. Ranges are allocated first fit, at the lowest offset they fit at. In buddy mode, created with
+/// [`buddy`](RangeAllocator::buddy), every size is rounded up to a power of two and aligned to itself, so
+/// allocations never straddle the boundary of a larger block
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, RangeAllocator};
+///
+/// let mut heap = RangeAllocator::new(0..4096);
+///
+/// assert_eq!(Some(r!(0..100)), heap.allocate(100));
+/// assert_eq!(Some(r!(256..512)), heap.allocate_aligned(256, 256));
+/// assert_eq!(Some(r!(100..150)), heap.allocate(50));
+/// assert!(heap.free(r!(0..100)));
+/// assert!(!heap.free(r!(0..100)));
+///
+/// let mut buddy = RangeAllocator::buddy(0..4096);
+///
+/// assert_eq!(Some(r!(0..128)), buddy.allocate(100));
+/// assert_eq!(Some(r!(512..1024)), buddy.allocate(300));
+/// assert_eq!(Some(r!(128..192)), buddy.allocate(64));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RangeAllocator {
+    space: Range<u64>,
+    free: RangeSet<u64>,
+    buddy: bool,
+}
+
+impl RangeAllocator {
+    /// Create an allocator handing out ranges from given space first fit
+    pub fn new(space: impl Into<Range<u64>>) -> Self {
+        let space = space.into();
+        RangeAllocator { free: RangeSet::single(space.clone()), space, buddy: false }
+    }
+
+    /// Create an allocator handing out ranges from given space in blocks of a power of two, which are aligned to
+    /// their size counting from zero, so the space should start at a multiple of the largest block
+    pub fn buddy(space: impl Into<Range<u64>>) -> Self {
+        RangeAllocator { buddy: true, ..RangeAllocator::new(space) }
+    }
+
+    /// The space ranges are handed out from
+    pub fn space(&self) -> &Range<u64> {
+        &self.space
+    }
+
+    /// The parts of the space that aren't allocated
+    pub fn free_space(&self) -> &RangeSet<u64> {
+        &self.free
+    }
+
+    /// If nothing is allocated
+    pub fn is_unused(&self) -> bool {
+        self.free == RangeSet::single(self.space.clone())
+    }
+
+    /// Allocate a range of `size` offsets, returns `None` if there's no free range large enough
+    pub fn allocate(&mut self, size: u64) -> Option<Range<u64>> {
+        self.allocate_aligned(size, 1)
+    }
+
+    /// Allocate a range of `size` offsets starting at a multiple of `align`, returns `None` if there's no free range
+    /// large enough or if `align` isn't a power of two
+    ///
+    /// In buddy mode the size is rounded up to a power of two of at least `align`, which is also what the range is
+    /// aligned to
+    pub fn allocate_aligned(&mut self, size: u64, align: u64) -> Option<Range<u64>> {
+        if size == 0 || !align.is_power_of_two() {
+            return None;
+        }
+
+        let (size, align) = if self.buddy {
+            let block = size.max(align).checked_next_power_of_two()?;
+            (block, block)
+        } else {
+            (size, align)
+        };
+
+        let start = self.free.items().find_map(|range| {
+            let start = range.first_value()?.checked_next_multiple_of(align)?;
+            (start.checked_add(size - 1)? <= range.last_value()?).then_some(start)
+        })?;
+
+        let range = Range::new(Bound::Included(start), Bound::Excluded(start.checked_add(size)?));
+        self.free.remove(range.clone());
+        Some(range)
+    }
+
+    /// Return given range to the free space, returns `false` without freeing anything if any part of it is outside
+    /// of the space or already free
+    pub fn free(&mut self, range: impl Into<Range<u64>>) -> bool {
+        let range = range.into();
+        if !self.space.contains_range((range.start(), range.end())) || self.free.is_overlapping(&range) {
+            return false;
+        }
+
+        self.free.add(range);
+        true
+    }
+}
+
+/// A part of a set to move while compacting it, returned by [`RangeSet::compaction_plan`](RangeSet::compaction_plan)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Relocation {
+    source: u64,
+    destination: u64,
+    size: u64,
+}
+
+impl Relocation {
+    /// The inclusive range to move
+    #[inline]
+    pub fn source(&self) -> Range<u64> {
+        Range::new(Bound::Included(self.source), Bound::Included(self.source + (self.size - 1)))
+    }
+
+    /// The inclusive range to move it to
+    #[inline]
+    pub fn destination(&self) -> Range<u64> {
+        Range::new(Bound::Included(self.destination), Bound::Included(self.destination + (self.size - 1)))
+    }
+
+    /// The amount of offsets moved
+    #[inline]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// The first and last value of every range in given set
+fn values(set: &RangeSet<u64>) -> impl Iterator<Item=(u64, u64)> + '_ {
+    set.items()
+        .filter_map(|range| range.first_value().zip(range.last_value()))
+        .filter(|(first, last)| first <= last)
+}
+
+impl RangeSet<u64> {
+    /// Plan moving the values in this set so they form a single range at the start of `target`, returns `None`
+    /// if they don't fit in `target`
+    ///
+    /// Only the parts outside of the final range are moved, each into a gap inside of it, so no fewer offsets can be
+    /// moved. A range may be split over multiple gaps. No destination overlaps with any source, so the moves can be
+    /// carried out in any order
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let used = range_set![r!(0u64..10), r!(20..30), r!(40..45)];
+    /// let plan = used.compaction_plan(0..100).unwrap();
+    ///
+    /// // Everything has to fit in `0..25`, so `25..30` moves to `10..15` and `40..45` to `15..20`
+    /// let moves: Vec<_> = plan.iter().map(|relocation| (relocation.source(), relocation.destination())).collect();
+    /// assert_eq!(vec![(r!(25..=29), r!(10..=14)), (r!(40..=44), r!(15..=19))], moves);
+    /// assert_eq!(10, plan.iter().map(|relocation| relocation.size()).sum::<u64>());
+    /// ```
+    pub fn compaction_plan(&self, target: impl Into<Range<u64>>) -> Option<Vec<Relocation>> {
+        let target = target.into();
+        let total = values(self).try_fold(0u64, |total, (first, last)| total.checked_add(last - first + 1))?;
+        if total == 0 {
+            return Some(vec![]);
+        }
+
+        let start = target.first_value()?;
+        let end = start.checked_add(total)?;
+        if end - 1 > target.last_value()? {
+            return None;
+        }
+
+        let packed = RangeSet::single(start..end);
+        let (gaps, outside) = (packed.difference(self), self.difference(&packed));
+        let (mut gaps, mut outside) = (values(&gaps), values(&outside));
+        let (mut gap, mut part) = (gaps.next(), outside.next());
+        let mut plan = vec![];
+        while let (Some((gap_first, gap_last)), Some((part_first, part_last))) = (gap, part) {
+            let size = (gap_last - gap_first).min(part_last - part_first) + 1;
+            plan.push(Relocation { source: part_first, destination: gap_first, size });
+            gap = if size > gap_last - gap_first { gaps.next() } else { Some((gap_first + size, gap_last)) };
+            part = if size > part_last - part_first { outside.next() } else { Some((part_first + size, part_last)) };
+        }
+
+        Some(plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeAllocator, RangeSet};
+
+    #[test]
+    fn allocator() {
+        let mut heap = RangeAllocator::new(10..1000);
+        assert_eq!(None, heap.allocate(0));
+        assert_eq!(None, heap.allocate(991));
+        assert_eq!(None, heap.allocate_aligned(8, 3));
+        assert_eq!(Some(r!(10..20)), heap.allocate(10));
+        assert_eq!(Some(r!(64..96)), heap.allocate_aligned(32, 64));
+        assert_eq!(Some(r!(20..60)), heap.allocate(40));
+        assert_eq!(range_set![r!(60..64), r!(96..1000)], *heap.free_space());
+        assert_eq!(Some(r!(60..64)), heap.allocate(4));
+
+        assert!(heap.free(r!(64..96)));
+        assert!(!heap.free(r!(80..90)));
+        assert!(!heap.free(r!(0..5)));
+        assert_eq!(Some(r!(64..80)), heap.allocate_aligned(16, 16));
+        assert!(heap.free(r!(10..60)) && heap.free(r!(64..80)) && heap.free(r!(60..64)));
+        assert!(heap.is_unused());
+
+        let mut buddy = RangeAllocator::buddy(0..1024);
+        assert_eq!(Some(r!(0..1)), buddy.allocate(1));
+        assert_eq!(Some(r!(64..128)), buddy.allocate_aligned(3, 64));
+        assert_eq!(Some(r!(2..4)), buddy.allocate(2));
+        assert_eq!(Some(r!(512..1024)), buddy.allocate(300));
+        assert_eq!(None, buddy.allocate(257));
+        assert!(buddy.free(r!(512..1024)));
+        assert_eq!(None, buddy.allocate(1025));
+        assert_eq!(None, RangeAllocator::buddy(0..=u64::MAX).allocate(u64::MAX));
+    }
+
+    #[test]
+    fn compaction_plan() {
+        let used = range_set![r!(0u64..2), r!(4..=8), r!(10>..12), r!(20..30), r!(50..53)];
+        let plan = used.compaction_plan(0..100).unwrap();
+        let mut compacted = used.clone();
+        for relocation in &plan {
+            assert_eq!(RangeSet::single(relocation.source()), used.intersection(&relocation.source()));
+            assert!(relocation.destination().into_iter().all(|value| !used.contains(&value)));
+            compacted.remove(relocation.source());
+            compacted.add(relocation.destination());
+        }
+
+        assert!(compacted.values().eq(0..21));
+        assert_eq!(used.difference(&r!(0..21)).measure(), Some(plan.iter().map(|relocation| relocation.size()).sum()));
+        assert_eq!(Some(vec![]), range_set![r!(5u64..10)].compaction_plan(5..10));
+        assert_eq!(None, range_set![r!(5u64..10)].compaction_plan(6..10));
+        assert_eq!(Some(vec![]), RangeSet::<u64>::empty().compaction_plan(0..0));
+
+        let plan = range_set![r!((u64::MAX)..=u64::MAX)].compaction_plan(0u64..10).unwrap();
+        assert_eq!(vec![(r!((u64::MAX)..=u64::MAX), r!(0..=0))], plan.iter().map(|relocation| (relocation.source(), relocation.destination())).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::ops::Add;
+use crate::{Bound, Discrete, Measure, Range, RangeSet};
+
+/// A finite universe of values, which a [`BoundedRangeSet`](BoundedRangeSet) is interpreted relative to
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, Domain, Range};
+///
+/// struct Ports;
+///
+/// impl Domain<u16> for Ports {
+///     fn universe() -> Range<u16> {
+///         r!(1..=65535)
+///     }
+/// }
+/// ```
+pub trait Domain<T> {
+    /// Every value in this domain, which has to be bounded on both sides
+    fn universe() -> Range<T>;
+}
+
+/// The domain holding every value of a [`Discrete`](Discrete) type, from its lowest to its highest value
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct FullDomain;
+
+impl<T: Discrete> Domain<T> for FullDomain {
+    fn universe() -> Range<T> {
+        let min = T::min_value().expect("a full domain needs a lowest value");
+        let max = T::max_value().expect("a full domain needs a highest value");
+        Range::new(Bound::Included(min), Bound::Included(max))
+    }
+}
+
+/// A set of ranges within the finite universe of a [`Domain`](Domain)
+///
+/// Every range is clamped to the universe, so [`invert`](BoundedRangeSet::invert) and
+/// [`is_full`](BoundedRangeSet::is_full) are relative to it and the [`count`](BoundedRangeSet::count) and
+/// [`measure`](BoundedRangeSet::measure) of a set are always defined
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, BoundedRangeSet, Domain, Range};
+///
+/// struct Ports;
+///
+/// impl Domain<u16> for Ports {
+///     fn universe() -> Range<u16> {
+///         r!(1..=65535)
+///     }
+/// }
+///
+/// let mut open: BoundedRangeSet<u16, Ports> = BoundedRangeSet::new();
+/// open.add(r!(..1024));
+///
+/// assert_eq!(&range_set![r!(1..1024)], open.as_set());
+/// assert_eq!(&range_set![r!(1024..=65535)], open.invert().as_set());
+/// assert_eq!(1023, open.count());
+/// ```
+pub struct BoundedRangeSet<T: Ord, D = FullDomain> {
+    set: RangeSet<T>,
+    domain: PhantomData<fn() -> D>,
+}
+
+impl<T: Ord + Debug, D> Debug for BoundedRangeSet<T, D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundedRangeSet")
+            .field("set", &self.set)
+            .finish()
+    }
+}
+
+impl<T: Ord + Clone, D> Clone for BoundedRangeSet<T, D> {
+    fn clone(&self) -> Self {
+        BoundedRangeSet { set: self.set.clone(), domain: PhantomData }
+    }
+}
+
+impl<T: Ord, D> PartialEq for BoundedRangeSet<T, D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.set == other.set
+    }
+}
+
+impl<T: Ord, D> Eq for BoundedRangeSet<T, D> {}
+
+impl<T: Ord + Debug, D: Domain<T>> Default for BoundedRangeSet<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Debug, D: Domain<T>> BoundedRangeSet<T, D> {
+    /// Create a new empty set
+    pub fn new() -> Self {
+        BoundedRangeSet { set: RangeSet::empty(), domain: PhantomData }
+    }
+
+    /// If this set holds no values
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Returns `true` if given value falls within this set
+    pub fn contains(&self, value: &T) -> bool {
+        self.set.contains(value)
+    }
+
+    /// The ranges of this set
+    #[inline]
+    pub fn as_set(&self) -> &RangeSet<T> {
+        &self.set
+    }
+
+    /// Returns the ranges of this set
+    #[inline]
+    pub fn into_set(self) -> RangeSet<T> {
+        self.set
+    }
+}
+
+impl<T: Ord + Clone + Debug, D: Domain<T>> BoundedRangeSet<T, D> {
+    /// Create a new set holding the whole universe of the domain
+    pub fn full() -> Self {
+        Self::clamped(Self::universe())
+    }
+
+    /// If this set holds the whole universe of the domain
+    pub fn is_full(&self) -> bool {
+        Self::universe().difference(&self.set).items().all(|range| range.is_empty())
+    }
+
+    /// Add given range to this set, the part outside of the universe is ignored
+    pub fn add(&mut self, range: impl Into<Range<T>>) {
+        self.set = self.set.union(&RangeSet::from([range.into()]).intersection(&Self::universe()));
+    }
+
+    /// Returns every value of the universe that's not in this set
+    pub fn invert(&self) -> Self {
+        Self::clamped(Self::universe().difference(&self.set))
+    }
+
+    /// Create an union of this set and given set
+    pub fn union(&self, other: &Self) -> Self {
+        Self::clamped(self.set.union(&other.set))
+    }
+
+    /// Get the intersection of this set and given set
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::clamped(self.set.intersection(&other.set))
+    }
+
+    /// Get the difference of this set with given set
+    pub fn difference(&self, other: &Self) -> Self {
+        Self::clamped(self.set.difference(&other.set))
+    }
+
+    /// The total measure of the ranges in this set, see [`RangeSet::measure`](RangeSet::measure)
+    pub fn measure<L>(&self) -> L
+        where T: Measure<Length=L>, L: Add<Output=L> + Default {
+        self.set.measure().expect("the universe of a domain has to be bounded")
+    }
+
+    /// The amount of values in this set, saturating at `u128::MAX`
+    pub fn count(&self) -> u128
+        where T: Discrete {
+        self.set.items()
+            .filter_map(|range| Some((range.first_value()?, range.last_value()?)))
+            .filter_map(|(first, last)| T::steps_between(&first, &last))
+            .fold(0u128, |total, steps| total.saturating_add(steps).saturating_add(1))
+    }
+
+    fn universe() -> RangeSet<T> {
+        RangeSet::from([D::universe()])
+    }
+
+    fn clamped(set: RangeSet<T>) -> Self {
+        // Set operations can leave empty ranges like `255>..=255` at the edge of the universe
+        let items = set.items.into_iter().filter(|range| !range.is_empty()).collect();
+        BoundedRangeSet { set: RangeSet { items }, domain: PhantomData }
+    }
+}
+
+impl<T: Ord + Clone + Debug, D: Domain<T>> From<RangeSet<T>> for BoundedRangeSet<T, D> {
+    /// Create a set from the part of given set inside of the universe
+    fn from(set: RangeSet<T>) -> Self {
+        Self::clamped(set.intersection(&Self::universe()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, BoundedRangeSet, Domain, Range};
+
+    struct Degrees;
+
+    impl Domain<i32> for Degrees {
+        fn universe() -> Range<i32> {
+            r!(0..360)
+        }
+    }
+
+    #[test]
+    fn bounded() {
+        let set: BoundedRangeSet<u8> = range_set![r!(..10), r!(250..)].into();
+        assert_eq!(&range_set![r!(0..10), r!(250..=255)], set.as_set());
+        assert_eq!(16, set.count());
+        assert_eq!(240, set.invert().count());
+        assert!(set.union(&set.invert()).is_full());
+        assert!(set.intersection(&set.invert()).is_empty());
+        assert_eq!(set, BoundedRangeSet::full().difference(&set.invert()));
+        assert_eq!(256, BoundedRangeSet::<u8>::full().count());
+        assert_eq!(u128::MAX, BoundedRangeSet::<u128>::full().count());
+        assert_eq!(0, BoundedRangeSet::<i8>::new().count());
+    }
+
+    #[test]
+    fn bounded_measure() {
+        let mut set: BoundedRangeSet<i32, Degrees> = BoundedRangeSet::new();
+        set.add(r!(..90));
+        set.add(r!(270..));
+        assert_eq!(180, set.measure());
+        assert_eq!(180, set.invert().measure());
+        assert!(set.contains(&0) && !set.contains(&360));
+    }
+}
@@ -0,0 +1,321 @@
+use std::fmt::Debug;
+use crate::{sort_ranges, Bound, Coalesce, InvalidRangeError, InvalidRangePolicy, Range, RangeSet, UnsortedError};
+use crate::storage::Ranges;
+use crate::coalesce::Touching;
+
+/// Builds a [`RangeSet`](RangeSet) from ranges pushed in order of their start
+///
+/// Overlapping and touching ranges are merged while pushing, so building a set is done in a single linear pass
+///
+/// Inverted ranges are handled according to the [`InvalidRangePolicy`](InvalidRangePolicy) of the builder, which
+/// drops them by default, which ranges are merged is decided by its [`Coalesce`](Coalesce) policy
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, RangeSetBuilder};
+///
+/// let mut builder = RangeSetBuilder::new();
+/// builder.push(r!(0..4));
+/// builder.push(r!(2..6));
+/// builder.push(r!(10..));
+///
+/// assert_eq!(range_set![r!(0..6), r!(10..)], builder.finish());
+/// ```
+#[derive(Debug)]
+pub struct RangeSetBuilder<T: Ord + Debug> {
+    items: Ranges<Range<T>>,
+    last: Option<Range<T>>,
+    policy: InvalidRangePolicy,
+    error: Option<InvalidRangeError<T>>,
+    merges: fn(&Range<T>, &Range<T>) -> bool,
+}
+
+impl<T: Ord + Debug> Default for RangeSetBuilder<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::with_capacity(4)
+    }
+}
+
+impl<T: Ord + Debug> RangeSetBuilder<T> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new builder with room for given amount of ranges
+    pub fn with_capacity(cap: usize) -> Self {
+        RangeSetBuilder {
+            items: Ranges::with_capacity(cap),
+            last: None,
+            policy: InvalidRangePolicy::default(),
+            error: None,
+            merges: Touching::merges,
+        }
+    }
+
+    /// Set the policy used for inverted ranges pushed to this builder
+    ///
+    /// With [`InvalidRangePolicy::Error`](InvalidRangePolicy::Error) the first inverted range is kept and returned
+    /// by [`try_finish`](RangeSetBuilder::try_finish), while the range itself is left out of the set
+    pub fn with_policy(mut self, policy: InvalidRangePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set the policy deciding which ranges are merged, by default ranges that overlap or touch are merged
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, Overlapping, RangeSetBuilder};
+    ///
+    /// let mut builder = RangeSetBuilder::new().with_coalesce(Overlapping);
+    /// builder.push(r!(0..4));
+    /// builder.push(r!(4..6));
+    ///
+    /// assert_eq!(vec![&r!(0..4), &r!(4..6)], builder.finish().items().collect::<Vec<_>>());
+    /// ```
+    pub fn with_coalesce<C: Coalesce<T>>(mut self, _: C) -> Self {
+        self.merges = C::merges;
+        self
+    }
+
+    /// The policy used for inverted ranges pushed to this builder
+    #[inline]
+    pub fn policy(&self) -> InvalidRangePolicy {
+        self.policy
+    }
+
+    /// Push a range to the set being built, returns `true` once the set became unbounded at the end, after which
+    /// every pushed range is ignored
+    ///
+    /// The range may not start before the previously pushed range, this is only checked in debug builds,
+    /// see [`try_push`](RangeSetBuilder::try_push) for a checked variant
+    pub fn push(&mut self, range: impl Into<Range<T>>) -> bool {
+        let range = range.into();
+        if self.last.as_ref().is_some_and(|x| x.end == Bound::Unbounded) {
+            return true;
+        }
+
+        let range = match self.policy.check(range) {
+            Ok(Some(range)) => range,
+            Ok(None) => return false,
+            Err(error) => {
+                self.error.get_or_insert(error);
+                return false;
+            }
+        };
+
+        match self.last.take() {
+            None => self.last = Some(range),
+            Some(mut v) => {
+                debug_assert!(v.start_pos() <= range.start_pos(), "range ({:?}) added to builder is lower than previous range {:?}", range, v);
+                if !(self.merges)(&v, &range) {
+                    self.items.push(v);
+                    self.last = Some(range);
+                } else {
+                    if range.end_pos() > v.end_pos() {
+                        v.end = range.end;
+                    }
+
+                    self.last = Some(v);
+                }
+            }
+        }
+
+        self.last.as_ref().is_some_and(|x| x.end == Bound::Unbounded)
+    }
+
+    /// Push a range to the set being built, like [`push`](RangeSetBuilder::push), but returns an error holding
+    /// the range if it starts before the previously pushed range
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, RangeSetBuilder};
+    ///
+    /// let mut builder = RangeSetBuilder::new();
+    /// assert_eq!(Ok(false), builder.try_push(r!(4..8)));
+    ///
+    /// let err = builder.try_push(r!(2..3)).unwrap_err();
+    /// assert_eq!(&r!(2..3), err.range());
+    /// ```
+    pub fn try_push(&mut self, range: impl Into<Range<T>>) -> Result<bool, UnsortedError<T>> {
+        let range = range.into();
+        if self.last.as_ref().is_some_and(|last| last.start_pos() > range.start_pos()) {
+            return Err(UnsortedError::new(range));
+        }
+
+        Ok(self.push(range))
+    }
+
+    /// Finish building and return the resulting set, or the first inverted range that was pushed if the policy of
+    /// this builder is [`InvalidRangePolicy::Error`](InvalidRangePolicy::Error)
+    pub fn try_finish(mut self) -> Result<RangeSet<T>, InvalidRangeError<T>> {
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => Ok(self.finish()),
+        }
+    }
+
+    /// Finish building and return the resulting set
+    pub fn finish(mut self) -> RangeSet<T> {
+        if let Some(v) = self.last {
+            self.items.push(v);
+        }
+
+        RangeSet { items: self.items.compact() }
+    }
+}
+
+/// Builds a [`RangeSet`](RangeSet) from ranges pushed in any order
+///
+/// The ranges are only collected while pushing, and sorted and merged once when finishing, which is faster than
+/// calling [`add`](RangeSet::add) for every range when loading many ranges of which the order is unknown
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, BufferedBuilder};
+///
+/// let mut builder = BufferedBuilder::new();
+/// builder.push(r!(10..));
+/// builder.push(r!(2..6));
+/// builder.push(r!(0..4));
+///
+/// assert_eq!(range_set![r!(0..6), r!(10..)], builder.finish());
+/// ```
+#[derive(Debug)]
+pub struct BufferedBuilder<T: Ord + Debug> {
+    ranges: Vec<Range<T>>,
+    policy: InvalidRangePolicy,
+}
+
+impl<T: Ord + Debug> Default for BufferedBuilder<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::with_capacity(0)
+    }
+}
+
+impl<T: Ord + Debug> BufferedBuilder<T> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new builder with room for given amount of ranges
+    pub fn with_capacity(cap: usize) -> Self {
+        BufferedBuilder {
+            ranges: Vec::with_capacity(cap),
+            policy: InvalidRangePolicy::default(),
+        }
+    }
+
+    /// Set the policy used for inverted ranges pushed to this builder, see
+    /// [`RangeSetBuilder::with_policy`](RangeSetBuilder::with_policy)
+    pub fn with_policy(mut self, policy: InvalidRangePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// The amount of ranges pushed to this builder
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// If no ranges have been pushed to this builder
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Push a range to the set being built
+    #[inline]
+    pub fn push(&mut self, range: impl Into<Range<T>>) {
+        self.ranges.push(range.into());
+    }
+
+    /// Finish building and return the resulting set, or the first inverted range that was pushed if the policy of
+    /// this builder is [`InvalidRangePolicy::Error`](InvalidRangePolicy::Error)
+    pub fn try_finish(self) -> Result<RangeSet<T>, InvalidRangeError<T>> {
+        self.sorted().try_finish()
+    }
+
+    /// Finish building and return the resulting set
+    pub fn finish(self) -> RangeSet<T> {
+        self.sorted().finish()
+    }
+
+    /// Sort the pushed ranges and feed them to a builder expecting them in order
+    fn sorted(mut self) -> RangeSetBuilder<T> {
+        sort_ranges(&mut self.ranges);
+
+        let mut builder = RangeSetBuilder::with_capacity(self.ranges.len()).with_policy(self.policy);
+        for range in self.ranges {
+            builder.push(range);
+        }
+
+        builder
+    }
+}
+
+impl<T: Ord + Debug> Extend<Range<T>> for BufferedBuilder<T> {
+    fn extend<I: IntoIterator<Item=Range<T>>>(&mut self, iter: I) {
+        self.ranges.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, BufferedBuilder, InvalidRangePolicy, RangeSet, RangeSetBuilder};
+
+    #[test]
+    pub fn builder() {
+        let mut builder = RangeSetBuilder::new();
+        builder.push(r!(..20));
+        builder.push(r!(..4));
+        builder.push(r!(10..));
+        builder.push(r!(50..));
+        let fin = builder.finish();
+
+        assert_eq!(RangeSet::unbound(), fin);
+
+        let mut builder = RangeSetBuilder::new();
+        builder.push(r!(..1));
+        builder.push(r!(4 >..));
+        let fin = builder.finish();
+
+        assert_eq!(fin.items, [r!(..1), r!(4 >..)].into());
+    }
+
+    #[test]
+    pub fn try_push() {
+        let mut builder = RangeSetBuilder::new();
+        assert_eq!(Ok(false), builder.try_push(r!(4..8)));
+        assert_eq!(Ok(false), builder.try_push(r!(4..10)));
+        assert_eq!(r!(3..), builder.try_push(r!(3..)).unwrap_err().into_range());
+        assert_eq!(Ok(true), builder.try_push(r!(10..)));
+
+        assert_eq!(RangeSet::from([r!(4..)]), builder.finish());
+    }
+
+    #[test]
+    pub fn buffered() {
+        let builder = BufferedBuilder::<u32>::new();
+        assert!(builder.is_empty());
+        assert_eq!(RangeSet::empty(), builder.finish());
+
+        let mut builder = BufferedBuilder::with_capacity(4);
+        builder.extend([r!(20..30), r!(5..=9), r!(..2), r!(10..20), r!(7..3)]);
+        assert_eq!(5, builder.len());
+        assert_eq!(RangeSet::from([r!(..2), r!(5..=9), r!(10..30)]), builder.finish());
+
+        let mut builder = BufferedBuilder::new().with_policy(InvalidRangePolicy::Error);
+        builder.extend([r!(20..30), r!(7..3)]);
+        assert_eq!(r!(7..3), builder.try_finish().unwrap_err().into_range());
+    }
+}
@@ -0,0 +1,257 @@
+use std::fmt::{Debug, Formatter};
+use std::ops::{BitAnd, BitOr, Not, Sub};
+use crate::{Bound, Range, RangeSet, RangeSetBuilder};
+
+/// A set of bytes stored as a 256-bit mask, where every set operation is a handful of bitwise operations
+///
+/// This is meant for small domains like byte classes in parsers, convert from and to a
+/// [`RangeSet<u8>`](RangeSet) to combine it with other sets
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, ByteSet};
+///
+/// let digits = ByteSet::from(r!(b'0'..=b'9'));
+/// let hex = digits | ByteSet::from(r!(b'a'..=b'f'));
+///
+/// assert!(hex.contains(b'c'));
+/// assert!(!(hex - digits).contains(b'5'));
+/// assert_eq!(16, hex.len());
+/// assert_eq!(range_set![r!(b'0'..=b'9'), r!(b'a'..=b'f')], hex.to_range_set());
+/// ```
+#[derive(Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct ByteSet {
+    bits: [u64; 4],
+}
+
+impl Debug for ByteSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.ranges()).finish()
+    }
+}
+
+impl ByteSet {
+    /// Create a new set holding no bytes
+    #[inline]
+    pub const fn empty() -> Self {
+        ByteSet { bits: [0; 4] }
+    }
+
+    /// Create a new set holding every byte
+    #[inline]
+    pub const fn unbound() -> Self {
+        ByteSet { bits: [u64::MAX; 4] }
+    }
+
+    /// If this set holds no bytes
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.bits[0] | self.bits[1] | self.bits[2] | self.bits[3] == 0
+    }
+
+    /// If this set holds every byte
+    #[inline]
+    pub const fn is_unbound(&self) -> bool {
+        self.bits[0] & self.bits[1] & self.bits[2] & self.bits[3] == u64::MAX
+    }
+
+    /// The amount of bytes in this set
+    #[inline]
+    pub const fn len(&self) -> usize {
+        (self.bits[0].count_ones() + self.bits[1].count_ones() + self.bits[2].count_ones() + self.bits[3].count_ones()) as usize
+    }
+
+    /// Returns `true` if given byte is in this set
+    #[inline]
+    pub const fn contains(&self, byte: u8) -> bool {
+        self.bits[(byte >> 6) as usize] & (1 << (byte & 63)) != 0
+    }
+
+    /// Add a single byte to this set
+    #[inline]
+    pub fn insert(&mut self, byte: u8) {
+        self.bits[(byte >> 6) as usize] |= 1 << (byte & 63);
+    }
+
+    /// Remove a single byte from this set
+    #[inline]
+    pub fn remove(&mut self, byte: u8) {
+        self.bits[(byte >> 6) as usize] &= !(1 << (byte & 63));
+    }
+
+    /// Add every byte in given range to this set
+    pub fn add(&mut self, range: Range<u8>) {
+        *self = *self | ByteSet::from(range);
+    }
+
+    /// Returns a set holding the bytes in either set
+    #[inline]
+    pub const fn union(&self, other: &Self) -> Self {
+        ByteSet { bits: [self.bits[0] | other.bits[0], self.bits[1] | other.bits[1], self.bits[2] | other.bits[2], self.bits[3] | other.bits[3]] }
+    }
+
+    /// Returns a set holding the bytes in both sets
+    #[inline]
+    pub const fn intersection(&self, other: &Self) -> Self {
+        ByteSet { bits: [self.bits[0] & other.bits[0], self.bits[1] & other.bits[1], self.bits[2] & other.bits[2], self.bits[3] & other.bits[3]] }
+    }
+
+    /// Returns a set holding the bytes in this set but not in the other
+    #[inline]
+    pub const fn difference(&self, other: &Self) -> Self {
+        self.intersection(&other.invert())
+    }
+
+    /// Returns a set holding every byte not in this set
+    #[inline]
+    pub const fn invert(&self) -> Self {
+        ByteSet { bits: [!self.bits[0], !self.bits[1], !self.bits[2], !self.bits[3]] }
+    }
+
+    /// Returns an iterator over all bytes in this set, in order
+    pub fn values(&self) -> impl Iterator<Item=u8> + '_ {
+        (0..=u8::MAX).filter(|byte| self.contains(*byte))
+    }
+
+    /// Returns an iterator over the inclusive ranges of consecutive bytes in this set, in order
+    pub fn ranges(&self) -> impl Iterator<Item=Range<u8>> + '_ {
+        let mut next = Some(0u8);
+        std::iter::from_fn(move || {
+            let mut byte = next?;
+            while !self.contains(byte) {
+                byte = byte.checked_add(1)?;
+            }
+
+            let start = byte;
+            loop {
+                match byte.checked_add(1) {
+                    Some(after) if self.contains(after) => byte = after,
+                    after => {
+                        next = after;
+                        return Some(Range::new(Bound::Included(start), Bound::Included(byte)));
+                    }
+                }
+            }
+        })
+    }
+
+    /// Returns the bytes of this set as a [`RangeSet`](RangeSet) of inclusive ranges
+    pub fn to_range_set(&self) -> RangeSet<u8> {
+        let mut builder = RangeSetBuilder::new();
+        for range in self.ranges() {
+            builder.push(range);
+        }
+
+        builder.finish()
+    }
+}
+
+impl From<Range<u8>> for ByteSet {
+    fn from(range: Range<u8>) -> Self {
+        let (Some(first), Some(last)) = (range.first_value(), range.last_value()) else {
+            return ByteSet::empty();
+        };
+
+        let mut set = ByteSet::empty();
+        if first > last {
+            return set;
+        }
+
+        for (index, word) in set.bits.iter_mut().enumerate() {
+            let low = (index as u32) * 64;
+            let start = (first as u32).max(low);
+            let end = (last as u32).min(low + 63);
+            if start <= end {
+                let width = end - start + 1;
+                let mask = if width == 64 { u64::MAX } else { ((1u64 << width) - 1) << (start - low) };
+                *word |= mask;
+            }
+        }
+
+        set
+    }
+}
+
+impl From<&RangeSet<u8>> for ByteSet {
+    fn from(set: &RangeSet<u8>) -> Self {
+        set.items().fold(ByteSet::empty(), |bytes, range| bytes | ByteSet::from(range.clone()))
+    }
+}
+
+impl From<ByteSet> for RangeSet<u8> {
+    fn from(set: ByteSet) -> Self {
+        set.to_range_set()
+    }
+}
+
+impl FromIterator<u8> for ByteSet {
+    fn from_iter<I: IntoIterator<Item=u8>>(iter: I) -> Self {
+        let mut set = ByteSet::empty();
+        for byte in iter {
+            set.insert(byte);
+        }
+
+        set
+    }
+}
+
+impl BitOr for ByteSet {
+    type Output = ByteSet;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(&rhs)
+    }
+}
+
+impl BitAnd for ByteSet {
+    type Output = ByteSet;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(&rhs)
+    }
+}
+
+impl Sub for ByteSet {
+    type Output = ByteSet;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        self.difference(&rhs)
+    }
+}
+
+impl Not for ByteSet {
+    type Output = ByteSet;
+
+    #[inline]
+    fn not(self) -> Self {
+        self.invert()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, ByteSet, RangeSet};
+
+    #[test]
+    fn byte_set() {
+        let set = ByteSet::from(&range_set![r!(..=3), r!(62..=66), r!(127>..130), r!(250..)]);
+        assert_eq!(range_set![r!(0..=3), r!(62..=66), r!(128..=129), r!(250..=255)], set.to_range_set());
+        assert_eq!(4 + 5 + 2 + 6, set.len());
+        assert!(set.contains(255) && set.contains(0) && !set.contains(127));
+
+        assert!(ByteSet::from(r!(..)).is_unbound());
+        assert!(ByteSet::from(r!(3>..4)).is_empty());
+        assert_eq!(64, ByteSet::from(r!(64..128)).len());
+        assert_eq!(range_set![r!(4..=61), r!(67..=127), r!(130..=249)], (!set).into());
+        assert_eq!(RangeSet::<u8>::empty(), (set - set).into());
+
+        let mut bytes: ByteSet = b"hello".iter().copied().collect();
+        bytes.remove(b'l');
+        bytes.add(r!(b'a'..b'c'));
+        assert_eq!(b"abeho".to_vec(), bytes.values().collect::<Vec<_>>());
+    }
+}
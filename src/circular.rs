@@ -0,0 +1,166 @@
+use std::fmt::Debug;
+use crate::{Range, RangeSet};
+
+/// A set of ranges over a domain that wraps around, like angles, the time of day or sequence numbers
+///
+/// A range that ends before it starts, e.g. `r!(350..10)` on a domain of `0..360`, wraps around the end of the domain
+/// to its start. The ranges are stored as a normal [`RangeSet`](RangeSet) within the domain, which
+/// [`ranges`](CircularRangeSet::ranges) joins up again at the wrap point
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, CircularRangeSet};
+///
+/// let mut heading = CircularRangeSet::new(r!(0..360));
+/// heading.add(r!(350..10));
+///
+/// assert!(heading.contains(&355) && heading.contains(&5));
+/// assert!(!heading.contains(&180));
+/// assert_eq!(vec![r!(350..10)], heading.ranges().collect::<Vec<_>>());
+/// assert_eq!(vec![r!(10..350)], heading.invert().ranges().collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CircularRangeSet<T: Ord> {
+    domain: Range<T>,
+    set: RangeSet<T>,
+}
+
+impl<T: Ord + Debug> CircularRangeSet<T> {
+    /// Create a new empty set over given domain, its end wraps around to its start
+    pub fn new(domain: Range<T>) -> Self {
+        CircularRangeSet { domain, set: RangeSet::empty() }
+    }
+
+    /// The domain this set wraps around in
+    #[inline]
+    pub fn domain(&self) -> &Range<T> {
+        &self.domain
+    }
+
+    /// The ranges in this set without joining them at the wrap point, sorted like a [`RangeSet`](RangeSet)
+    #[inline]
+    pub fn as_set(&self) -> &RangeSet<T> {
+        &self.set
+    }
+
+    /// If this set is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Returns `true` if given value falls within this set
+    pub fn contains(&self, value: &T) -> bool {
+        self.set.contains(value)
+    }
+}
+
+impl<T: Ord + Clone + Debug> CircularRangeSet<T> {
+    /// Create a new set covering the whole domain
+    pub fn full(domain: Range<T>) -> Self {
+        let set = RangeSet::from([domain.clone()]);
+        CircularRangeSet { domain, set }
+    }
+
+    /// If this set covers the whole domain
+    pub fn is_full(&self) -> bool {
+        self.set.items().eq([&self.domain])
+    }
+
+    /// Add given range to this set, a range that ends before it starts wraps around the end of the domain
+    ///
+    /// Parts of the range outside of the domain are ignored
+    pub fn add(&mut self, range: impl Into<Range<T>>) {
+        let range = range.into();
+        let pieces = if range.is_inverted() {
+            RangeSet::from([
+                Range::new(range.start, self.domain.end.clone()),
+                Range::new(self.domain.start.clone(), range.end),
+            ])
+        } else {
+            RangeSet::from([range])
+        };
+
+        self.set = self.set.union(&pieces.intersection(&self.domain_set()));
+    }
+
+    /// Returns the ranges in this set, where the ranges touching the end and start of the domain are joined into
+    /// one wrapping range, which comes last
+    pub fn ranges(&self) -> impl Iterator<Item=Range<T>> + '_ {
+        let items = &self.set.items[..];
+        let wraps = match items {
+            [first, .., last] => first.start == self.domain.start && last.end == self.domain.end,
+            _ => false,
+        };
+
+        let (middle, wrapped) = match (wraps, items) {
+            (true, [first, middle @ .., last]) => (middle, Some(Range::new(last.start.clone(), first.end.clone()))),
+            _ => (items, None),
+        };
+
+        middle.iter().cloned().chain(wrapped)
+    }
+
+    /// Returns the part of the domain that's not in this set
+    pub fn invert(&self) -> Self {
+        self.with_set(self.domain_set().difference(&self.set))
+    }
+
+    /// Create an union of this set and given set, which should have the same domain
+    pub fn union(&self, other: &Self) -> Self {
+        debug_assert_eq!(self.domain, other.domain, "circular sets have different domains");
+        self.with_set(self.set.union(&other.set))
+    }
+
+    /// Get the intersection of this set and given set, which should have the same domain
+    pub fn intersection(&self, other: &Self) -> Self {
+        debug_assert_eq!(self.domain, other.domain, "circular sets have different domains");
+        self.with_set(self.set.intersection(&other.set))
+    }
+
+    /// Get the difference of this set with given set, which should have the same domain
+    pub fn difference(&self, other: &Self) -> Self {
+        debug_assert_eq!(self.domain, other.domain, "circular sets have different domains");
+        self.with_set(self.set.difference(&other.set))
+    }
+
+    fn domain_set(&self) -> RangeSet<T> {
+        RangeSet::from([self.domain.clone()])
+    }
+
+    fn with_set(&self, set: RangeSet<T>) -> Self {
+        CircularRangeSet { domain: self.domain.clone(), set }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, CircularRangeSet};
+
+    #[test]
+    fn circular() {
+        let mut minutes = CircularRangeSet::new(r!(0..1440));
+        minutes.add(r!(1380..60));
+        minutes.add(r!(600..720));
+        minutes.add(r!(2000..3000));
+        assert_eq!(&range_set![r!(0..60), r!(600..720), r!(1380..1440)], minutes.as_set());
+        assert_eq!(vec![r!(600..720), r!(1380..60)], minutes.ranges().collect::<Vec<_>>());
+
+        let mut night = CircularRangeSet::new(r!(0..1440));
+        night.add(r!(1320..30));
+        assert_eq!(vec![r!(1380..30)], minutes.intersection(&night).ranges().collect::<Vec<_>>());
+        assert_eq!(vec![r!(600..720), r!(1320..60)], minutes.union(&night).ranges().collect::<Vec<_>>());
+        assert_eq!(vec![r!(30..60), r!(600..720)], minutes.difference(&night).ranges().collect::<Vec<_>>());
+        assert_eq!(vec![r!(60..600), r!(720..1380)], minutes.invert().ranges().collect::<Vec<_>>());
+
+        assert!(CircularRangeSet::full(r!(0..360)).is_full());
+        assert!(CircularRangeSet::full(r!(0..360)).invert().is_empty());
+        assert_eq!(vec![r!(0..360)], CircularRangeSet::full(r!(0..360)).ranges().collect::<Vec<_>>());
+
+        let mut sequence = CircularRangeSet::new(r!(..));
+        sequence.add(r!((u32::MAX - 5)..10));
+        assert!(sequence.contains(&u32::MAX) && sequence.contains(&0) && !sequence.contains(&10));
+        assert_eq!(vec![r!((u32::MAX - 5)..10)], sequence.ranges().collect::<Vec<_>>());
+    }
+}
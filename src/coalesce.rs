@@ -0,0 +1,161 @@
+use std::fmt::Debug;
+use crate::{Discrete, Range, RangeSet, RangeSetBuilder};
+
+/// Decides which ranges are merged into one when building a set
+///
+/// Sets merge [`Touching`](Touching) ranges by default, other policies can be given to the builders with
+/// [`RangeSetBuilder::with_coalesce`](RangeSetBuilder::with_coalesce), or per operation with
+/// [`RangeSet::coalesced`](RangeSet::coalesced) and [`RangeSet::add_coalescing`](RangeSet::add_coalescing)
+pub trait Coalesce<T: Ord> {
+    /// Returns `true` if `next` should be merged into `previous`, `next` never starts before `previous`
+    fn merges(previous: &Range<T>, next: &Range<T>) -> bool;
+}
+
+/// Only merge ranges that share at least one point, e.g. `0..5` and `4..8`, but not `0..5` and `5..8`
+///
+/// Touching ranges kept apart this way hold the same values as the range they form together, so the set equals that
+/// range and [`invert`](RangeSet::invert) leaves no gap between them, other operations like
+/// [`difference`](RangeSet::difference) return sets merged by the default [`Touching`](Touching) policy
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Overlapping;
+
+impl<T: Ord> Coalesce<T> for Overlapping {
+    #[inline]
+    fn merges(previous: &Range<T>, next: &Range<T>) -> bool {
+        previous.end_pos().cmp_by(&next.start_pos(), |a, b| a.cmp(b)).is_gt()
+    }
+}
+
+/// Merge ranges that overlap or touch without a gap between them, e.g. `0..5` and `5..8`, this is the default
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Touching;
+
+impl<T: Ord> Coalesce<T> for Touching {
+    #[inline]
+    fn merges(previous: &Range<T>, next: &Range<T>) -> bool {
+        previous.end_pos().cmp_by(&next.start_pos(), |a, b| a.cmp(b)).is_ge()
+    }
+}
+
+/// Merge ranges that touch or that have no values between them, e.g. `0..=4` and `5..8` for integers
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Adjacent;
+
+impl<T: Discrete + Clone> Coalesce<T> for Adjacent {
+    fn merges(previous: &Range<T>, next: &Range<T>) -> bool {
+        if Touching::merges(previous, next) {
+            return true;
+        }
+
+        let after = previous.last_value().and_then(|last| last.successor());
+        after.is_some_and(|after| next.first_value().is_some_and(|first| first <= after))
+    }
+}
+
+impl<T: Ord + Debug> RangeSet<T> {
+    /// Add given range to this set, merging ranges according to given policy
+    ///
+    /// Every range of this set is merged again, so this also merges ranges that were kept apart before
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, Adjacent};
+    ///
+    /// let mut set = range_set![r!(..=4)];
+    /// set.add(r!(5..6));
+    /// assert_eq!(2, set.items().count());
+    ///
+    /// set.add_coalescing(r!(6..=8), Adjacent);
+    /// assert_eq!(range_set![r!(..=8)], set);
+    /// ```
+    pub fn add_coalescing<C: Coalesce<T>>(&mut self, range: impl Into<Range<T>>, policy: C) {
+        let range = range.into();
+        let mut builder = RangeSetBuilder::with_capacity(self.items.len() + 1).with_coalesce(policy);
+        let mut range = Some(range);
+
+        for item in self.items.take() {
+            if range.as_ref().is_some_and(|range| range.start_pos() < item.start_pos()) {
+                if let Some(range) = range.take() {
+                    builder.push(range);
+                }
+            }
+
+            builder.push(item);
+        }
+
+        if let Some(range) = range {
+            builder.push(range);
+        }
+
+        self.items = builder.finish().items;
+    }
+}
+
+impl<T: Ord + Clone + Debug> RangeSet<T> {
+    /// Returns this set with its ranges merged according to given policy
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, Adjacent};
+    ///
+    /// let set = range_set![r!(0..=3), r!(4..=6), r!(8..10)];
+    ///
+    /// assert_eq!(range_set![r!(0..=6), r!(8..10)], set.coalesced(Adjacent));
+    /// ```
+    pub fn coalesced<C: Coalesce<T>>(&self, policy: C) -> RangeSet<T> {
+        let mut builder = RangeSetBuilder::with_capacity(self.items.len()).with_coalesce(policy);
+        for item in self.items() {
+            builder.push(item.clone());
+        }
+
+        builder.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, Adjacent, Overlapping, RangeSetBuilder, Touching};
+
+    #[test]
+    fn coalesce() {
+        let ranges = [r!(0..5), r!(5..=8), r!(9..10), r!(9>..12), r!(11..20)];
+        let build = |builder: RangeSetBuilder<i32>| {
+            let mut builder = builder;
+            for range in ranges.clone() {
+                builder.push(range);
+            }
+
+            builder.finish().items().cloned().collect::<Vec<_>>()
+        };
+
+        assert_eq!(vec![r!(0..5), r!(5..=8), r!(9..20)], build(RangeSetBuilder::new().with_coalesce(Overlapping)));
+        assert_eq!(vec![r!(0..=8), r!(9..20)], build(RangeSetBuilder::new().with_coalesce(Touching)));
+        assert_eq!(vec![r!(0..20)], build(RangeSetBuilder::new().with_coalesce(Adjacent)));
+
+        let mut set = range_set![r!(0..=4u8), r!(10..)];
+        set.add_coalescing(r!(5..=9), Overlapping);
+        assert_eq!(3, set.items().count());
+        assert_eq!(range_set![r!(0..)], set.coalesced(Adjacent));
+        assert_eq!(range_set![r!(..)], range_set![r!(..=254u8), r!(254>..)].coalesced(Adjacent));
+
+        let mut set = range_set![r!(0..5)];
+        set.add_coalescing(r!(5..=8), Overlapping);
+        assert_eq!(vec![&r!(0..5), &r!(5..=8)], set.items().collect::<Vec<_>>());
+        assert_eq!(range_set![r!(0..=8)], set);
+        assert_eq!(vec![&r!(..0), &r!(8>..)], set.invert().items().collect::<Vec<_>>());
+        assert_eq!(vec![&r!(0..4), &r!(6..=8)], set.difference(&range_set![r!(4..6)]).items().collect::<Vec<_>>());
+        assert_eq!(vec![&r!(4..6)], set.intersection(&range_set![r!(4..6)]).items().collect::<Vec<_>>());
+
+        let mut set = range_set![r!(..=4)];
+        set.add(r!(4>..));
+        assert_eq!(vec![&r!(..)], set.items().collect::<Vec<_>>());
+
+        let mut set = range_set![r!(..=4)];
+        set.add_coalescing(r!(4>..), Overlapping);
+        assert_eq!(vec![&r!(..=4), &r!(4>..)], set.items().collect::<Vec<_>>());
+        assert_eq!(range_set![r!(..)], set);
+        assert!(set.invert().is_empty());
+    }
+}
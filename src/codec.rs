@@ -0,0 +1,211 @@
+use std::fmt::Debug;
+use std::io::{self, Read, Write};
+use crate::{Bound, InvalidRangePolicy, Range, RangeSet, RangeSetBuilder};
+
+/// A type that can be written to and read from a byte stream, used by [`RangeSet::write_to`](RangeSet::write_to)
+/// and [`RangeSet::read_from`](RangeSet::read_from)
+///
+/// Implemented for all integer types and `char`, which are encoded little-endian in their full width so the encoding
+/// is the same on every platform, `usize` and `isize` are always encoded in 8 bytes
+pub trait Codec: Sized {
+    /// Write this value to given writer
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+
+    /// Read a value from given reader
+    fn decode<R: Read>(reader: &mut R) -> io::Result<Self>;
+}
+
+macro_rules! impl_codec {
+    ($($ty:ty => $repr:ty),*) => {
+        $(
+            impl Codec for $ty {
+                #[inline]
+                fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+                    writer.write_all(&(*self as $repr).to_le_bytes())
+                }
+
+                #[inline]
+                fn decode<R: Read>(reader: &mut R) -> io::Result<Self> {
+                    let mut bytes = [0; std::mem::size_of::<$repr>()];
+                    reader.read_exact(&mut bytes)?;
+                    <$ty>::try_from(<$repr>::from_le_bytes(bytes)).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+                }
+            }
+        )*
+    };
+}
+
+impl_codec!(u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => u64);
+impl_codec!(i8 => i8, i16 => i16, i32 => i32, i64 => i64, i128 => i128, isize => i64);
+
+impl Codec for char {
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as u32).encode(writer)
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> io::Result<Self> {
+        char::from_u32(u32::decode(reader)?).ok_or_else(|| invalid_data("invalid char"))
+    }
+}
+
+/// The bytes every encoded set starts with
+const MAGIC: [u8; 4] = *b"RSET";
+/// The version of the format written, sets written in another version are rejected. Header fields that readers can
+/// skip are added without changing it, other changes that can't be skipped set a required flag instead
+const VERSION: u8 = 1;
+/// The flags in the low nibble change how the ranges are read, readers reject sets with flags they don't know there,
+/// flags in the high nibble can be ignored by readers that don't know them
+const REQUIRED_FLAGS: u8 = 0x0f;
+
+const UNBOUNDED: u8 = 0;
+const INCLUDED: u8 = 1;
+const EXCLUDED: u8 = 2;
+
+fn invalid_data(reason: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason.to_string())
+}
+
+fn encode_bound<T: Codec, W: Write>(bound: &Bound<T>, writer: &mut W) -> io::Result<()> {
+    match bound {
+        Bound::Unbounded => writer.write_all(&[UNBOUNDED]),
+        Bound::Included(value) => {
+            writer.write_all(&[INCLUDED])?;
+            value.encode(writer)
+        }
+        Bound::Excluded(value) => {
+            writer.write_all(&[EXCLUDED])?;
+            value.encode(writer)
+        }
+    }
+}
+
+fn decode_bound<T: Codec, R: Read>(reader: &mut R) -> io::Result<Bound<T>> {
+    match u8::decode(reader)? {
+        UNBOUNDED => Ok(Bound::Unbounded),
+        INCLUDED => Ok(Bound::Included(T::decode(reader)?)),
+        EXCLUDED => Ok(Bound::Excluded(T::decode(reader)?)),
+        kind => Err(invalid_data(format!("unknown kind of bound {}", kind))),
+    }
+}
+
+impl<T: Ord + Debug + Codec> RangeSet<T> {
+    /// Write this set to given writer one range at a time, without building the encoded set in memory first
+    ///
+    /// The set is written as a header followed by the amount of ranges and the ranges, each bound as a byte for its
+    /// kind followed by its value when it has one. The header holds the bytes `RSET`, the version of the format, a
+    /// byte of flags and the length of any header fields added later on, which older readers skip. The
+    /// writer isn't buffered, so wrap it in a [`BufWriter`](std::io::BufWriter) when writing to a file or socket
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, RangeSet};
+    ///
+    /// let set = range_set![r!(0u32..4), r!(10>..)];
+    /// let mut bytes = vec![];
+    /// set.write_to(&mut bytes).unwrap();
+    ///
+    /// assert_eq!(set, RangeSet::read_from(&bytes[..]).unwrap());
+    /// ```
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[VERSION, 0])?;
+        0u32.encode(&mut writer)?;
+
+        (self.items.len() as u64).encode(&mut writer)?;
+        for range in self.items.iter() {
+            encode_bound(&range.start, &mut writer)?;
+            encode_bound(&range.end, &mut writer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Read a set written by [`write_to`](RangeSet::write_to) from given reader one range at a time
+    ///
+    /// Returns an error with [`InvalidData`](io::ErrorKind::InvalidData) if the data isn't an encoded set, is written in
+    /// another version of the format, uses unknown required flags, or if the ranges aren't sorted or are inverted.
+    /// Header fields and optional flags this version doesn't know are skipped.
+    /// The reader isn't buffered, so wrap it in a [`BufReader`](std::io::BufReader) when reading from a file or
+    /// socket
+    pub fn read_from<R: Read>(mut reader: R) -> io::Result<RangeSet<T>> {
+        let mut header = [0; 6];
+        reader.read_exact(&mut header)?;
+        let [magic @ .., version, flags] = header;
+        if magic != MAGIC {
+            return Err(invalid_data("not an encoded range set"));
+        }
+
+        if version != VERSION {
+            return Err(invalid_data(format!("range set is encoded in version {}, only {} is supported", version, VERSION)));
+        }
+
+        if flags & REQUIRED_FLAGS != 0 {
+            return Err(invalid_data(format!("range set is encoded with unknown flags {:#04x}", flags & REQUIRED_FLAGS)));
+        }
+
+        // Skip over the header fields added later on
+        let extra = u32::decode(&mut reader)? as u64;
+        if io::copy(&mut reader.by_ref().take(extra), &mut io::sink())? < extra {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        let count = u64::decode(&mut reader)?;
+
+        // The count isn't trusted for the initial capacity, the ranges have to actually be there
+        let mut builder = RangeSetBuilder::with_capacity(count.min(1024) as usize).with_policy(InvalidRangePolicy::Error);
+        for _ in 0..count {
+            let range = Range::new(decode_bound(&mut reader)?, decode_bound(&mut reader)?);
+            builder.try_push(range).map_err(invalid_data)?;
+        }
+
+        builder.try_finish().map_err(invalid_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+    use crate::{r, range_set, RangeSet};
+
+    const HEADER: &[u8] = b"RSET\x01\x00\x00\x00\x00\x00";
+
+    #[test]
+    fn codec() {
+        let set = range_set![r!(..-4i16), r!(-2..=0), r!(1>..3), r!(5>..)];
+        let mut bytes = vec![];
+        set.write_to(&mut bytes).unwrap();
+        assert_eq!(10 + 8 + 1 + (1 + 2) + 2 * (1 + 2 + 1 + 2) + (1 + 2) + 1, bytes.len());
+        assert_eq!(set, RangeSet::read_from(&bytes[..]).unwrap());
+
+        let mut bytes = vec![];
+        RangeSet::<char>::empty().write_to(&mut bytes).unwrap();
+        assert_eq!(b"RSET\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00", &bytes[..]);
+        assert_eq!(RangeSet::<char>::empty(), RangeSet::read_from(&bytes[..]).unwrap());
+
+        let error = |ranges: &[u8]| RangeSet::<u8>::read_from(&[HEADER, ranges].concat()[..]).unwrap_err().kind();
+        assert_eq!(ErrorKind::UnexpectedEof, error(&[1, 0, 0, 0, 0, 0, 0, 0, 1, 4]));
+        assert_eq!(ErrorKind::InvalidData, error(&[1, 0, 0, 0, 0, 0, 0, 0, 3, 4, 0]));
+        assert_eq!(ErrorKind::InvalidData, error(&[1, 0, 0, 0, 0, 0, 0, 0, 1, 4, 1, 2]));
+        assert_eq!(ErrorKind::InvalidData, error(&[2, 0, 0, 0, 0, 0, 0, 0, 1, 4, 0, 1, 2, 2, 3]));
+        assert_eq!(ErrorKind::InvalidData, RangeSet::<char>::read_from(&[HEADER, &[1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0xd8, 0, 0, 0]].concat()[..]).unwrap_err().kind());
+    }
+
+    #[test]
+    fn codec_header() {
+        let ranges: &[u8] = &[1, 0, 0, 0, 0, 0, 0, 0, 1, 4, 0];
+        let read = |header: &[u8]| RangeSet::<u8>::read_from(&[header, ranges].concat()[..]);
+        assert_eq!(range_set![r!(4..)], read(HEADER).unwrap());
+
+        // Unknown header fields and optional flags are skipped, other versions are rejected
+        assert_eq!(range_set![r!(4..)], read(b"RSET\x01\x10\x03\x00\x00\x00abc").unwrap());
+        assert_eq!(ErrorKind::InvalidData, read(b"RSET\x02\x00\x00\x00\x00\x00").unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidData, read(b"RSET\x07\x00\x02\x00\x00\x00ab").unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidData, read(b"RSET\x00\x00\x00\x00\x00\x00").unwrap_err().kind());
+        assert_eq!(ErrorKind::UnexpectedEof, read(b"RSET\x01\x00\xff\x00\x00\x00abc").unwrap_err().kind());
+
+        assert_eq!(ErrorKind::InvalidData, read(b"RSEX\x01\x00\x00\x00\x00\x00").unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidData, read(b"RSET\x02\x01\x00\x00\x00\x00").unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidData, read(b"RSET\x01\x01\x00\x00\x00\x00").unwrap_err().kind());
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt::Debug;
+use std::iter::{FusedIterator, Map};
+use crate::{Bound, Discrete, DiscreteItem, Range, RangeSet};
+
+/// Group runs of items into inclusive ranges from the first to the last item of every run, an item continues the run
+/// of the item before it when `adjacent(previous, item)` returns `true`
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{group_ranges_by, r, RangeSet};
+///
+/// // Events closer than 5ms to the previous one belong to the same session
+/// let events = [100u64, 102, 106, 120, 121, 140];
+/// let sessions = RangeSet::from(group_ranges_by(events, |previous, next| next - previous < 5).collect::<Vec<_>>());
+///
+/// assert_eq!(RangeSet::from([r!(100..=106), r!(120..=121), r!(140..=140)]), sessions);
+/// ```
+pub fn group_ranges_by<I, F>(iter: I, adjacent: F) -> GroupRanges<I::IntoIter, F>
+    where I: IntoIterator, I::Item: Clone, F: FnMut(&I::Item, &I::Item) -> bool {
+    GroupRanges { iter: iter.into_iter(), adjacent, pending: None }
+}
+
+/// An iterator grouping runs of adjacent items into ranges, see [`group_ranges_by`](group_ranges_by)
+pub struct GroupRanges<I: Iterator, F> {
+    iter: I,
+    adjacent: F,
+    /// The item that ended the previous run, which starts the next one
+    pending: Option<I::Item>,
+}
+
+impl<I: Iterator, F: FnMut(&I::Item, &I::Item) -> bool> Iterator for GroupRanges<I, F> where I::Item: Clone {
+    type Item = Range<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.pending.take() {
+            Some(first) => first,
+            None => self.iter.next()?,
+        };
+
+        let mut last = first.clone();
+        for item in self.iter.by_ref() {
+            if !(self.adjacent)(&last, &item) {
+                self.pending = Some(item);
+                break;
+            }
+
+            last = item;
+        }
+
+        Some(Range::new(Bound::Included(first), Bound::Included(last)))
+    }
+}
+
+impl<I: FusedIterator, F: FnMut(&I::Item, &I::Item) -> bool> FusedIterator for GroupRanges<I, F> where I::Item: Clone {}
+
+/// An extension trait for iterators over discrete values, grouping runs of consecutive values into ranges
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, CollectRanges};
+///
+/// let ids = vec![1u32, 2, 3, 7, 8, 10];
+///
+/// assert_eq!(vec![r!(1..=3), r!(7..=8), r!(10..=10)], ids.iter().collect_ranges().collect::<Vec<_>>());
+/// assert_eq!(range_set![r!(1..=3), r!(7..=8), r!(10..=10)], ids.into_iter().collect_range_set());
+/// ```
+pub trait CollectRanges: Iterator + Sized {
+    /// Group runs of consecutive values into inclusive ranges
+    ///
+    /// The values should be sorted, repeated values are allowed, a value that doesn't continue the current run
+    /// starts a new range
+    fn collect_ranges(self) -> ConsecutiveRanges<Self>
+        where Self::Item: DiscreteItem {
+        let into_value = DiscreteItem::into_value as fn(Self::Item) -> <Self::Item as DiscreteItem>::Value;
+        ConsecutiveRanges { inner: group_ranges_by(self.map(into_value), consecutive) }
+    }
+
+    /// Collect the values into a set, the values don't have to be sorted
+    fn collect_range_set(self) -> RangeSet<<Self::Item as DiscreteItem>::Value>
+        where Self::Item: DiscreteItem, <Self::Item as DiscreteItem>::Value: Debug {
+        RangeSet::from(self.collect_ranges().collect::<Vec<_>>())
+    }
+}
+
+impl<I: Iterator> CollectRanges for I {}
+
+/// An iterator grouping runs of consecutive values into ranges, see [`collect_ranges`](CollectRanges::collect_ranges)
+#[allow(clippy::type_complexity)]
+pub struct ConsecutiveRanges<I: Iterator> where I::Item: DiscreteItem {
+    inner: GroupRanges<Map<I, fn(I::Item) -> <I::Item as DiscreteItem>::Value>, fn(&<I::Item as DiscreteItem>::Value, &<I::Item as DiscreteItem>::Value) -> bool>,
+}
+
+impl<I: Iterator> Iterator for ConsecutiveRanges<I> where I::Item: DiscreteItem {
+    type Item = Range<<I::Item as DiscreteItem>::Value>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<I: FusedIterator> FusedIterator for ConsecutiveRanges<I> where I::Item: DiscreteItem {}
+
+/// If `next` continues a run of consecutive values ending at `last`, repeated values continue a run as well
+fn consecutive<T: Discrete>(last: &T, next: &T) -> bool {
+    next == last || last.successor().as_ref() == Some(next)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{group_ranges_by, r, range_set, CollectRanges, Range};
+
+    #[test]
+    fn collect_ranges() {
+        let empty: Vec<u8> = vec![];
+        assert_eq!(0, empty.iter().collect_ranges().count());
+
+        let values = [0u8, 0, 1, 2, 4, 254, 255];
+        assert_eq!(vec![r!(0..=2), r!(4..=4), r!(254..=255)], values.iter().collect_ranges().collect::<Vec<Range<u8>>>());
+
+        let values = [5i32, 6, 1, 2, 3, -1];
+        assert_eq!(vec![r!(5..=6), r!(1..=3), r!(-1..=-1)], values.into_iter().collect_ranges().collect::<Vec<_>>());
+        assert_eq!(range_set![r!(-1..=-1), r!(1..=3), r!(5..=6)], values.iter().collect_range_set());
+    }
+
+    #[test]
+    fn group_by() {
+        assert_eq!(0, group_ranges_by(Vec::<u32>::new(), |_, _| true).count());
+
+        let pages = [0u32, 100, 4095, 4096, 9000, 12000];
+        let grouped: Vec<_> = group_ranges_by(pages, |previous, next| previous / 4096 == next / 4096).collect();
+        assert_eq!(vec![r!(0..=4095), r!(4096..=4096), r!(9000..=12000)], grouped);
+
+        let words = ["apple", "avocado", "banana", "cherry", "coconut"];
+        let grouped: Vec<_> = group_ranges_by(words, |previous, next| previous[..1] == next[..1]).collect();
+        assert_eq!(vec![r!("apple"..="avocado"), r!("banana"..="banana"), r!("cherry"..="coconut")], grouped);
+    }
+}
@@ -0,0 +1,300 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::ops::RangeBounds;
+use crate::{holds_values, max_end, max_start, min_end, min_start, PositionalBound, Range, RangeSet, RangeSetRef};
+use crate::Bound::Unbounded;
+
+/// A read-only view over the union of any amount of sets
+///
+/// The ranges of the union are merged on the fly while iterating, so combining sets doesn't build a new set. The
+/// union of no sets is empty
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, UnionView};
+///
+/// let base = range_set![r!(0..10), r!(30..40)];
+/// let patch = range_set![r!(5..20)];
+/// let overlay = range_set![r!(20..25)];
+/// let union = UnionView::new([base.as_ref(), patch.as_ref(), overlay.as_ref()]);
+///
+/// assert!(union.contains(&15));
+/// assert!(!union.contains(&27));
+/// assert_eq!(vec![r!((&0)..&25), r!((&30)..&40)], union.items().collect::<Vec<_>>());
+/// assert_eq!(vec![r!((&0)..&25)], union.overlapping(r!(22..28)).collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct UnionView<'a, T: Ord> {
+    sets: Vec<RangeSetRef<'a, T>>,
+}
+
+/// A read-only view over the intersection of any amount of sets
+///
+/// The ranges of the intersection are found on the fly while iterating, so combining sets doesn't build a new set.
+/// The intersection of no sets holds everything
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set};
+///
+/// let open = range_set![r!(8..12), r!(13..18)];
+/// let staffed = range_set![r!(9..17)];
+/// let intersection = open.intersection_view(&staffed);
+///
+/// assert!(intersection.contains(&10));
+/// assert!(!intersection.contains(&12));
+/// assert_eq!(vec![r!((&9)..&12), r!((&13)..&17)], intersection.items().collect::<Vec<_>>());
+/// assert_eq!(open.intersection(&staffed), intersection.to_range_set());
+/// ```
+#[derive(Debug, Clone)]
+pub struct IntersectionView<'a, T: Ord> {
+    sets: Vec<RangeSetRef<'a, T>>,
+}
+
+impl<T: Ord + Debug> RangeSet<T> {
+    /// Returns a view over the union of this set and given set
+    pub fn union_view<'a>(&'a self, other: &'a RangeSet<T>) -> UnionView<'a, T> {
+        UnionView::new([self.as_ref(), other.as_ref()])
+    }
+
+    /// Returns a view over the intersection of this set and given set
+    pub fn intersection_view<'a>(&'a self, other: &'a RangeSet<T>) -> IntersectionView<'a, T> {
+        IntersectionView::new([self.as_ref(), other.as_ref()])
+    }
+}
+
+impl<'a, T: Ord + Debug> UnionView<'a, T> {
+    /// Create a view over the union of given sets
+    pub fn new<I: IntoIterator<Item=RangeSetRef<'a, T>>>(sets: I) -> Self {
+        UnionView { sets: sets.into_iter().collect() }
+    }
+
+    /// If none of the sets hold anything
+    pub fn is_empty(&self) -> bool {
+        self.sets.iter().all(|set| set.is_empty())
+    }
+
+    /// Returns an iterator over the ranges in the union
+    pub fn items(&self) -> impl Iterator<Item=Range<&'a T>> {
+        Union { slices: self.sets.iter().map(|set| set.as_slice()).collect() }
+    }
+
+    /// Check if `other` is inside any of the sets
+    pub fn contains<Q: Ord + ?Sized>(&self, other: &Q) -> bool where T: Borrow<Q> {
+        self.sets.iter().any(|set| set.contains(other))
+    }
+
+    /// Returns an iterator over the ranges in the union that overlap with given range
+    pub fn overlapping(&self, range: impl RangeBounds<T>) -> impl Iterator<Item=Range<&'a T>> {
+        let start = PositionalBound::Start(range.start_bound());
+        let mut slices: Vec<_> = if holds_values(range.start_bound(), range.end_bound()) {
+            self.sets.iter().map(|set| &set.as_slice()[set.as_slice().partition_point(|item| item.end_pos() <= start)..]).collect()
+        } else {
+            Vec::new()
+        };
+
+        // Ranges ending before the window still belong to the union range reaching into it when they connect to it
+        while let Some(reach) = slices.iter().filter_map(|slice| slice.first()).map(Range::start).reduce(min_start) {
+            let mut extended = false;
+            for (set, slice) in self.sets.iter().zip(slices.iter_mut()) {
+                let items = set.as_slice();
+                let mut index = items.len() - slice.len();
+                while index > 0 && PositionalBound::connects(items[index - 1].end(), reach) {
+                    index -= 1;
+                    extended = true;
+                }
+
+                *slice = &items[index..];
+            }
+
+            if !extended {
+                break;
+            }
+        }
+
+        Union { slices }.take_while(move |item| PositionalBound::Start(item.start) < PositionalBound::End(range.end_bound()))
+    }
+
+    /// Copy the union into an owned set
+    pub fn to_range_set(&self) -> RangeSet<T> where T: Clone {
+        to_range_set(self.items())
+    }
+}
+
+impl<'a, T: Ord + Debug> IntersectionView<'a, T> {
+    /// Create a view over the intersection of given sets
+    pub fn new<I: IntoIterator<Item=RangeSetRef<'a, T>>>(sets: I) -> Self {
+        IntersectionView { sets: sets.into_iter().collect() }
+    }
+
+    /// If the sets have nothing in common
+    pub fn is_empty(&self) -> bool {
+        self.items().next().is_none()
+    }
+
+    /// Returns an iterator over the ranges in the intersection
+    pub fn items(&self) -> impl Iterator<Item=Range<&'a T>> {
+        Intersection::new(self.sets.iter().map(|set| set.as_slice()).collect())
+    }
+
+    /// Check if `other` is inside all of the sets
+    pub fn contains<Q: Ord + ?Sized>(&self, other: &Q) -> bool where T: Borrow<Q> {
+        self.sets.iter().all(|set| set.contains(other))
+    }
+
+    /// Returns an iterator over the ranges in the intersection that overlap with given range
+    pub fn overlapping(&self, range: impl RangeBounds<T>) -> impl Iterator<Item=Range<&'a T>> {
+        let window = Range::new(range.start_bound(), range.end_bound());
+        let holds = holds_values(window.start, window.end);
+
+        // A range of the intersection lies within a range of every set, so the ranges overlapping the window are
+        // found by only intersecting the ranges of the sets that overlap it
+        let slices = self.sets.iter().map(|set| set.overlapping_slice(window.clone())).collect();
+        Intersection::new(slices).filter(move |_| holds)
+    }
+
+    /// Copy the intersection into an owned set
+    pub fn to_range_set(&self) -> RangeSet<T> where T: Clone {
+        to_range_set(self.items())
+    }
+}
+
+fn to_range_set<'a, T: Ord + Clone + 'a>(items: impl Iterator<Item=Range<&'a T>>) -> RangeSet<T> {
+    RangeSet {
+        items: items
+            .map(|range| Range::new(range.start.cloned(), range.end.cloned()))
+            .collect(),
+    }
+}
+
+/// Merges the sorted ranges of multiple sets
+struct Union<'a, T> {
+    slices: Vec<&'a [Range<T>]>,
+}
+
+impl<'a, T: Ord> Iterator for Union<'a, T> {
+    type Item = Range<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Start with the range that starts first, and keep extending it with the ranges connecting to it
+        let first = self.slices.iter_mut()
+            .filter(|slice| !slice.is_empty())
+            .reduce(|first, slice| if min_start(first[0].start(), slice[0].start()) == first[0].start() { first } else { slice })?;
+
+        let (range, rest) = first.split_first()?;
+        *first = rest;
+
+        let (start, mut end) = (range.start(), range.end());
+        let mut extended = true;
+        while extended {
+            extended = false;
+            for slice in self.slices.iter_mut() {
+                while let Some((range, rest)) = slice.split_first().filter(|(range, _)| PositionalBound::connects(end, range.start())) {
+                    end = max_end(end, range.end());
+                    *slice = rest;
+                    extended = true;
+                }
+            }
+        }
+
+        Some(Range::new(start, end))
+    }
+}
+
+/// Finds the overlap between the sorted ranges of multiple sets
+struct Intersection<'a, T> {
+    slices: Vec<&'a [Range<T>]>,
+    /// The intersection of no sets holds everything, which is only yielded once
+    unbound: bool,
+}
+
+impl<'a, T> Intersection<'a, T> {
+    fn new(slices: Vec<&'a [Range<T>]>) -> Self {
+        Intersection { unbound: slices.is_empty(), slices }
+    }
+}
+
+impl<'a, T: Ord> Iterator for Intersection<'a, T> {
+    type Item = Range<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slices.is_empty() {
+            return std::mem::take(&mut self.unbound).then(Range::unbound);
+        }
+
+        loop {
+            let (mut start, mut end) = (Unbounded, Unbounded);
+            for slice in &self.slices {
+                let range = slice.first()?;
+                start = max_start(start, range.start());
+                end = min_end(end, range.end());
+            }
+
+            // The ranges ending first can't overlap with anything after them
+            for slice in self.slices.iter_mut() {
+                if slice[0].end() == end {
+                    *slice = &slice[1..];
+                }
+            }
+
+            if PositionalBound::cmp_start_end(start, end).is_lt() {
+                return Some(Range::new(start, end));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, IntersectionView, RangeSet, UnionView};
+
+    #[test]
+    fn union_view() {
+        let left = range_set![r!(..0), r!(4..8), r!(20..30)];
+        let middle = range_set![r!(8..10), r!(12..=14), r!(30..)];
+        let right = range_set![r!(9..12), r!(16..18)];
+        let union = UnionView::new([left.as_ref(), middle.as_ref(), right.as_ref()]);
+        assert!(!union.is_empty());
+        assert!(union.contains(&-5) && union.contains(&11) && union.contains(&50));
+        assert!(!union.contains(&2) && !union.contains(&15) && !union.contains(&18));
+        assert_eq!(left.union(&middle).union(&right), union.to_range_set());
+        assert_eq!(vec![r!((&4)..=&14), r!((&16)..&18)], union.overlapping(r!(13..17)).collect::<Vec<_>>());
+        assert_eq!(union.items().collect::<Vec<_>>(), union.overlapping(r!(..)).collect::<Vec<_>>());
+        assert_eq!(0, union.overlapping(r!(13..13)).count());
+
+        let set = union.to_range_set();
+        for window in [r!(..-5), r!(5..6), r!(10..11), r!(14>..16), r!(18..=20), r!(29..), r!(15..15), r!(12..9)] {
+            let expected: Vec<_> = set.as_ref().overlapping(window.clone()).map(|range| range.as_ref()).collect();
+            assert_eq!(expected, union.overlapping(window.clone()).collect::<Vec<_>>(), "{}", window);
+        }
+
+        let empty = RangeSet::empty();
+        assert_eq!(left, left.union_view(&empty).to_range_set());
+        assert!(empty.union_view(&empty).is_empty());
+        assert!(UnionView::<u8>::new([]).is_empty());
+    }
+
+    #[test]
+    fn intersection_view() {
+        let left = range_set![r!(..0), r!(4..8), r!(10..=20), r!(30..)];
+        let middle = range_set![r!(-5..6), r!(7..=10), r!(20..40)];
+        let right = range_set![r!(..)];
+        let intersection = IntersectionView::new([left.as_ref(), middle.as_ref(), right.as_ref()]);
+        assert!(!intersection.is_empty());
+        assert!(intersection.contains(&-1) && intersection.contains(&10) && intersection.contains(&35));
+        assert!(!intersection.contains(&0) && !intersection.contains(&6) && !intersection.contains(&40));
+        assert_eq!(left.intersection(&middle), intersection.to_range_set());
+        assert_eq!(vec![r!((&-5)..&0), r!((&4)..&6)], intersection.overlapping(r!(-3..5)).collect::<Vec<_>>());
+        assert_eq!(vec![r!((&10)..=&10), r!((&20)..=&20)], intersection.overlapping(r!(10..=20)).collect::<Vec<_>>());
+        assert_eq!(0, intersection.overlapping(r!(11..20)).count());
+        assert_eq!(0, intersection.overlapping(r!(4..4)).count());
+
+        let disjoint = range_set![r!(0..4), r!(8..10)];
+        assert!(left.intersection_view(&disjoint).is_empty());
+        assert_eq!(RangeSet::unbound(), IntersectionView::<u8>::new([]).to_range_set());
+        assert_eq!(vec![r!(..)], IntersectionView::<u8>::new([]).overlapping(r!(4..8)).collect::<Vec<_>>());
+        assert_eq!(0, IntersectionView::<u8>::new([]).overlapping(r!(4..4)).count());
+    }
+}
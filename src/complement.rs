@@ -0,0 +1,142 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::ops::RangeBounds;
+use crate::{BoundExt, PositionalBound, Range, RangeSet, RangeSetRef};
+use crate::Bound::Unbounded;
+
+/// A read-only view over the complement of a set, everything the set doesn't hold
+///
+/// The gaps between the ranges of the set are created on the fly, so unlike [`invert`](RangeSet::invert) creating
+/// and querying a view doesn't allocate
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set};
+///
+/// let covered = range_set![r!(0..10), r!(20..30)];
+/// let missing = covered.complement_view();
+///
+/// assert!(missing.contains(&15));
+/// assert!(!missing.contains(&5));
+/// assert_eq!(vec![r!((&10)..&20)], missing.overlapping(r!(5..25)).collect::<Vec<_>>());
+/// assert_eq!(covered.invert(), missing.to_range_set());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ComplementView<'a, T: Ord> {
+    items: RangeSetRef<'a, T>,
+}
+
+impl<T: Ord + Debug> RangeSet<T> {
+    /// Returns a view over the complement of this set
+    pub fn complement_view(&self) -> ComplementView<'_, T> {
+        self.as_ref().complement_view()
+    }
+}
+
+impl<'a, T: Ord + Debug> RangeSetRef<'a, T> {
+    /// Returns a view over the complement of this set
+    pub fn complement_view(&self) -> ComplementView<'a, T> {
+        ComplementView { items: *self }
+    }
+}
+
+impl<'a, T: Ord + Debug> ComplementView<'a, T> {
+    /// If the complement holds nothing, which is when the ranges of the set together cover everything
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.covers(Unbounded, Unbounded)
+    }
+
+    /// Returns an iterator over the ranges in the complement, the gaps between the ranges of the set
+    pub fn items(&self) -> impl Iterator<Item=Range<&'a T>> + '_ {
+        (0..=self.items.as_slice().len()).filter_map(|index| self.gap(index))
+    }
+
+    /// Check if `other` falls outside of the ranges of the set
+    pub fn contains<Q: Ord + ?Sized>(&self, other: &Q) -> bool where T: Borrow<Q> {
+        !self.items.contains(other)
+    }
+
+    /// Returns an iterator over the ranges in the complement that overlap with given range
+    pub fn overlapping<'b>(&'b self, range: impl RangeBounds<T> + 'b) -> impl Iterator<Item=Range<&'a T>> + 'b {
+        let items = self.items.as_slice();
+        let start = PositionalBound::Start(range.start_bound());
+        let end = PositionalBound::End(range.end_bound());
+
+        // Only the gaps before, between and after the ranges overlapping `range` can overlap it
+        let first = items.partition_point(|item| item.end_pos() <= start);
+        let last = first + items[first..].partition_point(|item| item.start_pos() < end);
+
+        (first..=last)
+            .filter_map(|index| self.gap(index))
+            .filter(move |gap| {
+                let query = Range::new(range.start_bound(), range.end_bound());
+                gap.start_pos() < query.end_pos() && query.start_pos() < gap.end_pos()
+            })
+    }
+
+    /// The gap before the range at given index, or after the last range for the index past it
+    fn gap(&self, index: usize) -> Option<Range<&'a T>> {
+        let items = self.items.as_slice();
+        let start = match index.checked_sub(1) {
+            Some(before) => items[before].end.as_ref().invert(),
+            None => Unbounded,
+        };
+
+        let end = match items.get(index) {
+            Some(after) => after.start.as_ref().invert(),
+            None => Unbounded,
+        };
+
+        // An unbounded side of the set leaves no gap before or after it
+        if (index == 0 && items.first().is_some_and(|first| first.start == Unbounded))
+            || (index == items.len() && items.last().is_some_and(|last| last.end == Unbounded)) {
+            return None;
+        }
+
+        Some(Range::new(start, end)).filter(|gap| !gap.is_empty())
+    }
+}
+
+impl<'a, T: Ord + Clone + Debug> ComplementView<'a, T> {
+    /// Copy the complement into an owned set, which is the same as [`invert`](RangeSet::invert)
+    pub fn to_range_set(&self) -> RangeSet<T> {
+        RangeSet {
+            items: self.items()
+                .map(|range| Range::new(range.start.cloned(), range.end.cloned()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeSet};
+
+    #[test]
+    fn complement_view() {
+        let set = range_set![r!(..0), r!(4..8), r!(10>..=20)];
+        let view = set.complement_view();
+        assert!(!view.is_empty());
+        assert!(view.contains(&0) && view.contains(&10) && view.contains(&21));
+        assert!(!view.contains(&-1) && !view.contains(&4) && !view.contains(&20));
+        assert_eq!(vec![r!((&0)..&4), r!((&8)..=&10), r!((&20)>..)], view.items().collect::<Vec<_>>());
+        assert_eq!(set.invert(), view.to_range_set());
+
+        assert_eq!(vec![r!((&0)..&4), r!((&8)..=&10)], view.overlapping(r!(-5..9)).collect::<Vec<_>>());
+        assert_eq!(vec![r!((&8)..=&10)], view.overlapping(r!(8..=8)).collect::<Vec<_>>());
+        assert_eq!(Vec::<crate::Range<&i32>>::new(), view.overlapping(r!(4..8)).collect::<Vec<_>>());
+        assert_eq!(vec![r!((&20)>..)], view.overlapping(r!(30..)).collect::<Vec<_>>());
+        assert_eq!(view.items().collect::<Vec<_>>(), view.overlapping(r!(..)).collect::<Vec<_>>());
+
+        assert_eq!(vec![r!(..)], RangeSet::<u8>::empty().complement_view().items().collect::<Vec<_>>());
+        assert!(RangeSet::<u8>::unbound().complement_view().is_empty());
+        assert_eq!(0, RangeSet::<u8>::unbound().complement_view().items().count());
+
+        let mut split = RangeSet::<u8>::unbound();
+        split.split_boundary_at(&5);
+        assert!(split.complement_view().is_empty());
+        assert_eq!(0, split.complement_view().items().count());
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{Bound, Discrete, Range};
+
+/// The lowest value of the second component of a composite key
+fn lowest<B: Discrete>() -> B {
+    B::min_value().expect("the second component of a composite key needs a lowest value")
+}
+
+/// Create the range over composite keys `(a, b)` where `a` falls within given range, with any `b`
+///
+/// The bounds are placed on the lowest `b` of the first `a` inside or after the range, so keys at the edges are
+/// neither missed nor included by accident
+///
+/// # Panics
+///
+/// If `B` has no lowest value
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{composite_range, r, Range};
+///
+/// let range = composite_range::<u32, u8>(r!(3..=5));
+///
+/// assert_eq!(Range::from((3, 0)..(6, 0)), range);
+/// assert!(range.contains(&(5, 255)));
+/// assert!(!range.contains(&(2, 255)));
+///
+/// // "all rows with a = 7"
+/// assert_eq!(Range::from((7, 0)..(8, 0)), composite_range::<u32, u8>(r!(7..=7)));
+/// ```
+pub fn composite_range<A: Discrete + Clone, B: Discrete>(first: Range<A>) -> Range<(A, B)> {
+    let start = match first.start {
+        Bound::Included(start) => Bound::Included((start, lowest())),
+        Bound::Excluded(start) => match start.successor() {
+            Some(next) => Bound::Included((next, lowest())),
+            // Nothing comes after the start, so no key does either
+            None => return Range::new(Bound::Included((start.clone(), lowest())), Bound::Excluded((start, lowest()))),
+        },
+        Bound::Unbounded => Bound::Unbounded,
+    };
+
+    let end = match first.end {
+        Bound::Included(end) => end.successor().map_or(Bound::Unbounded, |next| Bound::Excluded((next, lowest()))),
+        Bound::Excluded(end) => Bound::Excluded((end, lowest())),
+        Bound::Unbounded => Bound::Unbounded,
+    };
+
+    Range::new(start, end)
+}
+
+/// Create the range over composite keys `(a, b)` where `a` equals `first` and `b` falls within given range
+///
+/// An unbounded side of `second` stays within the keys starting with `first`
+///
+/// # Panics
+///
+/// If `second` is unbounded at the start and `B` has no lowest value
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{composite_range_with, r, Range};
+///
+/// let range = composite_range_with::<u32, u8>(7, r!(10..));
+///
+/// assert_eq!(Range::from((7, 10)..(8, 0)), range);
+/// assert!(range.contains(&(7, 255)));
+/// assert!(!range.contains(&(8, 0)));
+/// ```
+pub fn composite_range_with<A: Discrete + Clone, B: Discrete>(first: A, second: Range<B>) -> Range<(A, B)> {
+    let start = match second.start {
+        Bound::Included(start) => Bound::Included((first.clone(), start)),
+        Bound::Excluded(start) => Bound::Excluded((first.clone(), start)),
+        Bound::Unbounded => Bound::Included((first.clone(), lowest())),
+    };
+
+    let end = match second.end {
+        Bound::Included(end) => Bound::Included((first, end)),
+        Bound::Excluded(end) => Bound::Excluded((first, end)),
+        Bound::Unbounded => first.successor().map_or(Bound::Unbounded, |next| Bound::Excluded((next, lowest()))),
+    };
+
+    Range::new(start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{composite_range, composite_range_with, r, Range};
+
+    #[test]
+    fn composite() {
+        let keys: Vec<(u8, i8)> = [0, 1, 2, 254, 255].iter()
+            .flat_map(|a| [-128, -1, 0, 127].map(|b| (*a, b)))
+            .collect();
+
+        let check = |range: Range<(u8, i8)>, expected: &dyn Fn(&(u8, i8)) -> bool| {
+            for key in &keys {
+                assert_eq!(expected(key), range.contains(key), "{:?} in {:?}", key, range);
+            }
+        };
+
+        check(composite_range(r!(1..=2)), &|(a, _)| (1..=2).contains(a));
+        check(composite_range(r!(1>..)), &|(a, _)| *a > 1);
+        check(composite_range(r!(..255)), &|(a, _)| *a < 255);
+        check(composite_range(r!(255..=255)), &|(a, _)| *a == 255);
+        check(composite_range(r!(255>..)), &|_| false);
+        check(composite_range_with(1, r!(..0)), &|(a, b)| *a == 1 && *b < 0);
+        check(composite_range_with(2, r!(-1>..)), &|(a, b)| *a == 2 && *b > -1);
+        check(composite_range_with(255, r!(..)), &|(a, _)| *a == 255);
+    }
+}
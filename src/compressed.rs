@@ -0,0 +1,354 @@
+use std::mem;
+use crate::{Bound, Range, RangeSet, RangeSetBuilder};
+
+/// A read-only set of `u64` values in Elias–Fano encoding, which takes a little over 2 bits plus the logarithm of the
+/// average gap between boundaries for every boundary, created by [`RangeSet::compress`](RangeSet::compress)
+///
+/// Ranges are stored as their first and last value, so converting back gives inclusive ranges. Besides
+/// [`contains`](CompressedRangeSet::contains) this answers [`rank`](CompressedRangeSet::rank) and
+/// [`select`](CompressedRangeSet::select) without decompressing
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set};
+///
+/// let set = range_set![r!(10u64..20), r!(100..=150), r!(1000..)];
+/// let compressed = set.compress();
+///
+/// assert!(compressed.contains(150));
+/// assert!(!compressed.contains(20));
+/// assert_eq!(15, compressed.rank(105));
+/// assert_eq!(Some(105), compressed.select(15));
+/// assert_eq!(range_set![r!(10u64..=19), r!(100..=150), r!(1000..=u64::MAX)], compressed.to_range_set());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct CompressedRangeSet {
+    /// The first and last value of every range
+    bounds: EliasFano,
+    /// The amount of values before every range
+    offsets: EliasFano,
+}
+
+impl RangeSet<u64> {
+    /// Compress this set into a read-only set taking a fraction of the memory
+    pub fn compress(&self) -> CompressedRangeSet {
+        CompressedRangeSet::from(self)
+    }
+}
+
+impl CompressedRangeSet {
+    /// The amount of ranges in this set
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.offsets.len
+    }
+
+    /// If this is an empty set
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.len == 0
+    }
+
+    /// The amount of bytes this set has allocated on the heap
+    pub fn heap_bytes(&self) -> usize {
+        self.bounds.heap_bytes() + self.offsets.heap_bytes()
+    }
+
+    /// Check if `value` is inside of this set
+    pub fn contains(&self, value: u64) -> bool {
+        // An odd amount of boundaries up to the value puts it between the first and last value of a range
+        let count = self.bounds.count_le(value);
+        count % 2 == 1 || (count > 0 && self.bounds.get(count - 1) == value)
+    }
+
+    /// The amount of values in this set that are lower than `value`
+    pub fn rank(&self, value: u64) -> u64 {
+        let count = value.checked_sub(1).map_or(0, |before| self.bounds.count_le(before));
+        let index = count / 2;
+        if count % 2 == 1 {
+            self.offsets.get(index) + (value - self.bounds.get(count - 1))
+        } else if index < self.len() {
+            self.offsets.get(index)
+        } else if index > 0 {
+            self.offsets.get(index - 1) + (self.bounds.get(count - 1) - self.bounds.get(count - 2) + 1)
+        } else {
+            0
+        }
+    }
+
+    /// The value at given position in this set counting from zero, or `None` if the set holds fewer values
+    pub fn select(&self, position: u64) -> Option<u64> {
+        let index = self.offsets.count_le(position).checked_sub(1)?;
+        let value = self.bounds.get(index * 2).checked_add(position - self.offsets.get(index))?;
+        (value <= self.bounds.get(index * 2 + 1)).then_some(value)
+    }
+
+    /// Returns an iterator over the inclusive ranges in this set
+    pub fn items(&self) -> impl Iterator<Item=Range<u64>> + '_ {
+        (0..self.len()).map(|index| {
+            Range::new(Bound::Included(self.bounds.get(index * 2)), Bound::Included(self.bounds.get(index * 2 + 1)))
+        })
+    }
+
+    /// Decompress this set into a [`RangeSet`](RangeSet) of inclusive ranges
+    pub fn to_range_set(&self) -> RangeSet<u64> {
+        let mut builder = RangeSetBuilder::with_capacity(self.len());
+        for range in self.items() {
+            builder.push(range);
+        }
+
+        builder.finish()
+    }
+}
+
+impl From<&RangeSet<u64>> for CompressedRangeSet {
+    fn from(set: &RangeSet<u64>) -> Self {
+        let mut bounds: Vec<u64> = Vec::with_capacity(set.items.len() * 2);
+        let mut offsets = Vec::with_capacity(set.items.len());
+        let mut count = 0u64;
+        for range in set.items() {
+            let (Some(first), Some(last)) = (range.first_value(), range.last_value()) else {
+                continue;
+            };
+
+            if first > last {
+                continue;
+            }
+
+            match bounds.last_mut() {
+                // Ranges like `..=4` and `5..` don't connect as bounds, but do as values
+                Some(end) if *end + 1 == first => *end = last,
+                _ => {
+                    offsets.push(count);
+                    bounds.extend([first, last]);
+                }
+            }
+
+            // Only wraps when the set holds every value, after which nothing is counted anymore
+            count = count.wrapping_add(last - first).wrapping_add(1);
+        }
+
+        CompressedRangeSet { bounds: EliasFano::new(&bounds), offsets: EliasFano::new(&offsets) }
+    }
+}
+
+impl From<CompressedRangeSet> for RangeSet<u64> {
+    fn from(set: CompressedRangeSet) -> Self {
+        set.to_range_set()
+    }
+}
+
+/// The amount of ones or zeros between the positions remembered to speed up selecting them
+const SAMPLE: usize = 256;
+
+/// A non-decreasing sequence of integers, of which the low bits are packed and the high bits are stored as the gaps
+/// between them in unary
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+struct EliasFano {
+    len: usize,
+    low_bits: u32,
+    lows: Box<[u64]>,
+    /// The `i`th value sets bit `i + (value >> low_bits)`
+    highs: Box<[u64]>,
+    high_len: usize,
+    /// The position of every `SAMPLE`th one in `highs`
+    ones: Box<[usize]>,
+    /// The position of every `SAMPLE`th zero in `highs`
+    zeros: Box<[usize]>,
+}
+
+impl EliasFano {
+    fn new(values: &[u64]) -> Self {
+        let len = values.len();
+        let last = values.last().copied().unwrap_or(0);
+        let low_bits = match (last as u128 + 1) / len.max(1) as u128 {
+            0 => 0,
+            ratio => ratio.ilog2().min(63),
+        };
+
+        let high_len = len + (last >> low_bits) as usize + 1;
+        let mut set = EliasFano {
+            len,
+            low_bits,
+            lows: vec![0; (len * low_bits as usize).div_ceil(64)].into(),
+            highs: vec![0; high_len.div_ceil(64)].into(),
+            high_len,
+            ones: Box::default(),
+            zeros: Box::default(),
+        };
+
+        for (index, value) in values.iter().enumerate() {
+            set.set_low(index, value & set.low_mask());
+            let position = index + (value >> low_bits) as usize;
+            set.highs[position / 64] |= 1 << (position % 64);
+        }
+
+        let (mut ones, mut zeros) = (vec![], vec![]);
+        let (mut one_count, mut zero_count) = (0, 0);
+        for position in 0..high_len {
+            let (samples, count) = if set.bit(position) { (&mut ones, &mut one_count) } else { (&mut zeros, &mut zero_count) };
+            if *count % SAMPLE == 0 {
+                samples.push(position);
+            }
+
+            *count += 1;
+        }
+
+        set.ones = ones.into();
+        set.zeros = zeros.into();
+        set
+    }
+
+    fn heap_bytes(&self) -> usize {
+        (self.lows.len() + self.highs.len()) * mem::size_of::<u64>()
+            + (self.ones.len() + self.zeros.len()) * mem::size_of::<usize>()
+    }
+
+    #[inline]
+    fn low_mask(&self) -> u64 {
+        (1 << self.low_bits) - 1
+    }
+
+    #[inline]
+    fn bit(&self, position: usize) -> bool {
+        self.highs[position / 64] & (1 << (position % 64)) != 0
+    }
+
+    fn set_low(&mut self, index: usize, low: u64) {
+        if self.low_bits == 0 {
+            return;
+        }
+
+        let position = index * self.low_bits as usize;
+        let (word, offset) = (position / 64, position % 64);
+        self.lows[word] |= low << offset;
+        if offset + self.low_bits as usize > 64 {
+            self.lows[word + 1] |= low >> (64 - offset);
+        }
+    }
+
+    fn low(&self, index: usize) -> u64 {
+        if self.low_bits == 0 {
+            return 0;
+        }
+
+        let position = index * self.low_bits as usize;
+        let (word, offset) = (position / 64, position % 64);
+        let mut low = self.lows[word] >> offset;
+        if offset + self.low_bits as usize > 64 {
+            low |= self.lows[word + 1] << (64 - offset);
+        }
+
+        low & self.low_mask()
+    }
+
+    /// The value at given index
+    fn get(&self, index: usize) -> u64 {
+        let high = self.select(index, true) - index;
+        ((high as u64) << self.low_bits) | self.low(index)
+    }
+
+    /// The amount of values lower than or equal to `value`
+    fn count_le(&self, value: u64) -> usize {
+        let high = (value >> self.low_bits) as usize;
+        if self.len == 0 || high >= self.high_len - self.len {
+            return self.len;
+        }
+
+        // Values with a lower high part end at the zero before this one
+        let (mut position, mut index) = match high.checked_sub(1) {
+            None => (0, 0),
+            Some(before) => {
+                let position = self.select(before, false) + 1;
+                (position, position - high)
+            }
+        };
+
+        while position < self.high_len && self.bit(position) && self.low(index) <= value & self.low_mask() {
+            position += 1;
+            index += 1;
+        }
+
+        index
+    }
+
+    /// The position of the `nth` one or zero in `highs`
+    fn select(&self, nth: usize, one: bool) -> usize {
+        let samples = if one { &self.ones } else { &self.zeros };
+        let start = samples[nth / SAMPLE];
+        let mut remaining = (nth % SAMPLE) as u32;
+        let mut word = start / 64;
+        let read = |word: usize| if one { self.highs[word] } else { !self.highs[word] };
+        let mut bits = read(word) & (u64::MAX << (start % 64));
+        loop {
+            let count = bits.count_ones();
+            if remaining < count {
+                for _ in 0..remaining {
+                    bits &= bits - 1;
+                }
+
+                return word * 64 + bits.trailing_zeros() as usize;
+            }
+
+            remaining -= count;
+            word += 1;
+            bits = read(word);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeSet, RangeSetBuilder};
+
+    #[test]
+    fn compressed() {
+        let set = range_set![r!(..3u64), r!(5..=5), r!(6>..9), r!(40..600), r!(601..=601), r!(1000..1300), r!(5000..)];
+        let compressed = set.compress();
+        assert_eq!(7, compressed.len());
+        assert_eq!(1, range_set![r!(0u64..=4), r!(5..=9)].compress().len());
+
+        let mut rank = 0;
+        for value in 0..6000 {
+            assert_eq!(set.contains(&value), compressed.contains(value), "{}", value);
+            assert_eq!(rank, compressed.rank(value), "{}", value);
+            if set.contains(&value) {
+                assert_eq!(Some(value), compressed.select(rank), "{}", rank);
+                rank += 1;
+            }
+        }
+
+        assert!(compressed.contains(u64::MAX));
+        assert_eq!(Some(u64::MAX), compressed.select(compressed.rank(u64::MAX)));
+        assert_eq!(RangeSet::from(compressed.clone()).compress(), compressed);
+
+        // Many ranges spread over the whole domain, so the samples and packed low bits are used
+        let mut builder = RangeSetBuilder::new();
+        for index in 0..5000u64 {
+            builder.push(r!((index * 0x3_0000_0000_0001)..=index * 0x3_0000_0000_0001 + index));
+        }
+
+        let spread = builder.finish();
+        let compressed = spread.compress();
+        assert_eq!(spread, compressed.to_range_set());
+        assert!(compressed.heap_bytes() < spread.heap_bytes() / 2);
+        for (index, range) in spread.items().enumerate().step_by(7) {
+            let first = range.first_value().unwrap();
+            assert!(compressed.contains(first) && (index == 0 || !compressed.contains(first - 1)));
+            assert_eq!(Some(first), compressed.select(compressed.rank(first)));
+        }
+
+        let full = RangeSet::<u64>::unbound().compress();
+        assert!(full.contains(0) && full.contains(u64::MAX));
+        assert_eq!(u64::MAX, full.rank(u64::MAX));
+        assert_eq!(Some(u64::MAX), full.select(u64::MAX));
+
+        let empty = RangeSet::<u64>::empty().compress();
+        assert!(empty.is_empty());
+        assert!(!empty.contains(0));
+        assert_eq!(0, empty.rank(u64::MAX));
+        assert_eq!(None, empty.select(0));
+        assert_eq!(RangeSet::empty(), empty.to_range_set());
+    }
+}
@@ -0,0 +1,151 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::sync::{PoisonError, RwLock};
+use crate::{Range, RangeSet, SharedRangeSet};
+
+/// A [`RangeSet`](RangeSet) that can be read and modified from multiple threads at once
+///
+/// Meant for sets that are read often and modified rarely, readers only hold a read lock for the duration of a
+/// single lookup and [`snapshot`](ConcurrentRangeSet::snapshot) hands out a [`SharedRangeSet`](SharedRangeSet) that
+/// can be queried without holding any lock. A modification while snapshots are alive copies the ranges once
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use std::thread;
+/// use eater_rangeset::{r, range_set, ConcurrentRangeSet};
+///
+/// let set = Arc::new(ConcurrentRangeSet::from(range_set![r!(0..10)]));
+///
+/// let reader = {
+///     let set = set.clone();
+///     thread::spawn(move || set.contains(&5))
+/// };
+///
+/// set.add(r!(20..30));
+///
+/// assert!(reader.join().unwrap());
+/// assert!(set.contains(&25));
+/// ```
+#[derive(Debug)]
+pub struct ConcurrentRangeSet<T: Ord> {
+    inner: RwLock<SharedRangeSet<T>>,
+}
+
+impl<T: Ord + Debug> Default for ConcurrentRangeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> From<RangeSet<T>> for ConcurrentRangeSet<T> {
+    fn from(value: RangeSet<T>) -> Self {
+        ConcurrentRangeSet { inner: RwLock::new(SharedRangeSet::from(value)) }
+    }
+}
+
+impl<T: Ord + Debug> ConcurrentRangeSet<T> {
+    /// Create a new empty set
+    pub fn new() -> Self {
+        Self::from(RangeSet::new())
+    }
+
+    /// If this is an empty set
+    pub fn is_empty(&self) -> bool {
+        self.read(|set| set.is_empty())
+    }
+
+    /// Check if `other` falls within the ranges defined in this set
+    pub fn contains<Q: Ord + ?Sized>(&self, other: &Q) -> bool where T: Borrow<Q> {
+        self.read(|set| set.contains(other))
+    }
+
+    /// Returns the current state of the set, which isn't affected by later modifications
+    pub fn snapshot(&self) -> SharedRangeSet<T> {
+        self.read(|set| set.clone())
+    }
+
+    /// Replace the set, returning the previous state
+    pub fn replace(&self, set: RangeSet<T>) -> SharedRangeSet<T> {
+        let mut inner = self.inner.write().unwrap_or_else(PoisonError::into_inner);
+        std::mem::replace(&mut *inner, SharedRangeSet::from(set))
+    }
+
+    fn read<R>(&self, f: impl FnOnce(&SharedRangeSet<T>) -> R) -> R {
+        f(&self.inner.read().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl<T: Ord + Clone + Debug> ConcurrentRangeSet<T> {
+    /// Modify the set with given function while holding the write lock, readers see either the set before or
+    /// after the modification
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, ConcurrentRangeSet};
+    ///
+    /// let set = ConcurrentRangeSet::from(range_set![r!(0..10)]);
+    /// set.update(|set| *set = set.difference(&range_set![r!(4..6)]));
+    ///
+    /// assert_eq!(range_set![r!(0..4), r!(6..10)], *set.snapshot());
+    /// ```
+    pub fn update<R>(&self, f: impl FnOnce(&mut RangeSet<T>) -> R) -> R {
+        let mut inner = self.inner.write().unwrap_or_else(PoisonError::into_inner);
+        f(inner.make_mut())
+    }
+
+    /// Add a new range to this set, see [`RangeSet::add`](RangeSet::add)
+    pub fn add(&self, range: impl Into<Range<T>>) {
+        self.update(|set| set.add(range))
+    }
+
+    /// Returns the set, the ranges are only copied if they're shared with a snapshot
+    pub fn into_inner(self) -> RangeSet<T> {
+        self.inner.into_inner().unwrap_or_else(PoisonError::into_inner).into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+    use crate::{r, range_set, ConcurrentRangeSet};
+
+    #[test]
+    fn concurrent() {
+        let set = Arc::new(ConcurrentRangeSet::new());
+        assert!(set.is_empty());
+
+        let snapshot = set.snapshot();
+        let writers: Vec<_> = (0..4)
+            .map(|i| {
+                let set = set.clone();
+                thread::spawn(move || {
+                    for j in 0..50 {
+                        set.add(r!((j * 40 + i * 10)..j * 40 + i * 10 + 5));
+                    }
+                })
+            })
+            .collect();
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let set = set.clone();
+                thread::spawn(move || (0..2000).filter(|x| set.contains(x)).count())
+            })
+            .collect();
+
+        writers.into_iter().for_each(|writer| writer.join().unwrap());
+        readers.into_iter().for_each(|reader| assert!(reader.join().unwrap() <= 1000));
+
+        assert!(snapshot.is_empty());
+        assert_eq!(1000, (0..2000).filter(|x| set.contains(x)).count());
+        assert_eq!(200, set.snapshot().items().count());
+
+        let previous = set.replace(range_set![r!(0..1)]);
+        assert_eq!(200, previous.items().count());
+        assert_eq!(range_set![r!(0..1)], Arc::into_inner(set).unwrap().into_inner());
+    }
+}
@@ -1,45 +1,72 @@
-use crate::{Range, RangeSet};
-use crate::internal::LinearRangeAdder;
+use crate::{sort_ranges, CollectRanges, Discrete, Range, RangeSet};
+use crate::builder::RangeSetBuilder;
 use std::collections::Bound;
 use std::fmt::Debug;
-use std::ops::{RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+use std::ops::{RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 use std::ops::Bound::{Excluded, Included, Unbounded};
 
 #[cfg(feature = "smallvec")]
 impl<T: Ord + Debug, const N: usize> From<smallvec::SmallVec<[Range<T>; N]>> for RangeSet<T> {
     fn from(mut value: smallvec::SmallVec<[Range<T>; N]>) -> Self {
-        value.sort_by(|l, r| l.start_pos().cmp(&r.start_pos()));
-        let mut adder = LinearRangeAdder::with_capacity(value.len());
+        sort_ranges(&mut value);
+        let mut builder = RangeSetBuilder::with_capacity(value.len());
         for item in value {
-            adder.add(item);
+            builder.push(item);
         }
 
-        adder.finalize()
+        builder.finish()
     }
 }
 
 impl<T: Ord + Debug> From<Vec<Range<T>>> for RangeSet<T> {
     fn from(mut value: Vec<Range<T>>) -> Self {
-        value.sort_by(|l, r| l.start_pos().cmp(&r.start_pos()));
-        let mut adder = LinearRangeAdder::with_capacity(value.len());
+        sort_ranges(&mut value);
+        let mut builder = RangeSetBuilder::with_capacity(value.len());
         for item in value {
-            adder.add(item);
+            builder.push(item);
         }
 
-        adder.finalize()
+        builder.finish()
+    }
+}
+
+impl<T: Ord + Debug> From<Range<T>> for RangeSet<T> {
+    fn from(value: Range<T>) -> Self {
+        RangeSet::single(value)
     }
 }
 
 impl<T: Ord + Debug, I: Into<Range<T>>, const N: usize> From<[I; N]> for RangeSet<T> {
     fn from(value: [I; N]) -> Self {
         let mut value = value.map(Into::into);
-        value.sort_by(|l, r| l.start_pos().cmp(&r.start_pos()));
-        let mut adder = LinearRangeAdder::with_capacity(N);
+        sort_ranges(&mut value);
+        let mut builder = RangeSetBuilder::with_capacity(N);
         for item in value {
-            adder.add(item.into());
+            builder.push(item);
         }
 
-        adder.finalize()
+        builder.finish()
+    }
+}
+
+impl<T: Discrete + Clone + Debug> From<&[T]> for RangeSet<T> {
+    /// Create a set holding every value in given slice, grouping consecutive values into ranges
+    ///
+    /// The values are expected to be sorted, which is checked in debug builds, unsorted values still result in the
+    /// right set but take longer to convert
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, RangeSet};
+    ///
+    /// let column: &[u64] = &[1, 2, 3, 3, 8, 9];
+    ///
+    /// assert_eq!(range_set![r!(1..=3), r!(8..=9)], RangeSet::from(column));
+    /// ```
+    fn from(values: &[T]) -> Self {
+        debug_assert!(values.is_sorted(), "values given to RangeSet::from are not sorted");
+        values.iter().collect_range_set()
     }
 }
 
@@ -89,10 +116,20 @@ impl<T: Ord + Debug> From<RangeFrom<T>> for Range<T> {
     }
 }
 
+impl<T> RangeBounds<T> for Range<T> {
+    fn start_bound(&self) -> Bound<&T> {
+        self.start.as_ref()
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        self.end.as_ref()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use smallvec::smallvec;
-    use crate::{r, Range, range_set};
+    use crate::{r, Range, range_set, RangeSet};
 
     #[test]
     fn all() {
@@ -106,6 +143,10 @@ mod tests {
 
         assert_eq!(range_set![r!(4..)], vec![r!(4..)].into());
 
+        let values: &[i8] = &[-128, -127, 0, 1, 3, 126, 127];
+        assert_eq!(range_set![r!(-128..=-127), r!(0..=1), r!(3..=3), r!(126..=127)], values.into());
+        assert_eq!(RangeSet::<u32>::empty(), (&[][..]).into());
+
         #[cfg(feature = "smallvec")]
         {
             let v: smallvec::SmallVec<[Range<_>; 5]> = smallvec![r!(4..)];
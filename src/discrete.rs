@@ -0,0 +1,557 @@
+use std::fmt::Debug;
+use std::ops::RangeBounds;
+use crate::{max_start, min_end, Bound, Range, RangeSet, RangeSetBuilder, RangeSetError};
+
+/// A type where every value has a distinct next and previous value, like integers
+///
+/// This allows counting and stepping through the values inside of a range
+pub trait Discrete: Ord + Sized {
+    /// The lowest value of this type, if there is one
+    fn min_value() -> Option<Self>;
+
+    /// The highest value of this type, if there is one
+    fn max_value() -> Option<Self>;
+
+    /// The value right after this one, or `None` if this is the highest value
+    fn successor(&self) -> Option<Self>;
+
+    /// The value right before this one, or `None` if this is the lowest value
+    fn predecessor(&self) -> Option<Self>;
+
+    /// The amount of steps needed to get from `start` to `end`, or `None` if `end` is before `start`
+    fn steps_between(start: &Self, end: &Self) -> Option<u128>;
+
+    /// The value `steps` steps after this one, or `None` if that overflows
+    fn forward(&self, steps: u128) -> Option<Self>;
+}
+
+/// An item that can be turned into a [`Discrete`](Discrete) value, implemented for all integer types and references
+/// to any discrete type, so both `values.iter()` and `values.into_iter()` can be grouped into ranges
+///
+/// Implement this for custom discrete types to group owned values of them
+pub trait DiscreteItem {
+    /// The discrete value of this item
+    type Value: Discrete + Clone;
+
+    /// Turn this item into its discrete value
+    fn into_value(self) -> Self::Value;
+}
+
+impl<T: Discrete + Clone> DiscreteItem for &T {
+    type Value = T;
+
+    #[inline]
+    fn into_value(self) -> T {
+        self.clone()
+    }
+}
+
+macro_rules! impl_discrete_item {
+    ($($ty:ty),*) => {
+        $(
+            impl DiscreteItem for $ty {
+                type Value = $ty;
+
+                #[inline]
+                fn into_value(self) -> $ty {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete_item!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_discrete {
+    ($($ty:ty),*) => {
+        $(
+            impl Discrete for $ty {
+                #[inline]
+                fn min_value() -> Option<Self> {
+                    Some(<$ty>::MIN)
+                }
+
+                #[inline]
+                fn max_value() -> Option<Self> {
+                    Some(<$ty>::MAX)
+                }
+
+                #[inline]
+                fn successor(&self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                #[inline]
+                fn predecessor(&self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                #[inline]
+                fn steps_between(start: &Self, end: &Self) -> Option<u128> {
+                    if end < start {
+                        return None;
+                    }
+
+                    // Casting to i128 and back to u128 wraps correctly for every integer type up to 128 bits
+                    Some((*end as i128).wrapping_sub(*start as i128) as u128)
+                }
+
+                #[inline]
+                fn forward(&self, steps: u128) -> Option<Self> {
+                    let steps = i128::try_from(steps).ok()?;
+                    <$ty>::try_from((*self as i128).checked_add(steps)?).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl Discrete for u128 {
+    fn min_value() -> Option<Self> {
+        Some(u128::MIN)
+    }
+
+    fn max_value() -> Option<Self> {
+        Some(u128::MAX)
+    }
+
+    fn successor(&self) -> Option<Self> {
+        self.checked_add(1)
+    }
+
+    fn predecessor(&self) -> Option<Self> {
+        self.checked_sub(1)
+    }
+
+    fn steps_between(start: &Self, end: &Self) -> Option<u128> {
+        end.checked_sub(*start)
+    }
+
+    fn forward(&self, steps: u128) -> Option<Self> {
+        self.checked_add(steps)
+    }
+}
+
+impl Discrete for i128 {
+    fn min_value() -> Option<Self> {
+        Some(i128::MIN)
+    }
+
+    fn max_value() -> Option<Self> {
+        Some(i128::MAX)
+    }
+
+    fn successor(&self) -> Option<Self> {
+        self.checked_add(1)
+    }
+
+    fn predecessor(&self) -> Option<Self> {
+        self.checked_sub(1)
+    }
+
+    fn steps_between(start: &Self, end: &Self) -> Option<u128> {
+        (end >= start).then(|| end.wrapping_sub(*start) as u128)
+    }
+
+    fn forward(&self, steps: u128) -> Option<Self> {
+        self.checked_add_unsigned(steps)
+    }
+}
+
+impl<T: Discrete + Clone> Range<T> {
+    /// The first value inside of this range, or `None` if the range starts unbounded on a type without a lowest
+    /// value or if nothing comes after an excluded start
+    pub fn first_value(&self) -> Option<T> {
+        match &self.start {
+            Bound::Included(start) => Some(start.clone()),
+            Bound::Excluded(start) => start.successor(),
+            Bound::Unbounded => T::min_value(),
+        }
+    }
+
+    /// The last value inside of this range, or `None` if the range ends unbounded on a type without a highest
+    /// value or if nothing comes before an excluded end
+    pub fn last_value(&self) -> Option<T> {
+        match &self.end {
+            Bound::Included(end) => Some(end.clone()),
+            Bound::Excluded(end) => end.predecessor(),
+            Bound::Unbounded => T::max_value(),
+        }
+    }
+
+    /// The value in the middle of this range, rounded down, or `None` if the range holds no values
+    ///
+    /// An unbounded side counts as the lowest or highest value of the type
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// assert_eq!(Some(5), r!(0..=10).midpoint());
+    /// assert_eq!(Some(4), r!(0..10).midpoint());
+    /// assert_eq!(Some(191), r!(128u8..).midpoint());
+    /// assert_eq!(None, r!(3>..4).midpoint());
+    /// ```
+    pub fn midpoint(&self) -> Option<T> {
+        let first = self.first_value()?;
+        let steps = T::steps_between(&first, &self.last_value()?)?;
+        first.forward(steps / 2)
+    }
+
+    /// Some value inside of both this range and given domain, or `None` if they share no values
+    ///
+    /// The domain bounds the sides of this range that are unbounded, which is needed for types without a lowest or
+    /// highest value, or to keep the value within a sensible area
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// let range = r!(100..);
+    ///
+    /// assert_eq!(Some(150), range.representative(r!(0..=200)));
+    /// assert_eq!(None, range.representative(r!(0..100)));
+    /// ```
+    pub fn representative(&self, domain: impl RangeBounds<T>) -> Option<T> {
+        let start = max_start(self.start(), domain.start_bound());
+        let end = min_end(self.end(), domain.end_bound());
+        Range::new(start.cloned(), end.cloned()).midpoint()
+    }
+}
+
+impl<T: Discrete + Clone + Debug> RangeSet<T> {
+    /// Split this set into successive sets that each hold exactly `page_size` values, except for the last one which
+    /// may hold less
+    ///
+    /// Every page is built from inclusive ranges
+    ///
+    /// # Panics
+    ///
+    /// If `page_size` is 0, or if the set starts unbounded on a type without a lowest value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let ids = range_set![r!(1..=3), r!(10..15)];
+    /// let pages: Vec<_> = ids.pages(3).collect();
+    ///
+    /// assert_eq!(vec![
+    ///     range_set![r!(1..=3)],
+    ///     range_set![r!(10..=12)],
+    ///     range_set![r!(13..=14)],
+    /// ], pages);
+    /// ```
+    pub fn pages(&self, page_size: usize) -> impl Iterator<Item=RangeSet<T>> + '_ {
+        assert_ne!(page_size, 0, "page size must be larger than 0");
+
+        // The first and last value of every range, `None` as last value means the range never ends
+        let mut ranges = self.items.iter()
+            .filter_map(|range| {
+                let first = match range.start {
+                    Bound::Unbounded => Some(T::min_value().expect("can't page a set that starts unbounded on a type without a lowest value")),
+                    _ => range.first_value(),
+                }?;
+
+                let last = match range.end {
+                    Bound::Unbounded => T::max_value(),
+                    _ => Some(range.last_value()?),
+                };
+
+                // Skip ranges that hold no values, e.g. `1>..2`
+                last.as_ref().is_none_or(|last| *last >= first).then_some((first, last))
+            });
+
+        let mut current: Option<(T, Option<T>)> = None;
+
+        std::iter::from_fn(move || {
+            let mut builder = RangeSetBuilder::new();
+            let mut needed = page_size as u128;
+
+            while needed > 0 {
+                let (first, last) = match current.take() {
+                    Some(current) => current,
+                    None => match ranges.next() {
+                        Some(range) => range,
+                        None => break,
+                    },
+                };
+
+                let available = last.as_ref().and_then(|last| T::steps_between(&first, last)).and_then(|steps| steps.checked_add(1));
+                match available {
+                    Some(available) if available <= needed => {
+                        needed -= available;
+                        builder.push(Range::new(Bound::Included(first), last.map_or(Bound::Unbounded, Bound::Included)));
+                    }
+                    _ => {
+                        let end = first.forward(needed - 1).expect("range holds more values than needed");
+                        current = end.successor().map(|next| (next, last));
+                        builder.push(Range::new(Bound::Included(first), Bound::Included(end)));
+                        needed = 0;
+                    }
+                }
+            }
+
+            let page = builder.finish();
+            (!page.is_empty()).then_some(page)
+        })
+    }
+
+    /// Returns the value in this set closest to `to`, which is `to` itself if this set contains it, or `None` if this
+    /// set holds no values
+    ///
+    /// When the closest values below and above `to` are equally far away, the lower one is returned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let allowed = range_set![r!(0..=10), r!(20>..30), r!(50..)];
+    ///
+    /// assert_eq!(Some(5), allowed.nearest_value(&5));
+    /// assert_eq!(Some(10), allowed.nearest_value(&14));
+    /// assert_eq!(Some(21), allowed.nearest_value(&16));
+    /// assert_eq!(Some(29), allowed.nearest_value(&39));
+    /// assert_eq!(Some(0), allowed.nearest_value(&-100));
+    /// ```
+    pub fn nearest_value(&self, to: &T) -> Option<T> {
+        let Some((below, above)) = self.neighbours(to) else {
+            return Some(to.clone());
+        };
+
+        match (below, above) {
+            (Some((_, below)), Some((_, above))) => Some(if is_above_closer(&below, to, &above) { above } else { below }),
+            (below, above) => below.or(above).map(|(_, value)| value),
+        }
+    }
+
+    /// Extend the range closest to `value` up to it, so it's included in this set without adding a range holding
+    /// just `value`, if the range then reaches the range on the other side of `value` they're merged
+    ///
+    /// When the ranges below and above `value` are equally far away, the lower one is extended. If this set holds
+    /// no values, a range holding just `value` is added
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let mut buffered = range_set![r!(0..=10), r!(20..=30)];
+    ///
+    /// buffered.expand_to_include(&13);
+    /// assert_eq!(range_set![r!(0..=13), r!(20..=30)], buffered);
+    ///
+    /// buffered.expand_to_include(&17);
+    /// assert_eq!(range_set![r!(0..=13), r!(17..=30)], buffered);
+    ///
+    /// buffered.expand_to_include(&15);
+    /// assert_eq!(range_set![r!(0..=15), r!(17..=30)], buffered);
+    ///
+    /// buffered.expand_to_include(&16);
+    /// assert_eq!(range_set![r!(0..=30)], buffered);
+    /// ```
+    pub fn expand_to_include(&mut self, value: &T) {
+        let Some((below, above)) = self.neighbours(value) else {
+            return;
+        };
+
+        let extended = match (below, above) {
+            // A range above is never closer when `value` is right after the range below, so only extending the range
+            // below can reach the other one
+            (Some((_, last)), Some((above, first))) if is_above_closer(&last, value, &first) => {
+                Range::new(Bound::Included(value.clone()), above.end.clone())
+            }
+            (Some((below, _)), Some((above, first))) if value.successor().as_ref() == Some(&first) => {
+                Range::new(below.start.clone(), above.end.clone())
+            }
+            (Some((below, _)), _) => Range::new(below.start.clone(), Bound::Included(value.clone())),
+            (None, Some((above, _))) => Range::new(Bound::Included(value.clone()), above.end.clone()),
+            (None, None) => Range::new(Bound::Included(value.clone()), Bound::Included(value.clone())),
+        };
+
+        self.add(extended);
+    }
+
+    /// The closest ranges holding values below and above `value` together with their value closest to it, or `None`
+    /// if this set contains `value`
+    #[allow(clippy::type_complexity)]
+    fn neighbours(&self, value: &T) -> Option<(Option<(&Range<T>, T)>, Option<(&Range<T>, T)>)> {
+        let index = self.items.partition_point(|range| range.as_borrowed::<T>().end_pos() < &value);
+        if self.items.get(index).is_some_and(|range| range.contains(value)) {
+            return None;
+        }
+
+        // Ranges that hold no values, e.g. `1>..2`, are skipped
+        let values = |range: &Range<T>| range.first_value().zip(range.last_value()).filter(|(first, last)| first <= last);
+        let below = self.items[..index].iter().rev().find_map(|range| Some((range, values(range)?.1)));
+        let above = self.items[index..].iter().find_map(|range| Some((range, values(range)?.0)));
+        Some((below, above))
+    }
+
+    /// Returns the ranges of this set as half-open `(start, end)` pairs, where `start` is the first value of a range
+    /// and `end` the value right after its last one, for exchanging sets with code that only knows flat pairs
+    ///
+    /// Returns `None` if a range ends at the highest value of `T`, or starts unbounded on a type without a lowest
+    /// value, as neither can be written as a pair
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, RangeSet};
+    ///
+    /// let set = range_set![r!(..4u32), r!(10..=20), r!(30>..40)];
+    ///
+    /// assert_eq!(Some(vec![(0, 4), (10, 21), (31, 40)]), set.to_pairs());
+    /// assert_eq!(Ok(range_set![r!(0..4u32), r!(10..21), r!(31..40)]), RangeSet::from_pairs(set.to_pairs().unwrap()));
+    /// assert_eq!(None, range_set![r!(10u32..)].to_pairs());
+    /// ```
+    pub fn to_pairs(&self) -> Option<Vec<(T, T)>> {
+        let mut pairs = Vec::with_capacity(self.items.len());
+        for range in self.items.iter() {
+            // Ranges that hold no values, e.g. `1>..2` or `..0u8`, aren't written
+            let (first, last) = match (range.first_value(), range.last_value()) {
+                (Some(first), Some(last)) => (first, last),
+                (None, _) if range.start == Bound::Unbounded => return None,
+                (_, None) if range.end == Bound::Unbounded => return None,
+                _ => continue,
+            };
+
+            if last >= first {
+                pairs.push((first, last.successor()?));
+            }
+        }
+
+        Some(pairs)
+    }
+
+    /// Create a set from half-open `(start, end)` pairs like the ones returned by
+    /// [`to_pairs`](RangeSet::to_pairs), the pairs don't have to be sorted and may overlap, but may not be inverted
+    pub fn from_pairs<I: IntoIterator<Item=(T, T)>>(pairs: I) -> Result<Self, RangeSetError<T>> {
+        let ranges = pairs.into_iter()
+            .map(|(start, end)| Range::new(Bound::Included(start), Bound::Excluded(end)))
+            .collect();
+
+        RangeSet::try_from_vec(ranges)
+    }
+}
+
+/// If `above` is closer to `value` than `below`
+fn is_above_closer<T: Discrete>(below: &T, value: &T, above: &T) -> bool {
+    T::steps_between(value, above) < T::steps_between(below, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, Discrete, RangeSet};
+
+    #[test]
+    fn discrete() {
+        assert_eq!(Some(255), u8::steps_between(&0, &255));
+        assert_eq!(Some(255), i8::steps_between(&-128, &127));
+        assert_eq!(None, i8::steps_between(&1, &0));
+        assert_eq!(Some(u128::MAX), i128::steps_between(&i128::MIN, &i128::MAX));
+        assert_eq!(Some(127), (-128i8).forward(255));
+        assert_eq!(None, (-128i8).forward(256));
+        assert_eq!(None, 255u8.successor());
+        assert_eq!(Some(4), r!(3>..=4).first_value());
+        assert_eq!(None, r!(..0u8).last_value());
+    }
+
+    #[test]
+    fn pairs() {
+        let set = range_set![r!(..-5i8), r!(-2..=0), r!(3>..=3), r!(10..=126)];
+        assert_eq!(Some(vec![(-128, -5), (-2, 1), (10, 127)]), set.to_pairs());
+        assert_eq!(Ok(range_set![r!(-128..-5), r!(-2..1), r!(10..127)]), RangeSet::from_pairs(set.to_pairs().unwrap()));
+        assert_eq!(None, range_set![r!(0..=127i8)].to_pairs());
+        assert_eq!(Some(vec![]), RangeSet::<u8>::empty().to_pairs());
+        assert_eq!(Some(vec![(1, 2)]), range_set![r!(..0u8), r!(1..2), r!(255u8>..)].to_pairs());
+
+        assert_eq!(Ok(range_set![r!(0..10u8)]), RangeSet::from_pairs([(5, 10), (0, 6), (3, 3)]));
+        assert!(RangeSet::from_pairs([(5u8, 4)]).is_err());
+    }
+
+    #[test]
+    fn nearest_value() {
+        let set = range_set![r!(..-100i8), r!(-10..=-5), r!(3>..4), r!(10>..20), r!(120..)];
+        assert_eq!(Some(-128), set.nearest_value(&-128));
+        assert_eq!(Some(-101), set.nearest_value(&-100));
+        assert_eq!(Some(-101), set.nearest_value(&-56));
+        assert_eq!(Some(-10), set.nearest_value(&-55));
+        assert_eq!(Some(-5), set.nearest_value(&3));
+        assert_eq!(Some(11), set.nearest_value(&4));
+        assert_eq!(Some(11), set.nearest_value(&10));
+        assert_eq!(Some(19), set.nearest_value(&69));
+        assert_eq!(Some(120), set.nearest_value(&70));
+        assert_eq!(Some(127), set.nearest_value(&127));
+
+        assert_eq!(Some(7), range_set![r!(3..=7u8)].nearest_value(&255));
+        assert_eq!(Some(3), range_set![r!(3..=7u8)].nearest_value(&0));
+        assert_eq!(None, range_set![r!(3>..4u8)].nearest_value(&3));
+        assert_eq!(None, RangeSet::<u8>::empty().nearest_value(&3));
+    }
+
+    #[test]
+    fn expand_to_include() {
+        let mut set = range_set![r!(..-100i8), r!(-10..-5), r!(3>..4), r!(10>..20), r!(120..)];
+        set.expand_to_include(&-128);
+        set.expand_to_include(&15);
+        assert_eq!(range_set![r!(..-100i8), r!(-10..-5), r!(3>..4), r!(10>..20), r!(120..)], set);
+
+        set.expand_to_include(&-99);
+        set.expand_to_include(&-3);
+        set.expand_to_include(&5);
+        set.expand_to_include(&100);
+        assert_eq!(range_set![r!(..=-99i8), r!(-10..=-3), r!(3>..4), r!(5..20), r!(100..)], set);
+
+        set.expand_to_include(&4);
+        set.expand_to_include(&0);
+        set.expand_to_include(&-11);
+        assert_eq!(range_set![r!(..=-99i8), r!(-11..=0), r!(3>..20), r!(100..)], set);
+
+        // Both ranges are as close, the lower one is extended and merged with the other once it reaches it
+        set.expand_to_include(&2);
+        assert_eq!(range_set![r!(..=-99i8), r!(-11..=2), r!(3>..20), r!(100..)], set);
+        set.expand_to_include(&3);
+        assert_eq!(range_set![r!(..=-99i8), r!(-11..20), r!(100..)], set);
+
+        let mut set = RangeSet::empty();
+        set.expand_to_include(&4u8);
+        set.expand_to_include(&6);
+        assert_eq!(range_set![r!(4..=6u8)], set);
+    }
+
+    #[test]
+    fn midpoint() {
+        assert_eq!(Some(-1i8), r!(..).midpoint());
+        assert_eq!(Some(i128::MAX / 2), r!(0..).midpoint());
+        assert_eq!(Some(3), r!(3..=3).midpoint());
+        assert_eq!(None, r!(3..3).midpoint());
+        assert_eq!(Some(u64::MAX), r!(..).representative(r!((u64::MAX)..)));
+        assert_eq!(Some(5), r!(..=10).representative(r!(0..)));
+        assert_eq!(None, r!(..0).representative(r!(0..)));
+    }
+
+    #[test]
+    fn pages() {
+        let set = range_set![r!(1..=3), r!(5>..6), r!(10..15)];
+        assert_eq!(vec![range_set![r!(1..=3), r!(10..=14)]], set.pages(10).collect::<Vec<_>>());
+        assert_eq!(vec![range_set![r!(1..=2)], range_set![r!(3..=3), r!(10..=10)], range_set![r!(11..=12)], range_set![r!(13..=14)]], set.pages(2).collect::<Vec<_>>());
+        assert_eq!(0, RangeSet::<u8>::empty().pages(3).count());
+
+        let set = range_set![r!(250u8..)];
+        assert_eq!(vec![range_set![r!(250..=253)], range_set![r!(254..=255)]], set.pages(4).collect::<Vec<_>>());
+
+        let set: RangeSet<i8> = range_set![r!(..)];
+        assert_eq!(vec![range_set![r!(-128..=-29)], range_set![r!(-28..=71)], range_set![r!(72..=127)]], set.pages(100).collect::<Vec<_>>());
+
+        let set = range_set![r!((i128::MAX - 1)..)];
+        assert_eq!(vec![range_set![r!((i128::MAX - 1)..=i128::MAX)]], set.pages(usize::MAX).collect::<Vec<_>>());
+    }
+}
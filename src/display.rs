@@ -0,0 +1,236 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::slice;
+use std::str::FromStr;
+use crate::{Bound, ParseError, Range, RangeSet, RangeSetError};
+
+impl<T: Display> Display for Range<T> {
+    /// Formats the range in the same notation as the [`r!`](crate::r) macro, e.g. `4>..=6` or `..`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// assert_eq!("4>..=6", r!(4>..=6).to_string());
+    /// assert_eq!("..2", r!(..2).to_string());
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_range(self, f, &display_value)
+    }
+}
+
+impl<T: Ord + Display> Display for RangeSet<T> {
+    /// Formats the set as its ranges between braces, e.g. `{0..4, 6..=10}`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, RangeSet};
+    ///
+    /// assert_eq!("{..0, 4>..}", range_set![r!(..0), r!(4>..)].to_string());
+    /// assert_eq!("{}", RangeSet::<u32>::empty().to_string());
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_ranges(self.items.iter(), f, &display_value)
+    }
+}
+
+impl<T: FromStr> FromStr for Range<T> where T::Err: Display {
+    type Err = ParseError;
+
+    /// Parses a range in the notation it's [displayed](Display) in
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, Range};
+    ///
+    /// assert_eq!(Ok(r!(4>..=6)), "4>..=6".parse::<Range<u32>>());
+    /// assert_eq!(Ok(r!(..)), " .. ".parse::<Range<u32>>());
+    /// assert!("4-6".parse::<Range<u32>>().is_err());
+    /// ```
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (start, end) = input.trim().split_once("..")
+            .ok_or_else(|| ParseError::new(input, "missing `..` between the start and the end"))?;
+
+        let value = |value: &str| value.trim().parse::<T>().map_err(|error| ParseError::new(input, error.to_string()));
+
+        let start = start.trim();
+        let start = match start.strip_suffix('>') {
+            _ if start.is_empty() => Bound::Unbounded,
+            Some(start) => Bound::Excluded(value(start)?),
+            None => Bound::Included(value(start)?),
+        };
+
+        let end = end.trim();
+        let end = match end.strip_prefix('=') {
+            _ if end.is_empty() => Bound::Unbounded,
+            Some(end) => Bound::Included(value(end)?),
+            None => Bound::Excluded(value(end)?),
+        };
+
+        Ok(Range::new(start, end))
+    }
+}
+
+impl<T: Ord + Debug + FromStr> FromStr for RangeSet<T> where T::Err: Display {
+    type Err = RangeSetError<T>;
+
+    /// Parses a set in the notation it's [displayed](Display) in, the ranges don't have to be sorted and may
+    /// overlap, but may not be inverted
+    ///
+    /// Values containing a `,` can't be parsed
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, RangeSet};
+    ///
+    /// assert_eq!(Ok(range_set![r!(0..6), r!(8..)]), "{8.., 0..4, 2..6}".parse::<RangeSet<u32>>());
+    /// assert!("{4..2}".parse::<RangeSet<u32>>().is_err());
+    /// ```
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        RangeSet::try_from_vec(parse_ranges(input)?)
+    }
+}
+
+/// Wrapper displaying a range or set with a custom formatter for its values, see
+/// [`RangeSet::display_with`](RangeSet::display_with) and [`Range::display_with`](Range::display_with)
+pub struct DisplayWith<'a, T, F> {
+    ranges: &'a [Range<T>],
+    braces: bool,
+    format: F,
+}
+
+impl<T, F: Fn(&T, &mut Formatter<'_>) -> std::fmt::Result> Display for DisplayWith<'_, T, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.ranges {
+            [range] if !self.braces => fmt_range(range, f, &self.format),
+            ranges => fmt_ranges(ranges, f, &self.format),
+        }
+    }
+}
+
+impl<T> Range<T> {
+    /// Returns a wrapper that displays this range in its usual notation, formatting the start and end with given
+    /// function instead of their own [`Display`](Display)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// let range = r!(0x1000u32..=0x1fff);
+    ///
+    /// assert_eq!("0x1000..=0x1fff", range.display_with(|value, f| write!(f, "{:#x}", value)).to_string());
+    /// ```
+    pub fn display_with<F: Fn(&T, &mut Formatter<'_>) -> std::fmt::Result>(&self, format: F) -> DisplayWith<'_, T, F> {
+        DisplayWith { ranges: slice::from_ref(self), braces: false, format }
+    }
+}
+
+impl<T: Ord> RangeSet<T> {
+    /// Returns a wrapper that displays this set in its usual notation, formatting every value with given function
+    /// instead of its own [`Display`](Display), so `T` doesn't have to be wrapped just to change how it prints
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(0x1000u32..0x2000), r!(0x8000..)];
+    ///
+    /// assert_eq!("{0x1000..0x2000, 0x8000..}", set.display_with(|value, f| write!(f, "{:#x}", value)).to_string());
+    /// ```
+    pub fn display_with<F: Fn(&T, &mut Formatter<'_>) -> std::fmt::Result>(&self, format: F) -> DisplayWith<'_, T, F> {
+        DisplayWith { ranges: &self.items, braces: true, format }
+    }
+}
+
+/// Formats given value with its own [`Display`](Display), without the flags given for the whole range
+pub(crate) fn display_value<T: Display>(value: &T, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", value)
+}
+
+/// Formats given range in the notation of the [`r!`](crate::r) macro, using `value` to format its start and end
+fn fmt_range<T>(range: &Range<T>, f: &mut Formatter<'_>, value: &impl Fn(&T, &mut Formatter<'_>) -> std::fmt::Result) -> std::fmt::Result {
+    match &range.start {
+        Bound::Included(start) => {
+            value(start, f)?;
+            f.write_str("..")?
+        }
+        Bound::Excluded(start) => {
+            value(start, f)?;
+            f.write_str(">..")?
+        }
+        Bound::Unbounded => f.write_str("..")?,
+    }
+
+    match &range.end {
+        Bound::Included(end) => {
+            f.write_str("=")?;
+            value(end, f)
+        }
+        Bound::Excluded(end) => value(end, f),
+        Bound::Unbounded => Ok(()),
+    }
+}
+
+/// Formats given ranges between braces, e.g. `{0..4, 6..=10}`, using `value` to format their start and end
+pub(crate) fn fmt_ranges<'a, T: 'a>(ranges: impl IntoIterator<Item=&'a Range<T>>, f: &mut Formatter<'_>, value: &impl Fn(&T, &mut Formatter<'_>) -> std::fmt::Result) -> std::fmt::Result {
+    f.write_str("{")?;
+    for (index, range) in ranges.into_iter().enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+
+        fmt_range(range, f, value)?;
+    }
+
+    f.write_str("}")
+}
+
+/// Parses the ranges between braces in the notation of [`fmt_ranges`](fmt_ranges), in the order they're given
+pub(crate) fn parse_ranges<T: FromStr>(input: &str) -> Result<Vec<Range<T>>, ParseError> where T::Err: Display {
+    let inner = input.trim()
+        .strip_prefix('{')
+        .and_then(|inner| inner.strip_suffix('}'))
+        .ok_or_else(|| ParseError::new(input, "ranges have to be surrounded by `{` and `}`"))?;
+
+    if inner.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    inner.split(',')
+        .map(Range::from_str)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, Range, RangeSet, RangeSetError};
+
+    #[test]
+    fn display_parse() {
+        let set: RangeSet<i32> = range_set![r!(..-4), r!(-2..=0), r!(1>..3), r!(5>..)];
+        assert_eq!("{..-4, -2..=0, 1>..3, 5>..}", set.to_string());
+        assert_eq!(Ok(set), "{..-4, -2..=0, 1>..3, 5>..}".parse());
+        assert_eq!(Ok(RangeSet::<i32>::unbound()), "{..}".parse());
+        assert_eq!(Ok(RangeSet::<i32>::empty()), " { } ".parse());
+
+        assert_eq!(Ok(r!(1.5>..2.5)), "1.5>..2.5".parse::<Range<f64>>());
+
+        assert!(matches!("0..4".parse::<RangeSet<i32>>(), Err(RangeSetError::Parse(_))));
+        assert!(matches!("{0..x}".parse::<RangeSet<i32>>(), Err(RangeSetError::Parse(_))));
+        assert!(matches!("{4..=3}".parse::<RangeSet<i32>>(), Err(RangeSetError::InvalidRange(_))));
+    }
+
+    #[test]
+    fn display_with() {
+        let set: RangeSet<i32> = range_set![r!(..-4), r!(-2..=0), r!(1>..3), r!(5>..)];
+        assert_eq!("{..(-4), (-2)..=(0), (1)>..(3), (5)>..}", set.display_with(|value, f| write!(f, "({})", value)).to_string());
+        assert_eq!("{}", RangeSet::<i32>::empty().display_with(|_, _| unreachable!()).to_string());
+        assert_eq!("..", r!(..).display_with(|_: &i32, _| unreachable!()).to_string());
+        assert_eq!("  2>..=  4", r!(2>..=4).display_with(|value, f| write!(f, "{:3}", value)).to_string());
+    }
+}
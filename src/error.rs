@@ -0,0 +1,285 @@
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use crate::{Range, RangeSet};
+
+/// The errors the fallible methods of this crate can return
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum RangeSetError<T: Ord> {
+    /// A range of which the end comes before its start was given
+    InvalidRange(InvalidRangeError<T>),
+    /// A range was given out of order
+    Unsorted(UnsortedError<T>),
+    /// There wasn't enough memory to hold the ranges
+    CapacityExceeded,
+    /// A set or range couldn't be parsed
+    Parse(ParseError),
+    /// A range overlapped with ranges already in the set
+    Conflict(Conflict<T>),
+}
+
+impl<T: Ord> From<InvalidRangeError<T>> for RangeSetError<T> {
+    fn from(value: InvalidRangeError<T>) -> Self {
+        RangeSetError::InvalidRange(value)
+    }
+}
+
+impl<T: Ord> From<UnsortedError<T>> for RangeSetError<T> {
+    fn from(value: UnsortedError<T>) -> Self {
+        RangeSetError::Unsorted(value)
+    }
+}
+
+impl<T: Ord> From<ParseError> for RangeSetError<T> {
+    fn from(value: ParseError) -> Self {
+        RangeSetError::Parse(value)
+    }
+}
+
+impl<T: Ord> From<Conflict<T>> for RangeSetError<T> {
+    fn from(value: Conflict<T>) -> Self {
+        RangeSetError::Conflict(value)
+    }
+}
+
+impl<T: Ord + Debug> Display for RangeSetError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RangeSetError::InvalidRange(error) => Display::fmt(error, f),
+            RangeSetError::Unsorted(error) => Display::fmt(error, f),
+            RangeSetError::CapacityExceeded => write!(f, "not enough memory to hold the ranges"),
+            RangeSetError::Parse(error) => Display::fmt(error, f),
+            RangeSetError::Conflict(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl<T: Ord + Debug + 'static> Error for RangeSetError<T> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RangeSetError::InvalidRange(error) => Some(error),
+            RangeSetError::Unsorted(error) => Some(error),
+            RangeSetError::CapacityExceeded => None,
+            RangeSetError::Parse(error) => Some(error),
+            RangeSetError::Conflict(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned when parsing a range or set fails, holds the text that couldn't be parsed and why
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    input: String,
+    reason: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(input: &str, reason: impl Into<String>) -> Self {
+        ParseError { input: input.to_string(), reason: reason.into() }
+    }
+
+    /// The text that couldn't be parsed
+    #[inline]
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Why the text couldn't be parsed
+    #[inline]
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to parse {:?}: {}", self.input, self.reason)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Error returned when a range is given out of order, holds the rejected range
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnsortedError<T: Ord> {
+    range: Range<T>,
+}
+
+impl<T: Ord> UnsortedError<T> {
+    pub(crate) fn new(range: Range<T>) -> Self {
+        UnsortedError { range }
+    }
+
+    /// The range that was rejected
+    #[inline]
+    pub fn range(&self) -> &Range<T> {
+        &self.range
+    }
+
+    /// Returns the range that was rejected
+    #[inline]
+    pub fn into_range(self) -> Range<T> {
+        self.range
+    }
+}
+
+impl<T: Ord + Debug> Display for UnsortedError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "range {:?} starts before the previous range", self.range)
+    }
+}
+
+impl<T: Ord + Debug> Error for UnsortedError<T> {}
+
+/// Error returned when a range of which the end comes before its start is rejected, holds the rejected range
+///
+/// See [`InvalidRangePolicy`](crate::InvalidRangePolicy)
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InvalidRangeError<T: Ord> {
+    range: Range<T>,
+}
+
+impl<T: Ord> InvalidRangeError<T> {
+    pub(crate) fn new(range: Range<T>) -> Self {
+        InvalidRangeError { range }
+    }
+
+    /// The range that was rejected
+    #[inline]
+    pub fn range(&self) -> &Range<T> {
+        &self.range
+    }
+
+    /// Returns the range that was rejected
+    #[inline]
+    pub fn into_range(self) -> Range<T> {
+        self.range
+    }
+}
+
+impl<T: Ord + Debug> Display for InvalidRangeError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "range {:?} ends before it starts", self.range)
+    }
+}
+
+impl<T: Ord + Debug> Error for InvalidRangeError<T> {}
+
+/// Error returned by [`RangeSet::try_map`](crate::RangeSet::try_map), holds the range that failed to map and the
+/// error returned for it
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MapError<T: Ord, E> {
+    range: Range<T>,
+    error: E,
+}
+
+impl<T: Ord, E> MapError<T, E> {
+    pub(crate) fn new(range: Range<T>, error: E) -> Self {
+        MapError { range, error }
+    }
+
+    /// The range that failed to map
+    #[inline]
+    pub fn range(&self) -> &Range<T> {
+        &self.range
+    }
+
+    /// The error returned while mapping the range
+    #[inline]
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Returns the range that failed to map and the error returned while mapping it
+    #[inline]
+    pub fn into_inner(self) -> (Range<T>, E) {
+        (self.range, self.error)
+    }
+}
+
+impl<T: Ord + Debug, E: Display> Display for MapError<T, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to map range {:?}: {}", self.range, self.error)
+    }
+}
+
+impl<T: Ord + Debug, E: Error + 'static> Error for MapError<T, E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Error returned as [`RangeSetError::Conflict`](RangeSetError::Conflict) by
+/// [`RangeSet::try_insert_exclusive`](crate::RangeSet::try_insert_exclusive), holds the rejected range and the parts
+/// of it that are already covered
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Conflict<T: Ord> {
+    range: Range<T>,
+    conflicts: RangeSet<T>,
+}
+
+impl<T: Ord> Conflict<T> {
+    pub(crate) fn new(range: Range<T>, conflicts: RangeSet<T>) -> Self {
+        Conflict { range, conflicts }
+    }
+
+    /// The range that was rejected
+    #[inline]
+    pub fn range(&self) -> &Range<T> {
+        &self.range
+    }
+
+    /// The parts of the rejected range that are already covered
+    #[inline]
+    pub fn conflicts(&self) -> &RangeSet<T> {
+        &self.conflicts
+    }
+
+    /// Returns the range that was rejected and the parts of it that are already covered
+    #[inline]
+    pub fn into_inner(self) -> (Range<T>, RangeSet<T>) {
+        (self.range, self.conflicts)
+    }
+}
+
+impl<T: Ord + Debug> Display for Conflict<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "range {:?} overlaps with {:?}", self.range, self.conflicts.items)
+    }
+}
+
+impl<T: Ord + Debug> Error for Conflict<T> {}
+
+/// Error returned when converting a set into a type that can't represent one of its ranges, e.g. a `rangemap` set,
+/// holds the range that couldn't be converted
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnrepresentableError<T: Ord> {
+    range: Range<T>,
+}
+
+impl<T: Ord> UnrepresentableError<T> {
+    #[cfg_attr(not(feature = "rangemap"), allow(dead_code))]
+    pub(crate) fn new(range: Range<T>) -> Self {
+        UnrepresentableError { range }
+    }
+
+    /// The range that couldn't be converted
+    #[inline]
+    pub fn range(&self) -> &Range<T> {
+        &self.range
+    }
+
+    /// Returns the range that couldn't be converted
+    #[inline]
+    pub fn into_range(self) -> Range<T> {
+        self.range
+    }
+}
+
+impl<T: Ord + Debug> Display for UnrepresentableError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "range {:?} can't be represented", self.range)
+    }
+}
+
+impl<T: Ord + Debug> Error for UnrepresentableError<T> {}
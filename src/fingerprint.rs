@@ -0,0 +1,91 @@
+use std::fmt::Debug;
+use std::io::{self, Write};
+use crate::{Codec, Discrete, RangeSet};
+use crate::values::value_bounds;
+
+/// 64-bit FNV-1a, which is simple enough to stay the same across versions and platforms
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+}
+
+impl Write for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(Self::PRIME);
+        }
+
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<T: Discrete + Clone + Debug + Codec> RangeSet<T> {
+    /// A digest of the values in this set, which is the same for every set holding the same values
+    ///
+    /// The digest is calculated over the first and last value of every run of consecutive values in their
+    /// [`Codec`](Codec) encoding, so it doesn't depend on how the bounds are written, on the platform or on the
+    /// features of this crate, which makes it suitable to compare sets between machines. It's not a cryptographic
+    /// hash, so it shouldn't be relied on when the sets come from an untrusted source
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let left = range_set![r!(1u32..=4), r!(5..10)];
+    /// let right = range_set![r!(0u32>..10)];
+    ///
+    /// assert_eq!(left.fingerprint(), right.fingerprint());
+    /// assert_ne!(left.fingerprint(), range_set![r!(1u32..11)].fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a(Fnv1a::OFFSET_BASIS);
+        let mut write = |(first, last): (T, T)| {
+            first.encode(&mut hasher).and_then(|()| last.encode(&mut hasher)).expect("hashing can't fail");
+        };
+
+        let mut run: Option<(T, T)> = None;
+        for (first, last) in self.items.iter().filter_map(value_bounds) {
+            run = match run {
+                Some((start, end)) if end.successor().as_ref() == Some(&first) => Some((start, last)),
+                Some(previous) => {
+                    write(previous);
+                    Some((first, last))
+                }
+                None => Some((first, last)),
+            };
+        }
+
+        if let Some(run) = run {
+            write(run);
+        }
+
+        hasher.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeSet};
+
+    #[test]
+    fn fingerprint() {
+        assert_eq!(0xcbf29ce484222325, RangeSet::<u8>::empty().fingerprint());
+        assert_eq!(RangeSet::<u8>::empty().fingerprint(), range_set![r!(3u8>..4)].fingerprint());
+
+        // `..=4` and `4>..` aren't merged, but hold the same values as `..`
+        let unbound = RangeSet::<i8>::unbound().fingerprint();
+        assert_eq!(unbound, range_set![r!(..=4i8), r!(4>..)].fingerprint());
+        assert_eq!(unbound, range_set![r!(-128i8..=127)].fingerprint());
+        assert_ne!(unbound, range_set![r!(..=4i8), r!(5>..)].fingerprint());
+
+        // The encoding of u8 `[1, 2]` hashes to a known value
+        assert_eq!(0x082f2407b4e8902a, range_set![r!(1u8..=2)].fingerprint());
+    }
+}
@@ -0,0 +1,127 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::ops::RangeBounds;
+use crate::{cmp_bounds, holds_values, PositionalBound, Range, RangeSet};
+use crate::Bound;
+
+/// An immutable set optimized for queries, created by [`RangeSet::freeze`](RangeSet::freeze)
+///
+/// The starts and ends of the ranges are stored in separate arrays, so the binary search of a query only touches
+/// the ends it compares against, which fit more of them in a cache line
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set};
+///
+/// let frozen = range_set![r!(0..10), r!(20..30)].freeze();
+///
+/// assert!(frozen.contains(&25));
+/// assert!(!frozen.contains(&15));
+/// assert_eq!(vec![r!((&20)..&30)], frozen.overlapping(r!(15..25)).collect::<Vec<_>>());
+/// assert_eq!(range_set![r!(0..10), r!(20..30)], frozen.thaw());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct FrozenRangeSet<T: Ord> {
+    starts: Box<[Bound<T>]>,
+    ends: Box<[Bound<T>]>,
+}
+
+impl<T: Ord + Debug> RangeSet<T> {
+    /// Turn this set into an immutable set optimized for queries
+    pub fn freeze(self) -> FrozenRangeSet<T> {
+        let (starts, ends): (Vec<_>, Vec<_>) = self.items.into_iter().map(Range::into_inner).unzip();
+        FrozenRangeSet { starts: starts.into(), ends: ends.into() }
+    }
+}
+
+impl<T: Ord + Debug> FrozenRangeSet<T> {
+    /// The amount of ranges in this set
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// If this is an empty set
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// Returns an iterator over the ranges in this set
+    pub fn items(&self) -> impl Iterator<Item=Range<&T>> + '_ {
+        self.starts.iter().zip(self.ends.iter()).map(|(start, end)| Range::new(start.as_ref(), end.as_ref()))
+    }
+
+    /// Check if `other` is inside of this set, any borrowed form of `T` can be given
+    pub fn contains<Q: Ord + ?Sized>(&self, other: &Q) -> bool where T: Borrow<Q> {
+        let index = self.ends.partition_point(|end| PositionalBound::End(end.as_ref().map(Borrow::borrow)) < other);
+        self.starts.get(index).is_some_and(|start| PositionalBound::Start(start.as_ref().map(Borrow::borrow)) < other)
+    }
+
+    /// Returns an iterator over the ranges in this set that overlap with given range
+    pub fn overlapping(&self, range: impl RangeBounds<T>) -> impl Iterator<Item=Range<&T>> + '_ {
+        let (start, end) = (PositionalBound::Start(range.start_bound()), PositionalBound::End(range.end_bound()));
+        let first = self.ends.partition_point(|item| cmp_bounds(PositionalBound::End(item.as_ref()), start).is_le());
+        let last = if holds_values(*start, *end) {
+            first + self.starts[first..].partition_point(|item| cmp_bounds(PositionalBound::Start(item.as_ref()), end).is_lt())
+        } else {
+            first
+        };
+
+        self.starts[first..last].iter()
+            .zip(self.ends[first..last].iter())
+            .map(|(start, end)| Range::new(start.as_ref(), end.as_ref()))
+    }
+
+    /// Turn this set back into a mutable set
+    pub fn thaw(self) -> RangeSet<T> {
+        RangeSet {
+            items: self.starts.into_vec().into_iter()
+                .zip(self.ends.into_vec())
+                .map(|(start, end)| Range::new(start, end))
+                .collect(),
+        }
+    }
+}
+
+impl<T: Ord + Debug> From<RangeSet<T>> for FrozenRangeSet<T> {
+    fn from(value: RangeSet<T>) -> Self {
+        value.freeze()
+    }
+}
+
+impl<T: Ord + Debug> From<FrozenRangeSet<T>> for RangeSet<T> {
+    fn from(value: FrozenRangeSet<T>) -> Self {
+        value.thaw()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeSet};
+
+    #[test]
+    fn frozen() {
+        let set = range_set![r!(..0), r!(4..8), r!(10>..=20), r!(30..)];
+        let frozen = set.clone().freeze();
+        assert_eq!(4, frozen.len());
+        assert_eq!(set.items().map(|range| range.as_ref()).collect::<Vec<_>>(), frozen.items().collect::<Vec<_>>());
+
+        for value in -5..40 {
+            assert_eq!(set.contains(&value), frozen.contains(&value), "{}", value);
+        }
+
+        for query in [r!(..), r!(0..4), r!(0..=4), r!(8..=10), r!(8..=11), r!(20>..30), r!(25..), r!(5..5), r!(6..5)] {
+            let expected: Vec<_> = set.as_ref().overlapping(query.clone()).map(|range| range.as_ref()).collect();
+            assert_eq!(expected, frozen.overlapping(query.clone()).collect::<Vec<_>>(), "{}", query);
+        }
+
+        assert_eq!(set, frozen.thaw());
+
+        let empty = RangeSet::<u8>::empty().freeze();
+        assert!(empty.is_empty());
+        assert!(!empty.contains(&0));
+        assert_eq!(0, empty.overlapping(r!(..)).count());
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt::Debug;
+use crate::{Range, RangeSet};
+
+/// A reversible change to a set, holding exactly the parts that were added and removed
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Patch<T: Ord> {
+    added: RangeSet<T>,
+    removed: RangeSet<T>,
+}
+
+impl<T: Ord + Clone + Debug> Patch<T> {
+    /// The parts this patch adds
+    #[inline]
+    pub fn added(&self) -> &RangeSet<T> {
+        &self.added
+    }
+
+    /// The parts this patch removes
+    #[inline]
+    pub fn removed(&self) -> &RangeSet<T> {
+        &self.removed
+    }
+
+    /// Returns the patch that reverts this patch
+    pub fn inverse(&self) -> Patch<T> {
+        Patch { added: self.removed.clone(), removed: self.added.clone() }
+    }
+
+    /// Apply this patch to given set
+    pub fn apply(&self, set: &mut RangeSet<T>) {
+        if !self.removed.is_empty() {
+            *set = set.difference(&self.removed);
+        }
+
+        if !self.added.is_empty() {
+            *set = set.union(&self.added);
+        }
+    }
+}
+
+/// A set that records every modification as [`Patch`](Patch), so modifications can be undone, redone and replayed
+/// onto another set
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, Journal};
+///
+/// let mut selection = Journal::new(range_set![r!(0..10)]);
+/// selection.add(r!(5..15));
+/// selection.remove(r!(0..2));
+/// assert_eq!(&range_set![r!(2..15)], selection.set());
+///
+/// selection.undo();
+/// assert_eq!(&range_set![r!(0..15)], selection.set());
+/// selection.undo();
+/// assert_eq!(&range_set![r!(0..10)], selection.set());
+/// selection.redo();
+/// assert_eq!(&range_set![r!(0..15)], selection.set());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Journal<T: Ord> {
+    set: RangeSet<T>,
+    undo: Vec<Patch<T>>,
+    redo: Vec<Patch<T>>,
+}
+
+impl<T: Ord + Debug> Default for Journal<T> {
+    fn default() -> Self {
+        Journal::new(RangeSet::new())
+    }
+}
+
+impl<T: Ord> Journal<T> {
+    /// Start recording modifications to given set
+    pub fn new(set: RangeSet<T>) -> Self {
+        Journal { set, undo: vec![], redo: vec![] }
+    }
+
+    /// The current state of the set
+    #[inline]
+    pub fn set(&self) -> &RangeSet<T> {
+        &self.set
+    }
+
+    /// The patches that can be undone, oldest first
+    #[inline]
+    pub fn patches(&self) -> &[Patch<T>] {
+        &self.undo
+    }
+
+    /// Returns `true` if there's a patch to undo
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns `true` if there's a patch to redo
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forget all recorded patches, keeping the current state
+    pub fn clear_history(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Stop recording and return the current state of the set
+    pub fn into_inner(self) -> RangeSet<T> {
+        self.set
+    }
+}
+
+impl<T: Ord + Clone + Debug> Journal<T> {
+    /// Add a new range to the set, see [`RangeSet::add`](RangeSet::add)
+    ///
+    /// Nothing is recorded if the range was already covered
+    pub fn add(&mut self, range: impl Into<Range<T>>) {
+        let added = RangeSet::from([range.into()]).difference(&self.set);
+        self.record(Patch { added, removed: RangeSet::empty() });
+    }
+
+    /// Remove a range from the set, see [`RangeSet::remove`](RangeSet::remove)
+    ///
+    /// Nothing is recorded if the range wasn't covered
+    pub fn remove(&mut self, range: impl Into<Range<T>>) {
+        let removed = RangeSet::from([range.into()]).intersection(&self.set);
+        self.record(Patch { added: RangeSet::empty(), removed });
+    }
+
+    /// Revert the last patch, returns `false` if there was nothing to undo
+    pub fn undo(&mut self) -> bool {
+        let Some(patch) = self.undo.pop() else {
+            return false;
+        };
+
+        patch.inverse().apply(&mut self.set);
+        self.redo.push(patch);
+        true
+    }
+
+    /// Apply the last undone patch again, returns `false` if there was nothing to redo
+    pub fn redo(&mut self) -> bool {
+        let Some(patch) = self.redo.pop() else {
+            return false;
+        };
+
+        patch.apply(&mut self.set);
+        self.undo.push(patch);
+        true
+    }
+
+    /// Apply every recorded patch, oldest first, to given set
+    pub fn replay(&self, set: &mut RangeSet<T>) {
+        for patch in &self.undo {
+            patch.apply(set);
+        }
+    }
+
+    fn record(&mut self, patch: Patch<T>) {
+        if patch.added.is_empty() && patch.removed.is_empty() {
+            return;
+        }
+
+        patch.apply(&mut self.set);
+        self.undo.push(patch);
+        self.redo.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, Journal};
+
+    #[test]
+    fn journal() {
+        let mut journal = Journal::new(range_set![r!(0..10)]);
+        assert!(!journal.can_undo());
+        assert!(!journal.undo());
+
+        // Already covered, so nothing is recorded
+        journal.add(r!(2..4));
+        journal.remove(r!(20..30));
+        assert!(!journal.can_undo());
+
+        journal.add(r!(5..15));
+        journal.remove(r!(..3));
+        journal.add(r!(20..));
+        assert_eq!(&range_set![r!(3..15), r!(20..)], journal.set());
+        assert_eq!(&range_set![r!(10..15)], journal.patches()[0].added());
+        assert_eq!(&range_set![r!(0..3)], journal.patches()[1].removed());
+
+        let mut mirror = range_set![r!(-5..1), r!(12..13)];
+        journal.replay(&mut mirror);
+        assert_eq!(range_set![r!(-5..0), r!(10..15), r!(20..)], mirror);
+
+        assert!(journal.undo());
+        assert!(journal.undo());
+        assert_eq!(&range_set![r!(0..15)], journal.set());
+        assert!(journal.redo());
+        assert_eq!(&range_set![r!(3..15)], journal.set());
+
+        // A new modification drops the patches that could be redone
+        journal.add(r!(1..2));
+        assert!(!journal.can_redo());
+        assert!(!journal.redo());
+
+        while journal.undo() {}
+        assert_eq!(range_set![r!(0..10)], journal.into_inner());
+    }
+}
@@ -0,0 +1,122 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use crate::{Range, RangeSet};
+use crate::macros::trace_span;
+
+/// The least amount of pending ranges before they're merged into the set
+const MIN_PENDING: usize = 32;
+
+/// A [`RangeSet`](RangeSet) that buffers added ranges and merges them into the sorted ranges in batches
+///
+/// Adding to a `RangeSet` is `O(n)` as the ranges after the added range have to be moved, here added ranges are kept
+/// aside until there are as many of them as an eighth of the ranges in the set, then they're all merged at once,
+/// making adding `O(log n)` amortized, while lookups also check the pending ranges
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, LazyRangeSet};
+///
+/// let mut set = LazyRangeSet::new();
+/// for start in (0..100).rev() {
+///     set.add(r!((start * 10)..start * 10 + 5));
+/// }
+///
+/// assert!(set.contains(&42));
+/// assert!(!set.contains(&47));
+/// assert_eq!(100, set.as_set().items().count());
+/// ```
+#[derive(Debug, Clone)]
+pub struct LazyRangeSet<T: Ord> {
+    set: RangeSet<T>,
+    pending: Vec<Range<T>>,
+}
+
+impl<T: Ord + Debug> Default for LazyRangeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> From<RangeSet<T>> for LazyRangeSet<T> {
+    fn from(set: RangeSet<T>) -> Self {
+        LazyRangeSet { set, pending: vec![] }
+    }
+}
+
+impl<T: Ord + Debug> LazyRangeSet<T> {
+    /// Create a new empty set
+    pub fn new() -> Self {
+        Self::from(RangeSet::new())
+    }
+
+    /// Returns `true` if given item falls within any range of this set, including the ranges that aren't merged yet
+    pub fn contains<Q: Ord + ?Sized>(&self, item: &Q) -> bool where T: Borrow<Q> {
+        self.set.contains(item) || self.pending.iter().any(|range| range.contains(item))
+    }
+
+    /// The amount of added ranges that aren't merged into the set yet
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+impl<T: Ord + Clone + Debug> LazyRangeSet<T> {
+    /// Add a new range to this set, merging all pending ranges once there are enough of them
+    pub fn add(&mut self, range: impl Into<Range<T>>) {
+        self.pending.push(range.into());
+        if self.pending.len() >= MIN_PENDING.max(self.set.items.len() / 8) {
+            self.flush();
+        }
+    }
+
+    /// Merge all pending ranges into the set
+    pub fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        trace_span!("flush", ranges = self.set.items.len(), pending = self.pending.len());
+        let pending = RangeSet::from(std::mem::take(&mut self.pending));
+        self.set = self.set.union(&pending);
+    }
+
+    /// Returns the set after merging all pending ranges
+    pub fn as_set(&mut self) -> &RangeSet<T> {
+        self.flush();
+        &self.set
+    }
+
+    /// Returns the set after merging all pending ranges
+    pub fn into_set(mut self) -> RangeSet<T> {
+        self.flush();
+        self.set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, LazyRangeSet, RangeSet};
+
+    #[test]
+    fn lazy() {
+        let mut lazy = LazyRangeSet::from(range_set![r!(0..10)]);
+        let mut eager = range_set![r!(0..10)];
+
+        for index in 0..1000u32 {
+            let start = (index * 7919) % 5000;
+            let range = r!((start)..start + 3);
+            lazy.add(range.clone());
+            eager.add(range);
+
+            assert!(lazy.contains(&start));
+            assert!(lazy.pending() < 1000);
+        }
+
+        assert_eq!(eager, lazy.clone().into_set());
+        assert_eq!(&eager, lazy.as_set());
+        assert_eq!(0, lazy.pending());
+        assert_eq!(RangeSet::<u32>::empty(), LazyRangeSet::new().into_set());
+    }
+}
@@ -8,22 +8,128 @@
 //!
 //! This will change in the future
 
+use std::borrow::Borrow;
 use std::fmt::Debug;
-use std::{mem};
 use std::cmp::Ordering;
+use std::mem;
 use std::ops::{Deref, RangeBounds};
 use crate::Bound::{Excluded, Included, Unbounded};
-use crate::internal::LinearRangeAdder;
-
-
-mod internal;
+use crate::macros::trace_span;
+use crate::storage::Ranges;
+
+
+mod allocator;
+mod bounded;
+mod builder;
+mod byte_set;
+mod circular;
+mod coalesce;
+mod codec;
+mod collect;
+mod combined;
+mod complement;
+mod composite;
+mod compressed;
+mod concurrent;
 mod conversions;
+mod discrete;
+mod display;
+mod error;
+mod fingerprint;
+mod frozen;
+mod journal;
+mod lazy;
 mod macros;
+mod map;
+mod measure;
+mod merge;
+mod offset_map;
+mod persistent;
+mod policy;
+mod predicate;
+mod prefix;
+mod range_list;
+mod range_map;
+mod set_by;
+mod set_like;
+mod set_ref;
+mod shared;
+mod shift;
+mod state_map;
+mod storage;
+mod sweep;
+mod time_ranges;
+mod values;
+mod view;
+mod weight;
+mod workspace;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rangemap")]
+mod rangemap_compat;
+#[cfg(feature = "serde")]
+pub mod serialize;
+#[cfg(feature = "async")]
+mod stream;
+#[cfg(feature = "svg")]
+mod svg;
+#[cfg(feature = "semver")]
+mod version;
 
 /// Re-export for ease
 pub use std::ops::Bound;
 
 pub use crate::r as range;
+pub use crate::allocator::{RangeAllocator, Relocation};
+pub use crate::bounded::{BoundedRangeSet, Domain, FullDomain};
+pub use crate::builder::{BufferedBuilder, RangeSetBuilder};
+pub use crate::byte_set::ByteSet;
+pub use crate::circular::CircularRangeSet;
+pub use crate::coalesce::{Adjacent, Coalesce, Overlapping, Touching};
+pub use crate::codec::Codec;
+pub use crate::collect::{group_ranges_by, CollectRanges, ConsecutiveRanges, GroupRanges};
+pub use crate::combined::{IntersectionView, UnionView};
+pub use crate::complement::ComplementView;
+pub use crate::composite::{composite_range, composite_range_with};
+pub use crate::compressed::CompressedRangeSet;
+pub use crate::concurrent::ConcurrentRangeSet;
+pub use crate::discrete::{Discrete, DiscreteItem};
+pub use crate::display::DisplayWith;
+pub use crate::error::{Conflict, InvalidRangeError, MapError, ParseError, RangeSetError, UnrepresentableError, UnsortedError};
+pub use crate::frozen::FrozenRangeSet;
+pub use crate::journal::{Journal, Patch};
+pub use crate::lazy::LazyRangeSet;
+#[doc(hidden)]
+pub use crate::macros::range_set_eq_failed;
+pub use crate::measure::{Advance, Measure, ToF64};
+pub use crate::merge::{merge_sorted, MergeSorted};
+#[cfg(feature = "mmap")]
+pub use crate::mmap::MmapRangeSet;
+pub use crate::offset_map::OffsetMap;
+#[cfg(feature = "rayon")]
+pub use crate::parallel::ParValues;
+pub use crate::persistent::PersistentRangeSet;
+pub use crate::policy::InvalidRangePolicy;
+pub use crate::predicate::Cmp;
+pub use crate::prefix::{prefix_range, PrefixKey};
+pub use crate::range_list::RangeList;
+pub use crate::range_map::RangeMap;
+pub use crate::set_by::{ByKey, Compare, RangeSetBy, RangeSetByKey};
+pub use crate::set_like::RangeSetLike;
+pub use crate::set_ref::RangeSetRef;
+pub use crate::shared::SharedRangeSet;
+pub use crate::shift::Shift;
+pub use crate::state_map::RangeStateMap;
+#[cfg(feature = "svg")]
+pub use crate::svg::Timeline;
+pub use crate::sweep::{covered_by_at_least, refine};
+pub use crate::time_ranges::TimeRanges;
+pub use crate::values::{ExactValues, RangeValues, ValueCursor, Values, ValuesChunks};
+pub use crate::view::RangeSetView;
+pub use crate::weight::WeightProfile;
+pub use crate::workspace::Workspace;
 
 /// The list type used for storing multiple ranges in a set
 ///
@@ -38,14 +144,23 @@ pub type RangeVec<T> = smallvec::SmallVec<[T; 5]>;
 pub type RangeVec<T> = Vec<T>;
 
 /// A set of ranges
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq)]
 pub struct RangeSet<T: Ord> {
-    pub(crate) items: RangeVec<Range<T>>,
+    pub(crate) items: Ranges<Range<T>>,
+}
+
+impl<T: Ord> PartialEq for RangeSet<T> {
+    /// Sets are equal when they hold the same values, so touching ranges kept apart, e.g. by
+    /// [`split_boundary_at`](RangeSet::split_boundary_at), equal the range they form together and ranges holding no
+    /// values are ignored
+    fn eq(&self, other: &Self) -> bool {
+        coalesced(&self.items).eq(coalesced(&other.items))
+    }
 }
 
 impl<T: Ord + Debug> Default for RangeSet<T> {
     fn default() -> Self {
-        Self::with_capacity(5)
+        RangeSet { items: Ranges::Empty }
     }
 }
 
@@ -59,14 +174,45 @@ impl<T: Ord + Debug> RangeSet<T> {
     }
 
     pub fn unbound() -> Self {
-        let mut items = RangeVec::with_capacity(1);
-        items.push(Range::unbound());
+        Self {
+            items: Ranges::Single(Range::unbound()),
+        }
+    }
+
+    /// Create a set holding only given range, which is empty if the range holds nothing
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, RangeSet};
+    ///
+    /// assert_eq!(range_set![r!(4..8)], RangeSet::single(4..8));
+    /// assert_eq!(RangeSet::empty(), RangeSet::single(8..4));
+    /// ```
+    pub fn single(range: impl Into<Range<T>>) -> Self {
+        let range = range.into();
+        if range.is_inverted() || range.is_empty() {
+            return Self::empty();
+        }
 
         Self {
-            items,
+            items: Ranges::Single(range),
         }
     }
 
+    /// Create a set holding only given value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, RangeSet};
+    ///
+    /// assert_eq!(range_set![r!(4..=4)], RangeSet::point(4));
+    /// ```
+    pub fn point(value: T) -> Self where T: Clone {
+        Self::single(Range::new(Included(value.clone()), Included(value)))
+    }
+
     /// If this is an empty set
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -83,34 +229,156 @@ impl<T: Ord + Debug> RangeSet<T> {
     #[inline]
     pub fn with_capacity(data: usize) -> Self {
         RangeSet {
-            items: RangeVec::with_capacity(data),
+            items: Ranges::with_capacity(data),
         }
     }
 
+    /// The amount of bytes this set has allocated on the heap to store its ranges, memory owned by the values
+    /// themselves isn't counted, see [`heap_bytes_with`](RangeSet::heap_bytes_with) for that
+    ///
+    /// Sets with a single range, and with the `smallvec` feature sets with a few ranges, don't allocate
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// assert_eq!(0, range_set![r!(0u64..4)].heap_bytes());
+    /// ```
+    #[inline]
+    pub fn heap_bytes(&self) -> usize {
+        self.items.heap_bytes()
+    }
+
+    /// The amount of bytes this set has allocated on the heap, including the memory owned by every boundary value as
+    /// reported by given function
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!((String::from("a"))..String::from("c"))];
+    ///
+    /// assert_eq!(2, set.heap_bytes_with(|value| value.capacity()));
+    /// ```
+    pub fn heap_bytes_with(&self, value_bytes: impl Fn(&T) -> usize) -> usize {
+        let value_bytes = |bound: &Bound<T>| match bound {
+            Included(value) | Excluded(value) => value_bytes(value),
+            Unbounded => 0,
+        };
+
+        self.items.iter()
+            .map(|range| value_bytes(&range.start) + value_bytes(&range.end))
+            .sum::<usize>() + self.heap_bytes()
+    }
+
     /// Returns an iterator with all ranges inside of this set
     #[inline]
     pub fn items(&self) -> impl Iterator<Item=&Range<T>> {
         self.items.iter()
     }
 
+    /// Returns a borrowed, read-only view of this set
+    #[inline]
+    pub fn as_ref(&self) -> RangeSetRef<'_, T> {
+        RangeSetRef::from(self)
+    }
+
     /// Check if `other` falls within the ranges defined in this set
-    pub fn contains(&self, other: &T) -> bool {
-        for range in self.items() {
-            if range.start_pos() < other {
-                if range.end_pos() > other {
-                    return true;
-                }
-            } else {
-                // Window got overshot
-                break;
-            }
-        }
+    ///
+    /// Alike [`BTreeSet::contains`](std::collections::BTreeSet::contains) any borrowed form of `T` can be given
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, RangeSet};
+    ///
+    /// let keys: RangeSet<String> = range_set![r!(("a".to_string()).."c".to_string())];
+    ///
+    /// assert!(keys.contains("b"));
+    /// assert!(!keys.contains("c"));
+    /// ```
+    pub fn contains<Q: Ord + ?Sized>(&self, other: &Q) -> bool where T: Borrow<Q> {
+        self.as_ref().contains(other)
+    }
 
-        false
+    /// Check if `other` falls within the ranges defined in this set, starting the search at the range index in `hint`
+    ///
+    /// The hint is updated to the index of the range `other` falls in, or the range after it, so checking
+    /// increasing values one after another only takes a few steps each
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(0..4), r!(10..20), r!(30..)];
+    /// let mut hint = 0;
+    ///
+    /// let matched: Vec<_> = (0..40).step_by(3).filter(|offset| set.contains_hint(offset, &mut hint)).collect();
+    /// assert_eq!(vec![0, 3, 12, 15, 18, 30, 33, 36, 39], matched);
+    /// assert_eq!(2, hint);
+    /// ```
+    pub fn contains_hint<Q: Ord + ?Sized>(&self, other: &Q, hint: &mut usize) -> bool where T: Borrow<Q> {
+        self.as_ref().contains_hint(other, hint)
+    }
+
+    /// Returns the range in this set that contains `other`, if any
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(0..4), r!(10..20)];
+    ///
+    /// assert_eq!(Some(&r!(10..20)), set.find(&12));
+    /// assert_eq!(None, set.find(&4));
+    /// ```
+    pub fn find<Q: Ord + ?Sized>(&self, other: &Q) -> Option<&Range<T>> where T: Borrow<Q> {
+        self.as_ref().find(other)
+    }
+
+    /// Returns the amount of ranges in this set that overlap with given window, found with two binary searches
+    /// without going over the ranges
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(0..4), r!(6..10), r!(12..20)];
+    ///
+    /// assert_eq!(2, set.overlap_count(4..=12));
+    /// assert_eq!(0, set.overlap_count(10..12));
+    /// ```
+    pub fn overlap_count(&self, window: impl RangeBounds<T>) -> usize {
+        self.as_ref().overlap_count(window)
+    }
+
+    /// Returns `true` if any range in this set shares a value with given range, found with a binary search
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(0..4), r!(6..10)];
+    ///
+    /// assert!(set.intersects(3..6));
+    /// assert!(set.intersects(r!(9..)));
+    /// assert!(!set.intersects(4..6));
+    /// assert!(!set.intersects(2..2));
+    /// ```
+    pub fn intersects(&self, range: impl RangeBounds<T>) -> bool {
+        self.as_ref().intersects(range)
     }
 
     /// Add a new range to this set
     ///
+    /// Like every method taking a range, this accepts the std range types as well
+    ///
     /// # Example
     ///
     /// ```rust
@@ -118,13 +386,14 @@ impl<T: Ord + Debug> RangeSet<T> {
     ///
     /// let mut r = range_set![usize:];
     /// r.add(r!(4..));
-    /// r.add(r!(3..5));
+    /// r.add(3..5);
     ///
     /// assert_eq!(range_set![r!(3..)], r);
     /// ```
-    pub fn add(&mut self, range: Range<T>) {
-        // If it's unbound then adding won't result into any change
-        if self.is_unbound() {
+    pub fn add(&mut self, range: impl Into<Range<T>>) {
+        let range = range.into();
+        // If it's unbound then adding won't result into any change, inverted ranges hold nothing to add
+        if self.is_unbound() || range.is_inverted() {
             return;
         }
 
@@ -141,146 +410,232 @@ impl<T: Ord + Debug> RangeSet<T> {
             return;
         }
 
-        let mut adder = LinearRangeAdder::new();
-        let iter = self.items.drain(..);
+        let mut builder = RangeSetBuilder::new();
+        let iter = self.items.take();
 
         let mut range = Some(range);
 
         for item in iter {
             let item: Range<T> = item;
 
-            if range.as_ref().map_or(false, |range| range.start_pos() < item.start_pos()) {
+            if range.as_ref().is_some_and(|range| range.start_pos() < item.start_pos()) {
                 if let Some(r) = range.take() {
-                    if adder.add(r) {
+                    if builder.push(r) {
                         break;
                     }
                 }
             }
 
-            if adder.add(item) {
+            if builder.push(item) {
                 break;
             }
         }
 
         if let Some(r) = range {
-            adder.add(r);
+            builder.push(r);
         }
 
-        self.items = adder.finalize().items;
+        self.items = builder.finish().items;
     }
-}
 
-impl<T: Ord + Clone + Debug> RangeSet<T> {
-    /// Create an union of this set and given set
+    /// Add all given ranges to this set at once, sorting them and merging them with the ranges in this set in a
+    /// single pass, instead of rebuilding the set for every range like calling [`add`](RangeSet::add) for each
     ///
     /// # Example
     ///
     /// ```rust
     /// use eater_rangeset::{r, range_set};
     ///
-    /// let left = range_set![r!(4>..)];
-    /// let right = range_set![r!(0..7)];
+    /// let mut set = range_set![r!(0..4), r!(10..20)];
+    /// set.add_all(vec![r!(30..), r!(3..6), r!(8..=8), r!(25..24), r!(6..7)]);
     ///
-    /// assert_eq!(range_set![r!(0..)], left.union(&right));
+    /// assert_eq!(range_set![r!(0..7), r!(8..=8), r!(10..20), r!(30..)], set);
     /// ```
-    pub fn union(&self, other: &Self) -> Self {
-        if other.is_empty() {
-            return self.clone();
+    pub fn add_all(&mut self, ranges: impl IntoIterator<Item=Range<T>>) {
+        let mut ranges: Vec<_> = ranges.into_iter().filter(|range| !range.is_empty()).collect();
+        if ranges.is_empty() || self.is_unbound() {
+            return;
         }
 
-        if self.is_empty() {
-            return other.clone();
+        sort_ranges(&mut ranges);
+        let mut builder = RangeSetBuilder::with_capacity(self.items.len() + ranges.len());
+        for range in merge_sorted(self.items.take(), ranges) {
+            if builder.push(range) {
+                break;
+            }
         }
 
-        if self.is_unbound() || other.is_unbound() {}
+        self.items = builder.finish().items;
+    }
 
-        let mut left_iter = self.items();
-        let mut right_iter = other.items();
+    /// Add given range to this set, handling it according to given policy if it's inverted
+    ///
+    /// [`add`](RangeSet::add) drops inverted ranges, like [`InvalidRangePolicy::Drop`](InvalidRangePolicy::Drop)
+    pub fn add_with_policy(&mut self, range: impl Into<Range<T>>, policy: InvalidRangePolicy) -> Result<(), InvalidRangeError<T>> {
+        if let Some(range) = policy.check(range.into())? {
+            self.add(range);
+        }
 
-        let mut left = left_iter.next();
-        let mut right = right_iter.next();
+        Ok(())
+    }
 
-        let mut adder = LinearRangeAdder::new();
+    /// Add given range to this set after validating it, returns whether the set changed
+    ///
+    /// The range is rejected if it's inverted, a range already covered by this set, even across touching ranges,
+    /// leaves the set unchanged
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, RangeSetError};
+    ///
+    /// let mut set = range_set![r!(0..10)];
+    ///
+    /// assert_eq!(Ok(false), set.try_add(r!(2..4)));
+    /// assert_eq!(Ok(true), set.try_add(r!(8..12)));
+    /// assert!(matches!(set.try_add(r!(20..15)), Err(RangeSetError::InvalidRange(_))));
+    /// assert_eq!(range_set![r!(0..12)], set);
+    /// ```
+    pub fn try_add(&mut self, range: impl Into<Range<T>>) -> Result<bool, RangeSetError<T>> {
+        let range = range.into();
+        if range.is_inverted() {
+            return Err(InvalidRangeError::new(range).into());
+        }
 
-        loop {
-            match (left, right) {
-                (None, None) => break,
-                (Some(l), Some(r)) => {
-                    if r.start_pos() < l.start_pos() {
-                        if adder.add(r.clone()) {
-                            break;
-                        }
+        if range.is_empty() || self.as_ref().covers(range.start(), range.end()) {
+            return Ok(false);
+        }
 
-                        right = right_iter.next();
-                    } else {
-                        if adder.add(l.clone()) {
-                            break;
-                        }
+        self.add(range);
+        Ok(true)
+    }
 
-                        left = left_iter.next();
-                    }
-                }
-                (Some(l), None) => {
-                    if adder.add(l.clone()) {
-                        break;
-                    }
+    /// Create a set from given ranges, handling inverted ranges according to given policy
+    ///
+    /// Converting with [`From`](From) drops inverted ranges, like [`InvalidRangePolicy::Drop`](InvalidRangePolicy::Drop)
+    pub fn from_vec_with_policy(mut ranges: Vec<Range<T>>, policy: InvalidRangePolicy) -> Result<Self, InvalidRangeError<T>> {
+        sort_ranges(&mut ranges);
 
-                    left = left_iter.next();
-                }
+        let mut builder = RangeSetBuilder::with_capacity(ranges.len()).with_policy(policy);
+        for range in ranges {
+            builder.push(range);
+        }
 
-                (None, Some(r)) => {
-                    if adder.add(r.clone()) {
-                        break;
-                    }
+        builder.try_finish()
+    }
 
-                    right = right_iter.next();
-                }
-            }
-        }
+    /// Create a set from given ranges, returning an error if any of the ranges is inverted
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, RangeSet, RangeSetError};
+    ///
+    /// assert_eq!(Ok(range_set![r!(0..6)]), RangeSet::try_from_vec(vec![r!(2..6), r!(0..4)]));
+    /// assert!(matches!(RangeSet::try_from_vec(vec![r!(6..2)]), Err(RangeSetError::InvalidRange(_))));
+    /// ```
+    pub fn try_from_vec(ranges: Vec<Range<T>>) -> Result<Self, RangeSetError<T>> {
+        Ok(RangeSet::from_vec_with_policy(ranges, InvalidRangePolicy::Error)?)
+    }
 
-        adder.finalize()
+    /// Reserve room for at least `additional` more ranges, returning an error instead of aborting when that fails
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), RangeSetError<T>> {
+        self.items.try_reserve(additional).map_err(|()| RangeSetError::CapacityExceeded)
     }
+}
 
-    /// Invert current set, e.g. the result will match nothing this set matches
+impl<T: Ord + Clone + Debug> RangeSet<T> {
+    /// Add all given ranges to this set, returning an error if any of the ranges is inverted
+    ///
+    /// The set is only changed if all ranges are valid
     ///
     /// # Example
     ///
     /// ```rust
     /// use eater_rangeset::{r, range_set};
     ///
-    /// let t = range_set![r!(4>..)];
+    /// let mut set = range_set![r!(0..4)];
+    /// assert!(set.try_extend([r!(4..6), r!(9..8)]).is_err());
+    /// assert_eq!(range_set![r!(0..4)], set);
     ///
-    /// assert_eq!(range_set![r!(..=4)], t.invert());
+    /// assert_eq!(Ok(()), set.try_extend([r!(4..6), r!(8..9)]));
+    /// assert_eq!(range_set![r!(0..6), r!(8..9)], set);
     /// ```
-    pub fn invert(&self) -> RangeSet<T> {
-        if self.is_empty() {
-            return RangeSet::unbound();
+    pub fn try_extend<I: IntoIterator<Item=Range<T>>>(&mut self, ranges: I) -> Result<(), RangeSetError<T>> {
+        let other = RangeSet::try_from_vec(ranges.into_iter().collect())?;
+        if !other.is_empty() {
+            *self = self.union(&other);
         }
 
-        if self.is_unbound() {
-            return RangeSet::empty();
-        }
+        Ok(())
+    }
 
-        let mut items = RangeVec::with_capacity(self.items.len() + 2);
-        let mut current = Range::unbound();
+    /// Split the range holding `at` into two touching ranges, with the second one starting at `at`, the values in
+    /// this set stay the same
+    ///
+    /// This keeps the boundary around until an operation rebuilds the ranges of this set, which merges them again.
+    /// The set still equals the set without the boundary and leaves no gap between the two ranges. Returns `false` if
+    /// no range holds `at` or a range already starts at it
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let mut set = range_set![r!(0..10), r!(20..)];
+    /// assert!(set.split_boundary_at(&4));
+    /// assert!(!set.split_boundary_at(&4));
+    /// assert!(!set.split_boundary_at(&15));
+    ///
+    /// assert_eq!(vec![&r!(0..4), &r!(4..10), &r!(20..)], set.items().collect::<Vec<_>>());
+    /// assert!(set.contains(&3) && set.contains(&4));
+    /// assert_eq!(range_set![r!(0..10), r!(20..)], set);
+    /// ```
+    pub fn split_boundary_at(&mut self, at: &T) -> bool {
+        let index = self.items.partition_point(|range| range.end_pos() < at);
+        if self.items.get(index).is_none_or(|range| range.start_pos() >= at) {
+            return false;
+        }
 
-        for item in self.items() {
-            if item.start == Unbounded {
-                current.start = item.end.clone().invert();
-                continue;
-            }
+        let range = &mut self.items[index];
+        if range.start == Included(at.clone()) {
+            return false;
+        }
 
-            current.end = item.start.clone().invert();
-            let last = mem::replace(&mut current, Range::new(item.end.clone().invert(), Unbounded));
-            items.push(last);
+        let end = mem::replace(&mut range.end, Excluded(at.clone()));
+        self.items.insert(index + 1, Range::new(Included(at.clone()), end));
+        true
+    }
 
-            if item.end == Unbounded {
-                return RangeSet { items };
-            }
-        }
+    /// Create an union of this set and given set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let left = range_set![r!(4>..)];
+    /// let right = range_set![r!(0..7)];
+    ///
+    /// assert_eq!(range_set![r!(0..)], left.union(&right));
+    /// ```
+    pub fn union(&self, other: &impl RangeSetLike<T>) -> Self {
+        other.with_ranges(|other| self.as_ref().union(other))
+    }
 
-        items.push(current);
-        RangeSet { items }
+    /// Invert current set, e.g. the result will match nothing this set matches
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let t = range_set![r!(4>..)];
+    ///
+    /// assert_eq!(range_set![r!(..=4)], t.invert());
+    /// ```
+    pub fn invert(&self) -> RangeSet<T> {
+        self.as_ref().invert()
     }
 
     /// Get the intersection of the 2 sets, or in other words, the places where the sets overlap
@@ -295,12 +650,8 @@ impl<T: Ord + Clone + Debug> RangeSet<T> {
     ///
     /// assert_eq!(range_set![r!(4..5), r!(25..30)], left.intersection(&right));
     /// ```
-    pub fn intersection(&self, rhs: &RangeSet<T>) -> RangeSet<T> {
-        let left = self.invert();
-        let right = rhs.invert();
-        let inter = left.union(&right);
-
-        inter.invert()
+    pub fn intersection(&self, rhs: &impl RangeSetLike<T>) -> RangeSet<T> {
+        rhs.with_ranges(|rhs| self.as_ref().intersection(rhs))
     }
 
     /// Get the difference of this set with given set, alike `lhs - rhs`
@@ -317,107 +668,286 @@ impl<T: Ord + Clone + Debug> RangeSet<T> {
     /// // This method is asymmetric
     /// assert_eq!(range_set![r!(3..15)], right.difference(&left));
     /// ```
-    pub fn difference(&self, rhs: &RangeSet<T>) -> RangeSet<T> {
-        let left = self.invert();
-        let mid = left.union(rhs);
-        let res = mid.invert();
-        res
+    pub fn difference(&self, rhs: &impl RangeSetLike<T>) -> RangeSet<T> {
+        rhs.with_ranges(|rhs| self.as_ref().difference(rhs))
     }
 
     /// Returns `true` if this set does not overlap in anyway with given set
-    pub fn is_disjoint(&self, rhs: &RangeSet<T>) -> bool {
-        if self.is_empty() || rhs.is_empty() {
-            return true;
+    pub fn is_disjoint(&self, rhs: &impl RangeSetLike<T>) -> bool {
+        rhs.with_ranges(|rhs| self.as_ref().is_disjoint(rhs))
+    }
+
+    /// Returns `true` if this set overlaps anywhere with given set
+    pub fn is_overlapping(&self, rhs: &impl RangeSetLike<T>) -> bool {
+        rhs.with_ranges(|rhs| self.as_ref().is_overlapping(rhs))
+    }
+
+    /// Returns an iterator over every pair of overlapping ranges between this set and given set, together with the
+    /// part they overlap in
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let sessions = range_set![r!(0..10), r!(20..30)];
+    /// let outages = range_set![r!(5..25)];
+    /// let affected: Vec<_> = sessions.join(&outages).collect();
+    ///
+    /// assert_eq!(vec![
+    ///     (&r!(0..10), &r!(5..25), r!((&5)..&10)),
+    ///     (&r!(20..30), &r!(5..25), r!((&20)..&25)),
+    /// ], affected);
+    /// ```
+    pub fn join<'a>(&'a self, other: &'a RangeSet<T>) -> impl Iterator<Item=(&'a Range<T>, &'a Range<T>, Range<&'a T>)> + 'a {
+        self.as_ref().join(other.as_ref())
+    }
+
+    /// Split this set into the ranges for which given function returns `true` and the ranges for which it returns
+    /// `false`, in a single pass
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let recently_used = [2, 205];
+    /// let extents = range_set![r!(0..4), r!(10..100), r!(200..210)];
+    /// let (hot, cold) = extents.partition(|range| recently_used.iter().any(|block| range.contains(block)));
+    ///
+    /// assert_eq!(range_set![r!(0..4), r!(200..210)], hot);
+    /// assert_eq!(range_set![r!(10..100)], cold);
+    /// ```
+    pub fn partition(self, mut predicate: impl FnMut(&Range<T>) -> bool) -> (RangeSet<T>, RangeSet<T>) {
+        let (mut matching, mut rest) = (RangeVec::new(), RangeVec::new());
+        for range in self.items {
+            if predicate(&range) {
+                matching.push(range);
+            } else {
+                rest.push(range);
+            }
         }
 
-        if self.is_unbound() || rhs.is_unbound() {
-            return false;
+        // Leaving out ranges of a set can't make the remaining ranges overlap or touch
+        (RangeSet { items: matching.into() }, RangeSet { items: rest.into() })
+    }
+
+    /// Remove given range from this set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let mut set = range_set![r!(0..10), r!(20..30)];
+    /// set.remove(r!(5..25));
+    ///
+    /// assert_eq!(range_set![r!(0..5), r!(25..30)], set);
+    /// ```
+    pub fn remove(&mut self, range: impl Into<Range<T>>) {
+        let range = range.into();
+        let overlapping = self.as_ref().overlapping_slice(range.as_ref());
+        if overlapping.is_empty() {
+            return;
         }
 
-        let mut left_iter = self.items();
-        let mut right_iter = rhs.items();
+        let mut removal = RangeSet::with_capacity(1);
+        removal.items.push(range);
+        *self = self.difference(&removal);
+    }
 
-        let mut left = left_iter.next();
-        let mut right = right_iter.next();
+    /// Add given range only if no part of it is covered by this set yet, otherwise the set is left untouched and
+    /// a [`Conflict`](Conflict) holding the covered parts of the range is returned
+    ///
+    /// Touching ranges don't conflict and are merged like with [`add`](RangeSet::add), empty ranges leave the set
+    /// untouched and inverted ranges are rejected with [`RangeSetError::InvalidRange`](RangeSetError::InvalidRange)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, RangeSetError};
+    ///
+    /// let mut bookings = range_set![r!(9..12)];
+    /// assert_eq!(Ok(()), bookings.try_insert_exclusive(r!(12..13)));
+    ///
+    /// let Err(RangeSetError::Conflict(conflict)) = bookings.try_insert_exclusive(r!(8..10)) else { unreachable!() };
+    /// assert_eq!(&range_set![r!(9..10)], conflict.conflicts());
+    /// assert_eq!(range_set![r!(9..13)], bookings);
+    /// ```
+    pub fn try_insert_exclusive(&mut self, range: impl Into<Range<T>>) -> Result<(), RangeSetError<T>> {
+        let range = range.into();
+        if range.is_inverted() {
+            return Err(InvalidRangeError::new(range).into());
+        }
 
-        loop {
-            match (left, right) {
-                (Some(l), Some(r)) => {
-                    if r.start_pos() == l.start_pos() {
-                        return false;
-                    }
+        if range.is_empty() {
+            return Ok(());
+        }
 
-                    if r.start_pos() < l.start_pos() {
-                        if r.end_pos() >= l.end_pos() {
-                            return false;
-                        } else {
-                            right = right_iter.next();
-                        }
-                    } else {
-                        if r.start_pos() < l.end_pos() {
-                            return false;
-                        } else {
-                            left = left_iter.next();
-                        }
-                    }
-                }
+        let overlapping = self.as_ref().overlapping_slice(range.as_ref());
+        if overlapping.is_empty() {
+            self.add(range);
+            return Ok(());
+        }
 
-                _ => break,
+        let conflicts = overlapping.iter()
+            .map(|item| {
+                let start = if item.start_pos() < range.start_pos() { &range.start } else { &item.start };
+                let end = if item.end_pos() > range.end_pos() { &range.end } else { &item.end };
+                Range::new(start.clone(), end.clone())
+            })
+            .collect();
+
+        Err(Conflict::new(range, RangeSet { items: conflicts }).into())
+    }
+
+    /// Split this set into the part inside of given mask and the part outside of it, which are the same as
+    /// [`intersection`](RangeSet::intersection) and [`difference`](RangeSet::difference) but found together in a
+    /// single pass over both sets
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let work = range_set![r!(0..10), r!(20..30)];
+    /// let cached = range_set![r!(5..25)];
+    /// let (covered, uncovered) = work.split_by(&cached);
+    ///
+    /// assert_eq!(range_set![r!(5..10), r!(20..25)], covered);
+    /// assert_eq!(range_set![r!(0..5), r!(25..30)], uncovered);
+    /// ```
+    pub fn split_by(&self, mask: &RangeSet<T>) -> (RangeSet<T>, RangeSet<T>) {
+        trace_span!("split_by", left = self.items.len(), right = mask.items.len());
+        let (mut inside, mut outside) = (RangeVec::new(), RangeVec::new());
+        let push = |items: &mut RangeVec<Range<T>>, start: Bound<&T>, end: Bound<&T>| {
+            if holds_values(start, end) {
+                items.push(Range::new(start.cloned(), end.cloned()));
             }
-        }
-
-        true
-    }
+        };
 
-    /// Returns `true` if this set overlaps anywhere with given set
-    pub fn is_overlapping(&self, rhs: &RangeSet<T>) -> bool {
-        if self.is_empty() || rhs.is_empty() {
-            return false;
-        }
+        let mut masks = mask.items.iter().peekable();
+        for range in self.items.iter() {
+            // Mask ranges ending before this range can't overlap with any of the ranges after it either
+            while masks.next_if(|item| cmp_bounds(item.end_pos(), range.start_pos()).is_le()).is_some() {}
 
-        if self.is_unbound() || rhs.is_unbound() {
-            return true;
-        }
+            // The start of the part of `range` that hasn't been split yet, `None` once all of it is
+            let mut cursor = Some(range.start());
+            while let Some(start) = cursor {
+                let Some(item) = masks.peek().filter(|item| PositionalBound::cmp_start_end(item.start(), range.end()).is_lt()) else {
+                    break;
+                };
 
-        let mut left_iter = self.items();
-        let mut right_iter = rhs.items();
+                if item.start() != Unbounded {
+                    push(&mut outside, start, item.start().invert());
+                }
 
-        let mut left = left_iter.next();
-        let mut right = right_iter.next();
+                push(&mut inside, max_start(start, item.start()), min_end(range.end(), item.end()));
 
-        loop {
-            match (left, right) {
-                (Some(l), Some(r)) => {
-                    if l.start_pos() >= r.start_pos() {
-                        if l.start_pos() < r.end_pos() {
-                            return true;
-                        } else {
-                            right = right_iter.next();
-                        }
-                    } else {
-                        if l.end_pos() > r.start_pos() {
-                            return true;
-                        } else {
-                            left = left_iter.next();
-                        }
-                    }
+                // The mask range may go on to overlap the next range as well
+                if cmp_bounds(item.end_pos(), range.end_pos()).is_lt() {
+                    cursor = Some(item.end().invert());
+                    masks.next();
+                } else {
+                    cursor = None;
                 }
+            }
 
-                _ => break,
+            if let Some(start) = cursor {
+                push(&mut outside, start, range.end());
             }
         }
 
-        false
+        (RangeSet { items: inside.into() }, RangeSet { items: outside.into() })
+    }
+
+    /// Remove everything given set covers from this set, returns the parts that were actually removed
+    ///
+    /// This is the same as taking the [`intersection`](RangeSet::intersection) before the
+    /// [`difference`](RangeSet::difference), but only goes over both sets once, see [`split_by`](RangeSet::split_by)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let mut cached = range_set![r!(0..10), r!(20..30)];
+    /// let removed = cached.subtract_report(&range_set![r!(5..25), r!(40..50)]);
+    ///
+    /// assert_eq!(range_set![r!(5..10), r!(20..25)], removed);
+    /// assert_eq!(range_set![r!(0..5), r!(25..30)], cached);
+    /// ```
+    pub fn subtract_report(&mut self, other: &RangeSet<T>) -> RangeSet<T> {
+        let (removed, kept) = self.split_by(other);
+        *self = kept;
+        removed
     }
 }
 
 /// A range between point A and B, `start` and `end` are both std [`Bound`](Bound) objects
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct Range<T: Ord> {
+pub struct Range<T> {
     start: Bound<T>,
     end: Bound<T>,
 }
 
+impl<T: Ord> PartialOrd for Range<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> Ord for Range<T> {
+    /// Orders ranges by their start, and ranges starting at the same position by their end
+    fn cmp(&self, other: &Self) -> Ordering {
+        let cmp = |left: &&T, right: &&T| left.cmp(right);
+        self.start_pos().cmp_by(&other.start_pos(), cmp)
+            .then_with(|| self.end_pos().cmp_by(&other.end_pos(), cmp))
+    }
+}
+
+/// Sort given ranges by their start, and ranges starting at the same position by their end, which is the order
+/// [`RangeSetBuilder`](RangeSetBuilder) expects them in
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, sort_ranges, RangeSetBuilder};
+///
+/// let mut ranges = vec![r!(4..8), r!(..2), r!(4..=5), r!(1..3)];
+/// sort_ranges(&mut ranges);
+///
+/// assert_eq!(vec![r!(..2), r!(1..3), r!(4..=5), r!(4..8)], ranges);
+/// ```
+pub fn sort_ranges<T: Ord>(ranges: &mut [Range<T>]) {
+    trace_span!("sort_ranges", ranges = ranges.len());
+    ranges.sort_unstable();
+}
+
+/// Sort given ranges, drop the empty ones and merge the ones overlapping or touching each other, resulting in the
+/// same ranges a [`RangeSet`](RangeSet) built from them would hold
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{normalize, r};
+///
+/// let ranges = normalize(vec![r!(6..9), r!(3..3), r!(0..4), r!(2..6), r!(12..)]);
+///
+/// assert_eq!(&[r!(0..9), r!(12..)], &ranges[..]);
+/// ```
+pub fn normalize<T: Ord + Debug>(mut ranges: Vec<Range<T>>) -> RangeVec<Range<T>> {
+    ranges.retain(|range| !range.is_empty());
+    sort_ranges(&mut ranges);
+
+    let mut builder = RangeSetBuilder::with_capacity(ranges.len());
+    for range in ranges {
+        builder.push(range);
+    }
+
+    builder.finish().items.into_iter().collect()
+}
+
 impl<T: Ord> From<(T, T)> for Range<T> {
     fn from(value: (T, T)) -> Self {
         Range {
@@ -427,7 +957,7 @@ impl<T: Ord> From<(T, T)> for Range<T> {
     }
 }
 
-impl<T: Ord> Range<T> {
+impl<T> Range<T> {
     /// Create a new range from the 2 given bounds
     pub fn new(from: Bound<T>, to: Bound<T>) -> Range<T> {
         Self {
@@ -471,13 +1001,7 @@ impl<T: Ord> Range<T> {
     /// Returns `true` if this boundary is unbounded, or infinite
     #[inline]
     pub fn is_unbound(&self) -> bool {
-        self.start == Unbounded && self.end == Unbounded
-    }
-
-    /// Returns `true` if given item falls within this range
-    #[inline]
-    pub fn contains(&self, item: &T) -> bool {
-        (self.start_pos() < item) && (self.end_pos() > item)
+        matches!((&self.start, &self.end), (Unbounded, Unbounded))
     }
 
     /// Returns the internal `start` and `end` boundaries
@@ -485,6 +1009,83 @@ impl<T: Ord> Range<T> {
     pub fn into_inner(self) -> (Bound<T>, Bound<T>) {
         (self.start, self.end)
     }
+
+    /// Converts `&Range<T>` into `Range<&T>`
+    #[inline]
+    pub fn as_ref(&self) -> Range<&T> {
+        Range::new(self.start(), self.end())
+    }
+
+    /// Converts `&Range<T>` into `Range<&Q>`, for a borrowed form `Q` of `T`
+    #[inline]
+    pub(crate) fn as_borrowed<Q: ?Sized>(&self) -> Range<&Q> where T: Borrow<Q> {
+        Range::new(self.start().map(Borrow::borrow), self.end().map(Borrow::borrow))
+    }
+}
+
+impl<T: Ord> Range<T> {
+    /// Returns `true` if given item falls within this range, any borrowed form of `T` can be given
+    #[inline]
+    pub fn contains<Q: Ord + ?Sized>(&self, item: &Q) -> bool where T: Borrow<Q> {
+        let range = self.as_borrowed::<Q>();
+        (range.start_pos() < &item) && (range.end_pos() > &item)
+    }
+
+    /// Returns `true` if every value in given range also falls within this range, an empty range is contained by
+    /// any range
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// assert!(r!(0..10).contains_range(r!(2..=9)));
+    /// assert!(r!(0..=10).contains_range(r!(2..=10)));
+    /// assert!(!r!(0..10).contains_range(r!(2..=10)));
+    /// assert!(!r!(0>..10).contains_range(r!(0..5)));
+    /// assert!(r!(0..10).contains_range(r!(20..20)));
+    /// ```
+    pub fn contains_range(&self, other: impl RangeBounds<T>) -> bool {
+        let (start, end) = (PositionalBound::Start(other.start_bound()), PositionalBound::End(other.end_bound()));
+        !holds_values(*start, *end) || (cmp_bounds(self.start_pos(), start).is_le() && cmp_bounds(end, self.end_pos()).is_le())
+    }
+
+    /// Returns `true` if this range holds no values, which is when its end doesn't come after its start
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// assert!(r!(4..4).is_empty());
+    /// assert!(r!(4..2).is_empty());
+    /// assert!(r!(4>..=4).is_empty());
+    /// assert!(!r!(4..=4).is_empty());
+    /// assert!(!r!(5>..).is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        PositionalBound::cmp_start_end(self.start(), self.end()).is_ge()
+    }
+
+    /// Returns `true` if the end of this range comes before its start, like `4..2`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// assert!(r!(4..2).is_inverted());
+    /// assert!(!r!(4..4).is_inverted());
+    /// assert!(!r!(4..).is_inverted());
+    /// ```
+    #[inline]
+    pub fn is_inverted(&self) -> bool {
+        match (self.start(), self.end()) {
+            (Included(start) | Excluded(start), Included(end) | Excluded(end)) => end < start,
+            _ => false,
+        }
+    }
 }
 
 impl<T: Ord + Clone> Range<T> {
@@ -495,6 +1096,45 @@ impl<T: Ord + Clone> Range<T> {
             end: value.end_bound().cloned(),
         }
     }
+
+    /// Returns the range strictly between this range and given range, or `None` when they overlap or touch so there
+    /// is nothing between them
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// assert_eq!(Some(r!(4..=10)), r!(0..4).gap_between(r!(10>..20)));
+    /// assert_eq!(Some(r!(4..=10)), r!(10>..20).gap_between(r!(0..4)));
+    /// assert_eq!(None, r!(0..4).gap_between(r!(4..8)));
+    /// assert_eq!(None, r!(0..6).gap_between(r!(4..8)));
+    /// ```
+    pub fn gap_between(&self, other: impl RangeBounds<T>) -> Option<Range<T>> {
+        let (this, other) = (self.as_ref(), Range::new(other.start_bound(), other.end_bound()));
+        let (first, second) = if cmp_bounds(PositionalBound::Start(other.start), PositionalBound::Start(this.start)).is_lt() { (other, this) } else { (this, other) };
+        if PositionalBound::connects(first.end, second.start) {
+            return None;
+        }
+
+        Some(Range::new(first.end.invert().cloned(), second.start.invert().cloned())).filter(|gap| !gap.is_empty())
+    }
+
+    /// Returns the part of this range that falls inside given window, or `None` when it falls entirely outside of it
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// assert_eq!(Some(r!(4000..4096)), r!(4000..5000).clamp_to(0..4096));
+    /// assert_eq!(Some(r!(0..=10)), r!(..=10).clamp_to(0..4096));
+    /// assert_eq!(None, r!(4096..).clamp_to(0..4096));
+    /// ```
+    pub fn clamp_to(&self, window: impl RangeBounds<T>) -> Option<Range<T>> {
+        Some(Range::new(max_start(self.start(), window.start_bound()).cloned(), min_end(self.end(), window.end_bound()).cloned()))
+            .filter(|range| !range.is_empty())
+    }
 }
 
 ///
@@ -502,7 +1142,7 @@ impl<T: Ord + Clone> Range<T> {
 ///
 /// This allows us to implement `Ord`, since `Excluded` and `Included` function differentially, in either start or end position
 ///
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum PositionalBound<T> {
     Start(Bound<T>),
     End(Bound<T>),
@@ -527,55 +1167,174 @@ impl<T: Ord + Debug> PartialOrd for PositionalBound<T> {
 
 impl<T: Ord + Debug> Ord for PositionalBound<T> {
     fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_by(other, T::cmp)
+    }
+}
+
+impl<T> PositionalBound<T> {
+    /// The start boundary of given range
+    #[inline]
+    pub fn start_of(range: &Range<T>) -> PositionalBound<&T> {
+        PositionalBound::Start(range.start())
+    }
+
+    /// The end boundary of given range
+    #[inline]
+    pub fn end_of(range: &Range<T>) -> PositionalBound<&T> {
+        PositionalBound::End(range.end())
+    }
+
+    /// If this is a start boundary
+    #[inline]
+    pub fn is_start(&self) -> bool {
+        matches!(self, PositionalBound::Start(_))
+    }
+
+    /// If this is an end boundary
+    #[inline]
+    pub fn is_end(&self) -> bool {
+        matches!(self, PositionalBound::End(_))
+    }
+
+    /// Converts `&PositionalBound<T>` into `PositionalBound<&T>`
+    #[inline]
+    pub fn as_ref(&self) -> PositionalBound<&T> {
+        match self {
+            PositionalBound::Start(bound) => PositionalBound::Start(bound.as_ref()),
+            PositionalBound::End(bound) => PositionalBound::End(bound.as_ref()),
+        }
+    }
+
+    /// Returns the wrapped boundary, dropping whether it's a start or end boundary
+    #[inline]
+    pub fn into_inner(self) -> Bound<T> {
+        match self {
+            PositionalBound::Start(bound) | PositionalBound::End(bound) => bound,
+        }
+    }
+
+    /// Compare the position of a start boundary with the position of an end boundary
+    ///
+    /// `Less` means a range from `start` to `end` holds values, `Equal` means the end lies directly before the start
+    /// so a range from `start` to `end` is empty but a range ending at `end` touches a range starting at `start`, and
+    /// `Greater` means there is room between them
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::cmp::Ordering;
+    /// use eater_rangeset::PositionalBound;
+    /// use eater_rangeset::Bound::{Excluded, Included, Unbounded};
+    ///
+    /// assert_eq!(Ordering::Less, PositionalBound::cmp_start_end(Included(&1), Included(&1)));
+    /// assert_eq!(Ordering::Equal, PositionalBound::cmp_start_end(Included(&1), Excluded(&1)));
+    /// assert_eq!(Ordering::Greater, PositionalBound::cmp_start_end(Excluded(&1), Excluded(&1)));
+    /// assert_eq!(Ordering::Less, PositionalBound::cmp_start_end(Unbounded, Excluded(&1)));
+    /// ```
+    pub fn cmp_start_end(start: Bound<&T>, end: Bound<&T>) -> Ordering where T: Ord {
+        PositionalBound::Start(start).cmp_by(&PositionalBound::End(end), |left, right| left.cmp(right))
+    }
+
+    /// Returns `true` if a range ending at `end` and a range starting at `start` either overlap or touch, which is
+    /// when they'd be merged into a single range in a set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::PositionalBound;
+    /// use eater_rangeset::Bound::{Excluded, Included};
+    ///
+    /// assert!(PositionalBound::connects(Excluded(&4), Included(&4)));
+    /// assert!(PositionalBound::connects(Included(&4), Excluded(&4)));
+    /// assert!(!PositionalBound::connects(Excluded(&4), Excluded(&4)));
+    /// ```
+    pub fn connects(end: Bound<&T>, start: Bound<&T>) -> bool where T: Ord {
+        PositionalBound::cmp_start_end(start, end).is_le()
+    }
+
+    /// Compare the position of 2 boundaries, using given function to compare the values inside of them
+    ///
+    /// This is what [`Ord`](Ord) is implemented with, but allows for types that aren't `Ord` or that have to be
+    /// ordered differently
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::cmp::Ordering;
+    /// use eater_rangeset::PositionalBound;
+    /// use eater_rangeset::Bound::{Excluded, Included};
+    ///
+    /// let left = PositionalBound::End(Included(1.5));
+    /// let right = PositionalBound::Start(Excluded(2.5));
+    ///
+    /// assert_eq!(Ordering::Less, left.cmp_by(&right, f64::total_cmp));
+    /// ```
+    pub fn cmp_by(&self, other: &Self, mut cmp: impl FnMut(&T, &T) -> Ordering) -> Ordering {
         match (self, other) {
             (PositionalBound::Start(left), PositionalBound::End(right)) => {
                 match (left, right) {
-                    (Included(left), Included(right)) if left <= right => Ordering::Less,
+                    (Included(left), Included(right)) if cmp(left, right).is_le() => Ordering::Less,
                     (Included(_), Included(_)) => Ordering::Greater,
-                    (Included(left), Excluded(right)) => left.cmp(right),
-                    (Excluded(left), Included(right)) if left >= right => Ordering::Greater,
-                    (Excluded(left), Excluded(right)) if left >= right => Ordering::Greater,
+                    (Included(left), Excluded(right)) => cmp(left, right),
+                    (Excluded(left), Included(right)) => cmp(left, right),
+                    (Excluded(left), Excluded(right)) if cmp(left, right).is_ge() => Ordering::Greater,
                     _ => Ordering::Less,
                 }
             }
 
             (PositionalBound::Start(left), PositionalBound::Start(right)) => {
                 match (left, right) {
-                    (left, right) if left == right => Ordering::Equal,
+                    (Unbounded, Unbounded) => Ordering::Equal,
                     (Unbounded, _) => Ordering::Less,
                     (_, Unbounded) => Ordering::Greater,
-                    (Included(left), Included(right)) | (Excluded(left), Excluded(right)) => left.cmp(right),
-                    (Included(left), Excluded(right)) if left > right => Ordering::Greater,
-                    (Excluded(left), Included(right)) if left >= right => Ordering::Greater,
+                    (Included(left), Included(right)) | (Excluded(left), Excluded(right)) => cmp(left, right),
+                    (Included(left), Excluded(right)) if cmp(left, right).is_gt() => Ordering::Greater,
+                    (Excluded(left), Included(right)) if cmp(left, right).is_ge() => Ordering::Greater,
                     _ => Ordering::Less,
                 }
             }
 
             (PositionalBound::End(left), PositionalBound::Start(right)) => {
                 match (left, right) {
-                    (Included(left), Included(right)) if left < right => Ordering::Less,
+                    (Included(left), Included(right)) if cmp(left, right).is_lt() => Ordering::Less,
                     (Included(_), Included(_)) => Ordering::Greater,
-                    (Included(left), Excluded(right)) if left > right => Ordering::Greater,
-                    (Included(_), Excluded(_)) => Ordering::Less,
-                    (Excluded(left), Included(right)) => left.cmp(right),
-                    (Excluded(left), Excluded(right)) if left <= right => Ordering::Less,
+                    (Included(left), Excluded(right)) | (Excluded(left), Included(right)) => cmp(left, right),
+                    (Excluded(left), Excluded(right)) if cmp(left, right).is_le() => Ordering::Less,
                     _ => Ordering::Greater,
                 }
             }
 
             (PositionalBound::End(left), PositionalBound::End(right)) => {
                 match (left, right) {
-                    (left, right) if left == right => Ordering::Equal,
+                    (Unbounded, Unbounded) => Ordering::Equal,
                     (Unbounded, _) => Ordering::Greater,
                     (_, Unbounded) => Ordering::Less,
-                    (Included(left), Included(right)) | (Excluded(left), Excluded(right)) => left.cmp(right),
-                    (Included(left), Excluded(right)) if left >= right => Ordering::Greater,
-                    (Excluded(left), Included(right)) if left > right => Ordering::Greater,
+                    (Included(left), Included(right)) | (Excluded(left), Excluded(right)) => cmp(left, right),
+                    (Included(left), Excluded(right)) if cmp(left, right).is_ge() => Ordering::Greater,
+                    (Excluded(left), Included(right)) if cmp(left, right).is_gt() => Ordering::Greater,
                     _ => Ordering::Less,
                 }
             }
         }
     }
+
+    /// Compare the position of this boundary with given value, using given function to compare the values
+    ///
+    /// A boundary never equals a value, as boundaries always fall between values
+    pub fn cmp_value_by(&self, other: &T, cmp: impl FnOnce(&T, &T) -> Ordering) -> Ordering {
+        match self {
+            PositionalBound::Start(Unbounded) => Ordering::Less,
+            PositionalBound::End(Unbounded) => Ordering::Greater,
+            PositionalBound::Start(Included(left)) | PositionalBound::End(Excluded(left)) => match cmp(left, other) {
+                Ordering::Greater => Ordering::Greater,
+                _ => Ordering::Less,
+            },
+            PositionalBound::End(Included(left)) | PositionalBound::Start(Excluded(left)) => match cmp(left, other) {
+                Ordering::Less => Ordering::Less,
+                _ => Ordering::Greater,
+            },
+        }
+    }
 }
 
 
@@ -588,20 +1347,15 @@ impl<T: Ord> PartialEq<T> for PositionalBound<T> {
 
 impl<T: Ord> PartialOrd<T> for PositionalBound<T> {
     fn partial_cmp(&self, other: &T) -> Option<Ordering> {
-        let cmp = match self {
-            PositionalBound::Start(Unbounded) => Ordering::Less,
-            PositionalBound::End(Unbounded) => Ordering::Greater,
-            PositionalBound::Start(Included(left)) | PositionalBound::End(Excluded(left)) if left <= other => Ordering::Less,
-            PositionalBound::End(Included(left)) | PositionalBound::Start(Excluded(left)) if left < other => Ordering::Less,
-            _ => Ordering::Greater,
-        };
-
-        Some(cmp)
+        Some(self.cmp_value_by(other, T::cmp))
     }
 }
 
 /// An extension trait for [`Bound`](Bound), implements [`as_ref`](BoundExt::as_ref) and [`invert`](BoundExt::invert)
-pub trait BoundExt<T: Ord> {
+///
+/// Mapping a boundary or cloning its value is done with std's own [`Bound::map`](Bound::map) and
+/// [`Bound::cloned`](Bound::cloned)
+pub trait BoundExt<T> {
     /// Invert the position of this boundary
     ///
     /// # Example
@@ -620,7 +1374,7 @@ pub trait BoundExt<T: Ord> {
     fn as_ref(&self) -> Bound<&T>;
 }
 
-impl<T: Ord> BoundExt<T> for Bound<T> {
+impl<T> BoundExt<T> for Bound<T> {
     #[inline]
     fn invert(self) -> Bound<T> {
         match self {
@@ -640,6 +1394,73 @@ impl<T: Ord> BoundExt<T> for Bound<T> {
     }
 }
 
+/// Returns the start boundary that comes first, as used for the start of the union of 2 ranges
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::min_start;
+/// use eater_rangeset::Bound::{Excluded, Included, Unbounded};
+///
+/// assert_eq!(Included(1), min_start(Excluded(1), Included(1)));
+/// assert_eq!(Unbounded, min_start(Included(1), Unbounded));
+/// ```
+pub fn min_start<T: Ord>(left: Bound<T>, right: Bound<T>) -> Bound<T> {
+    if cmp_bounds(PositionalBound::Start(right.as_ref()), PositionalBound::Start(left.as_ref())).is_lt() { right } else { left }
+}
+
+/// Returns the start boundary that comes last, as used for the start of the intersection of 2 ranges
+pub fn max_start<T: Ord>(left: Bound<T>, right: Bound<T>) -> Bound<T> {
+    if cmp_bounds(PositionalBound::Start(right.as_ref()), PositionalBound::Start(left.as_ref())).is_gt() { right } else { left }
+}
+
+/// Returns the end boundary that comes first, as used for the end of the intersection of 2 ranges
+pub fn min_end<T: Ord>(left: Bound<T>, right: Bound<T>) -> Bound<T> {
+    if cmp_bounds(PositionalBound::End(right.as_ref()), PositionalBound::End(left.as_ref())).is_lt() { right } else { left }
+}
+
+/// Returns the end boundary that comes last, as used for the end of the union of 2 ranges
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::max_end;
+/// use eater_rangeset::Bound::{Excluded, Included, Unbounded};
+///
+/// assert_eq!(Included(1), max_end(Excluded(1), Included(1)));
+/// assert_eq!(Excluded(2), max_end(Excluded(2), Included(1)));
+/// assert_eq!(Unbounded, max_end(Included(1), Unbounded));
+/// ```
+pub fn max_end<T: Ord>(left: Bound<T>, right: Bound<T>) -> Bound<T> {
+    if cmp_bounds(PositionalBound::End(right.as_ref()), PositionalBound::End(left.as_ref())).is_gt() { right } else { left }
+}
+
+/// Compares the position of 2 boundaries without requiring `Debug` like [`Ord`](Ord) does
+fn cmp_bounds<T: Ord>(left: PositionalBound<&T>, right: PositionalBound<&T>) -> Ordering {
+    left.cmp_by(&right, |left, right| left.cmp(right))
+}
+
+/// The boundaries of given sorted ranges with the ranges that overlap or touch merged, skipping ranges that hold
+/// no values
+fn coalesced<T: Ord>(items: &[Range<T>]) -> impl Iterator<Item=(Bound<&T>, Bound<&T>)> {
+    let mut items = items.iter().filter(|range| holds_values(range.start(), range.end())).peekable();
+    std::iter::from_fn(move || {
+        let first = items.next()?;
+        let mut end = first.end();
+        while let Some(next) = items.next_if(|next| PositionalBound::connects(end, next.start())) {
+            end = max_end(end, next.end());
+        }
+
+        Some((first.start(), end))
+    })
+}
+
+/// Returns `true` if the range between given boundaries holds anything, the opposite of
+/// [`Range::is_empty`](Range::is_empty) without needing a range
+pub(crate) fn holds_values<T: Ord>(start: Bound<&T>, end: Bound<&T>) -> bool {
+    PositionalBound::cmp_start_end(start, end).is_lt()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::Bound::{Excluded, Included};
@@ -660,11 +1481,13 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn position_bounds() {
         assert_cmp!(PositionalBound::Start(Included(1)), PositionalBound::End(Included(1)), Ordering::Less);
         assert_cmp!(PositionalBound::Start(Included(2)), PositionalBound::End(Included(1)), Ordering::Greater);
         assert_cmp!(PositionalBound::Start(Included(1)), PositionalBound::End(Excluded(1)), Ordering::Equal);
-        assert_cmp!(PositionalBound::Start(Excluded(1)), PositionalBound::End(Included(1)), Ordering::Greater);
+        assert_cmp!(PositionalBound::Start(Excluded(1)), PositionalBound::End(Included(1)), Ordering::Equal);
+        assert_cmp!(PositionalBound::Start(Excluded(1)), PositionalBound::End(Included(0)), Ordering::Greater);
         assert_cmp!(PositionalBound::Start(Excluded(0)), PositionalBound::End(Included(1)), Ordering::Less);
         assert_cmp!(PositionalBound::Start(Excluded(4)), PositionalBound::End(Excluded(1)), Ordering::Greater);
 
@@ -685,6 +1508,153 @@ mod tests {
         assert!(PositionalBound::End(Included(4)) < 5);
     }
 
+    #[test]
+    fn bound_helpers() {
+
+        assert_eq!(Included(1), min_start(Included(1), Excluded(1)));
+        assert_eq!(Excluded(1), max_start(Included(1), Excluded(1)));
+        assert_eq!(Excluded(1), min_end(Included(1), Excluded(1)));
+        assert_eq!(Included(1), max_end(Included(1), Excluded(1)));
+        assert_eq!(Included(3), max_start(Unbounded, Included(3)));
+        assert_eq!(Included(3), min_end(Unbounded, Included(3)));
+        assert_eq!(Unbounded::<u32>, min_start(Unbounded, Included(3)));
+        assert_eq!(Unbounded::<u32>, max_end(Unbounded, Included(3)));
+    }
+
+    #[test]
+    fn positional_bound_helpers() {
+        let range = r!(1..4);
+        let start = PositionalBound::start_of(&range);
+        let copy = start;
+        assert_eq!(start, copy);
+        assert!(start.is_start() && !start.is_end());
+        assert_eq!(Excluded(&4), PositionalBound::end_of(&range).into_inner());
+        assert_eq!(PositionalBound::End(Included(&1)), PositionalBound::End(Included(1)).as_ref());
+
+        let mut seen = std::collections::HashSet::new();
+        assert!(seen.insert(PositionalBound::Start(Included(1))));
+        assert!(seen.insert(PositionalBound::End(Excluded(1))));
+        assert!(!seen.insert(PositionalBound::Start(Included(1))));
+
+        assert!(PositionalBound::connects(Included(&2), Excluded(&1)));
+        assert!(!PositionalBound::connects(Included(&1), Excluded(&2)));
+        assert!(PositionalBound::connects(Included(&1), Excluded(&1)));
+        assert!(PositionalBound::connects(Unbounded, Unbounded::<&u32>));
+    }
+
+    #[test]
+    fn range_ord() {
+        assert!(r!(..1) < r!(0..1));
+        assert!(r!(0..1) < r!(0..=1));
+        assert!(r!(0..=1) < r!(0>..1));
+        assert_eq!(Ordering::Equal, r!(0..1).cmp(&r!(0..1)));
+        assert_eq!(Some(&r!(4..)), [r!(4..), r!(..4), r!(1..=2)].iter().max());
+    }
+
+    #[test]
+    fn contains_range() {
+        assert!(Range::<u8>::unbound().contains_range(r!(..)));
+        assert!(r!(..).contains_range(r!(4>..=8)));
+        assert!(!r!(4..).contains_range(r!(..)));
+        assert!(r!(..=8).contains_range(r!(..8)));
+        assert!(!r!(..8).contains_range(r!(..=8)));
+        assert!(r!(4..).contains_range(r!(4>..)));
+        assert!(!r!(4>..).contains_range(r!(4..)));
+        assert!(r!(4..=8).contains_range(r!(4..=8)));
+        assert!(!r!(4..8).contains_range(r!(2..6)));
+        assert!(!r!(4..8).contains_range(r!(0..2)));
+        assert!(r!(4..8).contains_range(r!(6..2)));
+    }
+
+    #[test]
+    fn gap_between() {
+        assert_eq!(Some(r!(0..10)), r!(..0).gap_between(r!(10..)));
+        assert_eq!(Some(r!(0..10)), r!(10..).gap_between(r!(..0)));
+        assert_eq!(Some(r!(0>..10)), r!(..=0).gap_between(r!(10..=12)));
+        assert_eq!(Some(r!(4..=4)), r!(0..4).gap_between(r!(4>..8)));
+        assert_eq!(None, r!(..=4).gap_between(r!(4>..)));
+        assert_eq!(None, r!(..=4).gap_between(r!(4..)));
+        assert_eq!(None, r!(0..).gap_between(r!(10..20)));
+        assert_eq!(None, r!(0..20).gap_between(r!(5..10)));
+        assert_eq!(None, Range::<u8>::unbound().gap_between(r!(5..10)));
+    }
+
+    #[test]
+    fn clamp_to() {
+        assert_eq!(Some(r!(0..10)), Range::unbound().clamp_to(r!(0..10)));
+        assert_eq!(Some(r!(0>..10)), r!(0>..).clamp_to(r!(0..10)));
+        assert_eq!(Some(r!(2..=4)), r!(2..=4).clamp_to(r!(..)));
+        assert_eq!(Some(r!(4..=4)), r!(..=4).clamp_to(r!(4..8)));
+        assert_eq!(None, r!(..4).clamp_to(r!(4..8)));
+        assert_eq!(None, r!(8..).clamp_to(r!(4..8)));
+        assert_eq!(None, r!(6..5).clamp_to(r!(4..8)));
+    }
+
+    #[test]
+    fn normalize_ranges() {
+        assert!(normalize::<u32>(vec![]).is_empty());
+        assert!(normalize(vec![r!(1..1), r!(3..2)]).is_empty());
+        assert_eq!(&[r!(..=2), r!(3..)], &normalize(vec![r!(3..), r!(..2), r!(1..=2), r!(5..5)])[..]);
+        assert!(r!(1>..1).is_empty());
+        assert!(!r!(1>..).is_empty());
+    }
+
+    #[test]
+    fn try_add() {
+        let mut set = RangeSet::empty();
+        assert_eq!(Ok(true), set.try_add(r!(4..=6)));
+        assert_eq!(Ok(false), set.try_add(r!(4..=6)));
+        assert_eq!(Ok(false), set.try_add(r!(5..5)));
+        assert_eq!(Ok(true), set.try_add(r!(6..7)));
+        assert_eq!(Ok(false), set.try_add(r!(4..7)));
+        assert_eq!(Ok(true), set.try_add(r!(..)));
+        assert_eq!(Ok(false), set.try_add(r!(..)));
+        assert_eq!(Err(RangeSetError::InvalidRange(InvalidRangeError::new(r!(3..=2)))), set.try_add(r!(3..=2)));
+
+        let mut split = range_set![r!(0..10)];
+        split.split_boundary_at(&5);
+        assert_eq!(Ok(false), split.try_add(r!(2..8)));
+        assert_eq!(2, split.items().count());
+        assert_eq!(Ok(true), split.try_add(r!(2..=10)));
+    }
+
+    #[test]
+    fn add_all() {
+        let batch = vec![r!(40..45), r!(..-5), r!(12..=20), r!(0..2), r!(9>..12), r!(44..50), r!(7..6)];
+        let mut set = range_set![r!(-10..-3), r!(5..10), r!(60..)];
+        let mut expected = set.clone();
+        for range in batch.clone() {
+            expected.add(range);
+        }
+
+        set.add_all(batch);
+        assert_eq!(expected, set);
+        assert_eq!(range_set![r!(..-3), r!(0..2), r!(5..=20), r!(40..50), r!(60..)], set);
+
+        set.add_all(vec![r!(3..3), r!(30>..30)]);
+        assert_eq!(expected, set);
+
+        let mut set = RangeSet::empty();
+        set.add_all(vec![r!(3..4), r!(1..2), r!(2..3)]);
+        assert_eq!(range_set![r!(1..4)], set);
+        set.add_all(vec![r!(..), r!(5..6)]);
+        assert_eq!(range_set![r!(..)], set);
+    }
+
+    #[test]
+    fn single_and_point() {
+        assert_eq!(range_set![r!(..)], RangeSet::single(Range::<u8>::unbound()));
+        assert_eq!(range_set![r!(4>..=8)], RangeSet::single(r!(4>..=8)));
+        assert_eq!(range_set![r!(4>..=8)], RangeSet::from(r!(4>..=8)));
+        assert_eq!(RangeSet::empty(), RangeSet::single(r!(4>..4)));
+        assert_eq!(RangeSet::empty(), RangeSet::from(r!(4..=3)));
+
+        let point = RangeSet::point(10);
+        assert!(point.contains(&10));
+        assert!(!point.contains(&9) && !point.contains(&11));
+        assert_eq!(range_set![r!(10..=10)], point);
+    }
+
     #[test]
     fn contains() {
         let r = range_set!(r!(4..));
@@ -700,6 +1670,19 @@ mod tests {
         assert!(r!(0..3).contains(&0));
     }
 
+    #[test]
+    fn borrowed_lookups() {
+        let keys: RangeSet<Vec<u8>> = range_set![r!((b"abc".to_vec())..=b"abd".to_vec()), r!((b"x".to_vec())..)];
+
+        assert!(keys.contains(&b"abc"[..]));
+        assert!(keys.contains(&b"abcz"[..]));
+        assert!(!keys.contains(&b"abda"[..]));
+        assert!(keys.as_ref().contains(&b"zzz"[..]));
+
+        assert_eq!(Some(&r!((b"x".to_vec())..)), keys.find(&b"xy"[..]));
+        assert_eq!(None, keys.find(&b"w"[..]));
+    }
+
     #[test]
     fn add() {
         let mut range = range_set![r!(4..8)];
@@ -724,6 +1707,15 @@ mod tests {
         let mut range = range_set![r!(5..)];
         range.add(r!(1..));
         assert_eq!(range_set![r!(1..)], range);
+
+        let mut range = RangeSet::new();
+        range.add(0..2);
+        range.add(4..=6);
+        range.add(..=-5);
+        range.add(10..);
+        assert_eq!(range_set![r!(..=-5), r!(0..2), r!(4..=6), r!(10..)], range);
+        range.remove(..);
+        assert_eq!(RangeSet::empty(), range);
     }
 
     #[test]
@@ -849,6 +1841,41 @@ mod tests {
         assert!(right.is_disjoint(&left));
     }
 
+    #[test]
+    fn partition() {
+        let set = range_set![r!(..0), r!(4..8), r!(10>..=20), r!(30..)];
+        let (bounded, unbounded) = set.clone().partition(|range| range.start != Unbounded && range.end != Unbounded);
+        assert_eq!(range_set![r!(4..8), r!(10>..=20)], bounded);
+        assert_eq!(range_set![r!(..0), r!(30..)], unbounded);
+        assert_eq!(set, bounded.union(&unbounded));
+
+        assert_eq!((set.clone(), RangeSet::empty()), set.clone().partition(|_| true));
+        assert_eq!((RangeSet::empty(), RangeSet::<u8>::empty()), RangeSet::empty().partition(|_| true));
+    }
+
+    #[test]
+    fn split_by() {
+        let set = range_set![r!(..0), r!(4..8), r!(10>..=20), r!(30..)];
+        for mask in [
+            range_set![],
+            range_set![r!(..)],
+            range_set![r!(-5..=5)],
+            range_set![r!(..=4), r!(8..)],
+            range_set![r!(6..10), r!(15..16), r!(18>..35)],
+            range_set![r!(-2..-1), r!(5..=6), r!(7..11), r!(12..13), r!(40..)],
+        ] {
+            assert_eq!((set.intersection(&mask), set.difference(&mask)), set.split_by(&mask), "{}", mask);
+            assert_eq!((mask.intersection(&set), mask.difference(&set)), mask.split_by(&set), "{}", mask);
+
+            let mut subtracted = set.clone();
+            assert_eq!(set.split_by(&mask).0, subtracted.subtract_report(&mask), "{}", mask);
+            assert_eq!(set.split_by(&mask).1, subtracted, "{}", mask);
+        }
+
+        // Unlike `intersection` no `10>..=10` is left behind
+        assert_eq!((range_set![r!(6..8)], range_set![r!(..0), r!(4..6), r!(10>..=20), r!(30..)]), set.split_by(&range_set![r!(6..=10)]));
+    }
+
     #[test]
     fn is_overlapping() {
         let left: RangeSet<usize> = range_set!();
@@ -880,4 +1907,61 @@ mod tests {
         assert!(!left.is_overlapping(&right));
         assert!(!right.is_overlapping(&left));
     }
+
+    #[test]
+    fn try_insert_exclusive() {
+        let mut set = range_set![r!(0..10), r!(20..30)];
+        assert_eq!(Ok(()), set.try_insert_exclusive(r!(12..15)));
+        assert_eq!(Ok(()), set.try_insert_exclusive(r!(10..12)));
+        assert_eq!(range_set![r!(0..15), r!(20..30)], set);
+
+        let expect_conflict = |result: Result<(), RangeSetError<i32>>| match result {
+            Err(RangeSetError::Conflict(conflict)) => conflict,
+            result => panic!("expected a conflict, got {:?}", result),
+        };
+
+        let conflict = expect_conflict(set.try_insert_exclusive(r!(14>..=25)));
+        assert_eq!(&r!(14>..=25), conflict.range());
+        assert_eq!(&range_set![r!(14>..15), r!(20..=25)], conflict.conflicts());
+
+        let (_, conflicts) = expect_conflict(set.try_insert_exclusive(r!(..))).into_inner();
+        assert_eq!(set, conflicts);
+        assert_eq!(range_set![r!(0..15), r!(20..30)], set);
+
+        assert_eq!(Ok(()), set.try_insert_exclusive(r!(5..5)));
+        assert_eq!(Err(RangeSetError::InvalidRange(InvalidRangeError::new(r!(8..2)))), set.try_insert_exclusive(r!(8..2)));
+        assert_eq!(range_set![r!(0..15), r!(20..30)], set);
+        assert_eq!(2, set.items().count());
+    }
+
+    #[test]
+    fn split_boundary_at() {
+        let mut set = range_set![r!(..=10), r!(20>..30)];
+        assert!(set.split_boundary_at(&10));
+        assert!(set.split_boundary_at(&21));
+        assert!(!set.split_boundary_at(&20));
+        assert!(!set.split_boundary_at(&30));
+        assert_eq!(vec![&r!(..10), &r!(10..=10), &r!(20>..21), &r!(21..30)], set.items().collect::<Vec<_>>());
+        assert!(set.contains(&10) && set.contains(&21) && !set.contains(&20));
+        assert_eq!(range_set![r!(..=10), r!(20>..30)], set);
+        assert_eq!(vec![&r!(10>..=20), &r!(30..)], set.invert().items().collect::<Vec<_>>());
+        assert_eq!(vec![r!(10>..=20)], set.gaps().collect::<Vec<_>>());
+        assert_eq!(Some((1, r!(10>..=20))), set.largest_gap());
+
+        set.add(r!(40..50));
+        assert_eq!(range_set![r!(..=10), r!(20>..30), r!(40..50)], set);
+    }
+
+    #[test]
+    fn heap_bytes() {
+        let range_size = std::mem::size_of::<Range<u64>>();
+        assert_eq!(0, RangeSet::<u64>::empty().heap_bytes());
+        assert_eq!(0, RangeSet::<u64>::unbound().heap_bytes());
+
+        let set: RangeSet<u64> = (0..40).map(|value| Range::from(value * 2..value * 2 + 1)).collect::<Vec<_>>().into();
+        assert!(set.heap_bytes() >= 40 * range_size);
+        assert_eq!(set.heap_bytes() + 80 * 8, set.heap_bytes_with(|_| 8));
+        assert_eq!(0, set.union(&RangeSet::unbound()).heap_bytes_with(|_| 8));
+        assert_eq!(8, range_set![r!(1..)].heap_bytes_with(|_| 8));
+    }
 }
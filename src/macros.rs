@@ -1,3 +1,6 @@
+use std::fmt::{self, Debug};
+use crate::RangeSet;
+
 /// Create a new range set based on given ranges
 ///
 /// `<type>:` can be used to set the type when it can't resolved from context
@@ -141,4 +144,100 @@ macro_rules! r {
     (($l:expr) ..= $r:expr) => {
         $crate::Range::new($crate::Bound::Included($l), $crate::Bound::Included($r))
     };
-}
\ No newline at end of file
+}
+
+/// Enter a `debug` span with given fields for the rest of the scope, the fields are only evaluated when the `tracing`
+/// feature is enabled and the macro expands to nothing otherwise
+macro_rules! trace_span {
+    ($name:literal $(, $field:ident = $value:expr)* $(,)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($name $(, $field = $value)*).entered();
+    };
+}
+
+pub(crate) use trace_span;
+
+/// Assert that two range sets are equal, like [`assert_eq!`](assert_eq), but on failure prints the parts only in
+/// either set and the ranges that differ between them instead of both sets in full
+///
+/// A custom message can be given after the sets, like with [`assert_eq!`](assert_eq)
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{assert_range_set_eq, r, range_set};
+///
+/// let coverage = range_set![r!(0..10)].union(&range_set![r!(10..20)]);
+///
+/// assert_range_set_eq!(range_set![r!(0..20)], coverage);
+/// assert_range_set_eq!(range_set![r!(0..20)], coverage, "coverage of {} blocks", 2);
+/// ```
+#[macro_export]
+macro_rules! assert_range_set_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => if left != right {
+                $crate::range_set_eq_failed(left, right, None)
+            }
+        }
+    };
+
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => if left != right {
+                $crate::range_set_eq_failed(left, right, Some(format_args!($($arg)+)))
+            }
+        }
+    };
+}
+
+/// Panics with the differences between two sets, called by [`assert_range_set_eq!`](crate::assert_range_set_eq)
+#[doc(hidden)]
+#[track_caller]
+pub fn range_set_eq_failed<T: Ord + Clone + Debug>(left: &RangeSet<T>, right: &RangeSet<T>, message: Option<fmt::Arguments<'_>>) -> ! {
+    let debug = |value: &T, f: &mut fmt::Formatter<'_>| write!(f, "{:?}", value);
+
+    // Sweep over both sets at once, skipping the ranges they have in common
+    let (mut left_ranges, mut right_ranges) = (vec![], vec![]);
+    let (mut lefts, mut rights) = (left.items().peekable(), right.items().peekable());
+    loop {
+        match (lefts.peek(), rights.peek()) {
+            (Some(left), Some(right)) if left == right => {
+                lefts.next();
+                rights.next();
+            }
+            (Some(left), Some(right)) if left.start_pos() <= right.start_pos() => left_ranges.push(lefts.next().unwrap().clone()),
+            (Some(_), Some(_)) | (None, Some(_)) => right_ranges.push(rights.next().unwrap().clone()),
+            (Some(_), None) => left_ranges.push(lefts.next().unwrap().clone()),
+            (None, None) => break,
+        }
+    }
+
+    let (left_ranges, right_ranges) = (RangeSet { items: left_ranges.into_iter().collect() }, RangeSet { items: right_ranges.into_iter().collect() });
+    let message = message.map(|message| format!(": {}", message)).unwrap_or_default();
+    panic!(
+        "assertion `left == right` failed{}\n  only in left: {}\n only in right: {}\n   left ranges: {}\n  right ranges: {}",
+        message,
+        left.difference(right).display_with(debug),
+        right.difference(left).display_with(debug),
+        left_ranges.display_with(debug),
+        right_ranges.display_with(debug),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RangeSet;
+
+    #[test]
+    fn assert_range_set_eq() {
+        assert_range_set_eq!(range_set![r!(0..4)], range_set![r!(0..2), r!(2..4)]);
+        assert_range_set_eq!(RangeSet::<u8>::empty(), RangeSet::empty(), "never {}", "fails");
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `left == right` failed: coverage\n  only in left: {4..6}\n only in right: {10..12}\n   left ranges: {0..6}\n  right ranges: {0..4, 10..12}")]
+    fn assert_range_set_eq_failed() {
+        assert_range_set_eq!(range_set![r!(0..6), r!(20..30)], range_set![r!(0..4), r!(10..12), r!(20..30)], "coverage");
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt::Debug;
+use crate::{Bound, MapError, PositionalBound, Range, RangeSet};
+
+impl<T: Ord + Debug> RangeSet<T> {
+    /// Transform every boundary in this set with given function, the ranges are sorted and merged again afterwards
+    ///
+    /// `f` should be monotonic, e.g. `a <= b` should imply `f(a) <= f(b)`, ranges that end up with their end
+    /// before their start are checked for in debug builds only
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let lines = range_set![r!(1..3), r!(5..=6)];
+    /// let offsets = lines.map(|line| line * 80);
+    ///
+    /// assert_eq!(range_set![r!(80..240), r!(400..=480)], offsets);
+    /// ```
+    pub fn map<U: Ord + Debug>(self, mut f: impl FnMut(T) -> U) -> RangeSet<U> {
+        let items: Vec<Range<U>> = self.items.into_iter()
+            .map(|range| {
+                let range = Range::new(range.start.map(&mut f), range.end.map(&mut f));
+                debug_assert!(range.start_pos() <= range.end_pos(), "range ({:?}) ends before it starts after mapping", range);
+                range
+            })
+            .collect();
+
+        RangeSet::from(items)
+    }
+
+    /// Transform every boundary in this set with given fallible function, like [`map`](RangeSet::map)
+    ///
+    /// Stops at the first error, which is returned together with the range that failed to map
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let small = range_set![r!(1u64..3), r!(5..=6)];
+    /// assert_eq!(Ok(range_set![r!(1u32..3), r!(5..=6)]), small.try_map(|x| u32::try_from(*x)));
+    ///
+    /// let large = range_set![r!(1u64..3), r!(5..=u64::MAX)];
+    /// let err = large.try_map(|x| u32::try_from(*x)).unwrap_err();
+    /// assert_eq!(&r!(5..=u64::MAX), err.range());
+    /// ```
+    pub fn try_map<U: Ord + Debug, E>(self, mut f: impl FnMut(&T) -> Result<U, E>) -> Result<RangeSet<U>, MapError<T, E>> {
+        let mut items: Vec<Range<U>> = Vec::with_capacity(self.items.len());
+
+        for range in self.items {
+            let mapped = try_map_bound(&range.start, &mut f)
+                .and_then(|start| Ok(Range::new(start, try_map_bound(&range.end, &mut f)?)));
+
+            match mapped {
+                Ok(mapped) => {
+                    debug_assert!(mapped.start_pos() <= mapped.end_pos(), "range ({:?}) ends before it starts after mapping", mapped);
+                    items.push(mapped);
+                }
+                Err(error) => return Err(MapError::new(range, error)),
+            }
+        }
+
+        Ok(RangeSet::from(items))
+    }
+}
+
+impl<T: Ord + Debug> RangeSet<T> {
+    /// Transform every boundary in this set with given function, which receives the boundary as
+    /// [`PositionalBound`](PositionalBound) so it can tell start and end apart
+    ///
+    /// Like with [`map`](RangeSet::map) the ranges are sorted and merged again afterwards
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, PositionalBound};
+    /// use eater_rangeset::Bound::{Excluded, Included};
+    ///
+    /// let set = range_set![r!(1..4), r!(6..=8)];
+    ///
+    /// // Make all exclusive ends inclusive
+    /// let inclusive = set.map_bounds(|bound| match bound {
+    ///     PositionalBound::End(Excluded(x)) => Included(x - 1),
+    ///     PositionalBound::Start(bound) | PositionalBound::End(bound) => bound,
+    /// });
+    ///
+    /// assert_eq!(range_set![r!(1..=3), r!(6..=8)], inclusive);
+    /// ```
+    pub fn map_bounds<U: Ord + Debug>(self, mut f: impl FnMut(PositionalBound<T>) -> Bound<U>) -> RangeSet<U> {
+        let items: Vec<Range<U>> = self.items.into_iter()
+            .map(|range| {
+                let range = Range::new(f(PositionalBound::Start(range.start)), f(PositionalBound::End(range.end)));
+                debug_assert!(range.start_pos() <= range.end_pos(), "range ({:?}) ends before it starts after mapping", range);
+                range
+            })
+            .collect();
+
+        RangeSet::from(items)
+    }
+}
+
+fn try_map_bound<T, U, E>(bound: &Bound<T>, f: impl FnOnce(&T) -> Result<U, E>) -> Result<Bound<U>, E> {
+    Ok(match bound {
+        Bound::Included(value) => Bound::Included(f(value)?),
+        Bound::Excluded(value) => Bound::Excluded(f(value)?),
+        Bound::Unbounded => Bound::Unbounded,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, PositionalBound, RangeSet};
+    use crate::Bound::{Excluded, Included};
+
+    #[test]
+    fn map() {
+        let empty: RangeSet<u32> = range_set![];
+        assert_eq!(RangeSet::<u64>::empty(), empty.map(u64::from));
+
+        let set = range_set![r!(..2u32), r!(4..6), r!(8>..)];
+        assert_eq!(range_set![r!(..2u64), r!(4..6), r!(8>..)], set.clone().map(u64::from));
+        assert_eq!(range_set![r!(..1), r!(2..3), r!(4>..)], set.map(|x| x / 2));
+
+        // Ranges that end up touching are merged
+        assert_eq!(range_set![r!(0..4)], range_set![r!(0..4), r!(5..8)].map(|x| x / 2));
+    }
+
+    #[test]
+    fn map_bounds() {
+        let set = range_set![r!(..2), r!(4..=6), r!(8>..10)];
+        let half_open = set.map_bounds(|bound| match bound {
+            PositionalBound::Start(Excluded(x)) => Included(x + 1),
+            PositionalBound::End(Included(x)) => Excluded(x + 1),
+            PositionalBound::Start(bound) | PositionalBound::End(bound) => bound,
+        });
+
+        assert_eq!(range_set![r!(..2), r!(4..7), r!(9..10)], half_open);
+
+        let unbound: RangeSet<u32> = RangeSet::unbound();
+        let bounded = unbound.map_bounds(|bound| match bound {
+            PositionalBound::Start(_) => Included(0),
+            PositionalBound::End(_) => Included(u32::MAX),
+        });
+
+        assert_eq!(range_set![r!(0..=u32::MAX)], bounded);
+    }
+
+    #[test]
+    fn try_map() {
+        let set = range_set![r!(..2), r!(4..6), r!(8>..)];
+        assert_eq!(Ok(range_set![r!(..2u8), r!(4..6), r!(8>..)]), set.clone().try_map(|x| u8::try_from(*x)));
+
+        let set = range_set![r!(4..6), r!(8..=300)];
+        let (range, _) = set.try_map(|x| u8::try_from(*x)).unwrap_err().into_inner();
+        assert_eq!(r!(8..=300), range);
+
+        let keys: RangeSet<String> = range_set![r!(("10".to_string()).."20".to_string())];
+        assert_eq!(Ok(range_set![r!(10..20)]), keys.try_map(|x| x.parse::<u32>()));
+    }
+}
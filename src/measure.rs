@@ -0,0 +1,980 @@
+use std::fmt::Debug;
+use std::ops::{Add, RangeBounds, Sub};
+use std::time::{Duration, Instant};
+use crate::{cmp_bounds, holds_values, Bound, BoundExt, Discrete, Range, RangeSet, RangeSetRef, RangeVec};
+use crate::Bound::{Excluded, Included};
+use crate::sweep::Sweep;
+
+/// A type of which the distance between 2 values can be measured, which is what ranges and sets are measured with
+///
+/// Implemented for all integer and float types, [`Duration`](Duration) and [`Instant`](Instant), the length of signed
+/// integers is their unsigned counterpart so every range can be measured
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, Measure};
+///
+/// /// A timestamp in thousandths of a second
+/// #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+/// struct Millis(u64);
+///
+/// impl Measure for Millis {
+///     type Length = f64;
+///
+///     fn distance(start: &Self, end: &Self) -> f64 {
+///         (end.0 - start.0) as f64 / 1000.0
+///     }
+/// }
+///
+/// let busy = range_set![r!((Millis(0))..Millis(1500)), r!((Millis(2000))..Millis(2250))];
+/// assert_eq!(Some(1.75), busy.measure());
+/// ```
+pub trait Measure {
+    /// The type of the distance between 2 values
+    type Length: Add<Output=Self::Length>;
+
+    /// The distance from `start` to `end`
+    fn distance(start: &Self, end: &Self) -> Self::Length;
+
+    /// The distance from `start` to `end`, or `None` if it doesn't fit in a length
+    ///
+    /// Defaults to [`distance`](Measure::distance), types of which the distance may overflow should override this
+    #[inline]
+    fn checked_distance(start: &Self, end: &Self) -> Option<Self::Length> {
+        Some(Self::distance(start, end))
+    }
+
+    /// The sum of 2 lengths, or `None` if it doesn't fit in a length
+    ///
+    /// Defaults to adding the lengths, types of which the lengths may overflow should override this
+    #[inline]
+    fn checked_add(left: Self::Length, right: Self::Length) -> Option<Self::Length> {
+        Some(left + right)
+    }
+}
+
+macro_rules! impl_measure {
+    (checked: $($ty:ty),*) => {
+        $(
+            impl Measure for $ty {
+                type Length = $ty;
+
+                #[inline]
+                fn distance(start: &Self, end: &Self) -> Self::Length {
+                    *end - *start
+                }
+
+                #[inline]
+                fn checked_distance(start: &Self, end: &Self) -> Option<Self::Length> {
+                    end.checked_sub(*start)
+                }
+
+                #[inline]
+                fn checked_add(left: Self::Length, right: Self::Length) -> Option<Self::Length> {
+                    left.checked_add(right)
+                }
+            }
+        )*
+    };
+    (signed: $($ty:ty => $length:ty),*) => {
+        $(
+            impl Measure for $ty {
+                type Length = $length;
+
+                #[inline]
+                fn distance(start: &Self, end: &Self) -> Self::Length {
+                    end.abs_diff(*start)
+                }
+
+                #[inline]
+                fn checked_distance(start: &Self, end: &Self) -> Option<Self::Length> {
+                    (start <= end).then(|| end.abs_diff(*start))
+                }
+
+                #[inline]
+                fn checked_add(left: Self::Length, right: Self::Length) -> Option<Self::Length> {
+                    left.checked_add(right)
+                }
+            }
+        )*
+    };
+    ($($ty:ty),*) => {
+        $(
+            impl Measure for $ty {
+                type Length = $ty;
+
+                #[inline]
+                fn distance(start: &Self, end: &Self) -> Self::Length {
+                    *end - *start
+                }
+            }
+        )*
+    };
+}
+
+impl_measure!(checked: u8, u16, u32, u64, u128, usize, Duration);
+impl_measure!(signed: i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+impl_measure!(f32, f64);
+
+impl Measure for Instant {
+    type Length = Duration;
+
+    /// The time elapsed from `start` to `end`, which is zero if `end` comes first
+    #[inline]
+    fn distance(start: &Self, end: &Self) -> Duration {
+        end.saturating_duration_since(*start)
+    }
+
+    #[inline]
+    fn checked_add(left: Duration, right: Duration) -> Option<Duration> {
+        left.checked_add(right)
+    }
+}
+
+/// A type that can be moved forward by a length, the reverse of [`distance`](Measure::distance), which is used to
+/// cut ranges to a length
+///
+/// Implemented for all integer and float types, [`Duration`](Duration) and [`Instant`](Instant)
+pub trait Advance: Measure {
+    /// The value `length` after `start`
+    fn advance(start: &Self, length: Self::Length) -> Self;
+}
+
+macro_rules! impl_advance {
+    (signed: $($ty:ty),*) => {
+        $(
+            impl Advance for $ty {
+                #[inline]
+                fn advance(start: &Self, length: Self::Length) -> Self {
+                    start.checked_add_unsigned(length).expect("attempt to add with overflow")
+                }
+            }
+        )*
+    };
+    ($($ty:ty),*) => {
+        $(
+            impl Advance for $ty {
+                #[inline]
+                fn advance(start: &Self, length: Self::Length) -> Self {
+                    *start + length
+                }
+            }
+        )*
+    };
+}
+
+impl_advance!(u8, u16, u32, u64, u128, usize, f32, f64, Duration, Instant);
+impl_advance!(signed: i8, i16, i32, i64, i128, isize);
+
+/// A length that can be converted to an `f64` to compute ratios with, possibly losing precision
+///
+/// Implemented for all integer and float types and [`Duration`](Duration), which is converted to seconds
+pub trait ToF64 {
+    /// This length as an `f64`
+    fn to_f64(&self) -> f64;
+}
+
+macro_rules! impl_to_f64 {
+    ($($ty:ty),*) => {
+        $(
+            impl ToF64 for $ty {
+                #[inline]
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_to_f64!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl ToF64 for Duration {
+    #[inline]
+    fn to_f64(&self) -> f64 {
+        self.as_secs_f64()
+    }
+}
+
+impl<T: Ord + Clone> Range<T> {
+    /// Returns the distance between the start and the end of this range, or `None` when either side is unbounded
+    ///
+    /// Whether the boundaries are inclusive or exclusive is not taken into account
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// assert_eq!(Some(6), r!(4u32..10).measure());
+    /// assert_eq!(None, r!(4u32..).measure());
+    /// ```
+    pub fn measure<L>(&self) -> Option<L>
+        where T: Measure<Length=L> {
+        measure_between(self.start(), self.end())
+    }
+
+    /// Returns the distance between the start and the end of this range like [`measure`](Range::measure), but
+    /// returns `None` instead of overflowing
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// assert_eq!(Some(6), r!(4i8..10).checked_measure());
+    /// assert_eq!(Some(200), r!(-100i8..100).checked_measure());
+    /// assert_eq!(None, r!(4i8..).checked_measure());
+    /// ```
+    pub fn checked_measure<L>(&self) -> Option<L>
+        where T: Measure<Length=L> {
+        match (self.start(), self.end()) {
+            (Included(start) | Excluded(start), Included(end) | Excluded(end)) => T::checked_distance(start, end),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Discrete + Clone> Range<T> {
+    /// Returns the distance between the start and the end of this range as a `u128`, which holds the distance
+    /// between any 2 integers of up to 128 bits, or `None` when either side is unbounded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// assert_eq!(Some(255), r!(-128i8..127).measure_u128());
+    /// assert_eq!(None, r!(4u8..).measure_u128());
+    /// ```
+    pub fn measure_u128(&self) -> Option<u128> {
+        match (self.start(), self.end()) {
+            (Included(start) | Excluded(start), Included(end) | Excluded(end)) => T::steps_between(start, end),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Ord + Clone + Debug> RangeSet<T> {
+    /// Returns an iterator over the gaps between the ranges in this set, the parts before the first and
+    /// after the last range are not included
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(..4), r!(6..10), r!(12..)];
+    ///
+    /// assert_eq!(vec![r!(4..6), r!(10..12)], set.gaps().collect::<Vec<_>>());
+    /// ```
+    pub fn gaps(&self) -> impl Iterator<Item=Range<T>> + '_ {
+        self.as_ref().gaps()
+    }
+
+    /// Returns an iterator over the gaps between the ranges in this set that are at least `length` wide
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(..4u32), r!(6..10), r!(13..)];
+    ///
+    /// assert_eq!(vec![r!(10..13)], set.gaps_of_at_least(3).collect::<Vec<_>>());
+    /// ```
+    pub fn gaps_of_at_least<'a, L: PartialOrd + 'a>(&'a self, length: L) -> impl Iterator<Item=Range<T>> + 'a
+        where T: Measure<Length=L> {
+        self.as_ref().gaps_of_at_least(length)
+    }
+
+    /// Returns the widest range in this set, unbounded ranges are considered the widest
+    ///
+    /// When multiple ranges are equally wide, the first one is returned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(0..4), r!(6..20), r!(30..32)];
+    ///
+    /// assert_eq!(Some(&r!(6..20)), set.largest_range());
+    /// ```
+    pub fn largest_range<L: Ord>(&self) -> Option<&Range<T>>
+        where T: Measure<Length=L> {
+        self.as_ref().largest_range()
+    }
+
+    /// Returns the narrowest range in this set, unbounded ranges are considered the widest
+    ///
+    /// When multiple ranges are equally narrow, the first one is returned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(..4), r!(6..20), r!(30..32)];
+    ///
+    /// assert_eq!(Some(&r!(30..32)), set.smallest_range());
+    /// ```
+    pub fn smallest_range<L: Ord>(&self) -> Option<&Range<T>>
+        where T: Measure<Length=L> {
+        self.as_ref().smallest_range()
+    }
+
+    /// Returns the widest gap between 2 ranges in this set, together with the index of the range directly
+    /// preceding it
+    ///
+    /// When multiple gaps are equally wide, the first one is returned
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(..4), r!(6..20), r!(30..32)];
+    ///
+    /// assert_eq!(Some((1, r!(20..30))), set.largest_gap());
+    /// ```
+    pub fn largest_gap<L: Ord>(&self) -> Option<(usize, Range<T>)>
+        where T: Measure<Length=L> {
+        self.as_ref().largest_gap()
+    }
+
+    /// Returns the total measure of all ranges in this set, or `None` if the set is unbounded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// assert_eq!(Some(8), range_set![r!(0u32..4), r!(6..10)].measure());
+    /// assert_eq!(None, range_set![r!(0u32..4), r!(6..)].measure());
+    /// ```
+    pub fn measure<L>(&self) -> Option<L>
+        where T: Measure<Length=L>, L: Add<Output=L> + Default {
+        self.as_ref().measure()
+    }
+
+    /// Returns the total measure of all ranges in this set like [`measure`](RangeSet::measure), but returns `None`
+    /// instead of overflowing
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// assert_eq!(Some(8), range_set![r!(0i8..4), r!(6..10)].checked_measure());
+    /// assert_eq!(Some(199), range_set![r!(-100i8..0), r!(1..100)].checked_measure());
+    /// assert_eq!(None, range_set![r!(-100i8..0), r!(1..)].checked_measure());
+    /// ```
+    pub fn checked_measure<L>(&self) -> Option<L>
+        where T: Measure<Length=L>, L: Default {
+        self.as_ref().checked_measure()
+    }
+
+    /// Returns the total measure of all ranges in this set as a `u128`, summing the widths of extents that wouldn't
+    /// fit the type itself, or `None` if the set is unbounded or the total doesn't even fit in a `u128`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let extents = range_set![r!((i64::MIN)..0), r!(1..i64::MAX)];
+    ///
+    /// assert_eq!(Some(u64::MAX - 1), extents.checked_measure());
+    /// assert_eq!(Some(u64::MAX as u128 - 1), extents.measure_u128());
+    /// ```
+    pub fn measure_u128(&self) -> Option<u128>
+        where T: Discrete {
+        self.as_ref().measure_u128()
+    }
+
+    /// Returns the measure of the intersection of this set and given set, without building the intersection
+    ///
+    /// Returns `None` when the intersection is unbounded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let left = range_set![r!(0u32..10), r!(20..30)];
+    /// let right = range_set![r!(5..25)];
+    ///
+    /// assert_eq!(Some(10), left.intersection_measure(&right));
+    /// assert_eq!(left.intersection(&right).measure(), left.intersection_measure(&right));
+    /// ```
+    pub fn intersection_measure<L>(&self, other: &Self) -> Option<L>
+        where T: Measure<Length=L>, L: Add<Output=L> + Default {
+        self.as_ref().intersection_measure(other.as_ref())
+    }
+
+    /// Returns the Jaccard index of this set and given set, the measure of their intersection divided by the
+    /// measure of their union
+    ///
+    /// Returns `None` when the union is empty or unbounded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let left = range_set![r!(0..10)];
+    /// let right = range_set![r!(5..15)];
+    ///
+    /// assert_eq!(Some(5.0 / 15.0), left.jaccard(&right));
+    /// ```
+    pub fn jaccard<L>(&self, other: &Self) -> Option<f64>
+        where T: Measure<Length=L>, L: Add<Output=L> + Default + ToF64 {
+        self.as_ref().jaccard(other.as_ref())
+    }
+
+    /// Returns the overlap coefficient of this set and given set, the measure of their intersection divided by
+    /// the measure of the smaller of the 2 sets
+    ///
+    /// Returns `None` when either set is empty, or both are unbounded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let left = range_set![r!(0..10)];
+    /// let right = range_set![r!(5..25)];
+    ///
+    /// assert_eq!(Some(0.5), left.overlap_coefficient(&right));
+    /// ```
+    pub fn overlap_coefficient<L>(&self, other: &Self) -> Option<f64>
+        where T: Measure<Length=L>, L: Add<Output=L> + Default + ToF64 + PartialOrd {
+        self.as_ref().overlap_coefficient(other.as_ref())
+    }
+
+    /// Returns `true` if this set and given set only differ in parts narrower than `epsilon`, so boundaries that
+    /// are less than `epsilon` apart and slivers narrower than `epsilon` are ignored
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let millis = Duration::from_millis;
+    /// let left = range_set![r!((millis(0))..millis(1000)), r!((millis(2000))..millis(3000))];
+    /// let right = range_set![r!((millis(1))..millis(999)), r!((millis(1999))..millis(2500)), r!((millis(2501))..millis(3000))];
+    ///
+    /// assert!(left.approx_eq(&right, millis(5)));
+    /// assert!(!left.approx_eq(&right, millis(1)));
+    /// ```
+    pub fn approx_eq<L: PartialOrd>(&self, other: &Self, epsilon: L) -> bool
+        where T: Measure<Length=L> {
+        [self.difference(other), other.difference(self)].iter()
+            .flat_map(RangeSet::items)
+            .all(|range| range.measure().is_some_and(|measure| measure < epsilon))
+    }
+
+    /// Returns the smallest gap between any range in this set and any range in given set, which is zero if they
+    /// overlap or touch, or `None` if either set is empty
+    ///
+    /// Both sets are walked once side by side, whether boundaries are inclusive or exclusive isn't taken into account
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let scheduled = range_set![r!(9u32..10), r!(14..15)];
+    /// let blackout = range_set![r!(..7), r!(12..13), r!(20..)];
+    ///
+    /// assert_eq!(Some(1), scheduled.distance_to(&blackout));
+    /// assert_eq!(Some(0), scheduled.distance_to(&range_set![r!(15..16)]));
+    /// assert_eq!(None, scheduled.distance_to(&range_set![]));
+    /// ```
+    pub fn distance_to<L: PartialOrd + Default>(&self, other: &Self) -> Option<L>
+        where T: Measure<Length=L> {
+        self.as_ref().distance_to(other.as_ref())
+    }
+
+    /// Returns the earliest part of `within` that isn't covered by this set and is at least `duration` wide
+    ///
+    /// The whole uncovered part is returned, clipped to `within`, an unbounded part is wide enough for any duration
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let busy = range_set![r!(9u32..10), r!(11..12), r!(14..16)];
+    ///
+    /// assert_eq!(Some(r!(12..14)), busy.find_slot(2, r!(8..18)));
+    /// assert_eq!(Some(r!(8..9)), busy.find_slot(1, r!(8..18)));
+    /// assert_eq!(Some(r!(16..)), busy.find_slot(3, r!(8..)));
+    /// assert_eq!(None, busy.find_slot(3, r!(8..18)));
+    /// ```
+    pub fn find_slot<L: PartialOrd>(&self, duration: L, within: impl RangeBounds<T>) -> Option<Range<T>>
+        where T: Measure<Length=L> {
+        self.as_ref().find_slot(duration, within)
+    }
+
+    /// Returns the start of this set covering a measure of `length`, which are the earliest ranges with the last one
+    /// cut short so their measure adds up to `length` exactly
+    ///
+    /// The whole set is returned if its measure is less than `length`. A range with an unbounded start has no
+    /// earliest part, so it's returned whole
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// const MIB: u64 = 1 << 20;
+    /// let available = range_set![r!(0..16 * MIB), r!((32 * MIB)..)];
+    ///
+    /// assert_eq!(range_set![r!(0..16 * MIB), r!((32 * MIB)..80 * MIB)], available.take_measure(64 * MIB));
+    /// assert_eq!(Some(64 * MIB), available.take_measure(64 * MIB).measure());
+    /// ```
+    pub fn take_measure<L>(&self, length: L) -> RangeSet<T>
+        where T: Advance<Length=L>, L: PartialOrd + Sub<Output=L> + Default {
+        let mut remaining = length;
+        let mut items = RangeVec::new();
+        for range in self.items.iter() {
+            if remaining <= L::default() {
+                break;
+            }
+
+            let (Included(start) | Excluded(start)) = &range.start else {
+                items.push(range.clone());
+                break;
+            };
+
+            match range.measure() {
+                Some(measure) if measure <= remaining => {
+                    remaining = remaining - measure;
+                    items.push(range.clone());
+                }
+                _ => {
+                    items.push(Range::new(range.start.clone(), Excluded(T::advance(start, remaining))));
+                    break;
+                }
+            }
+        }
+
+        RangeSet { items: items.into() }
+    }
+}
+
+impl<'a, T: Ord + Clone + Debug> RangeSetRef<'a, T> {
+    /// See [`RangeSet::gaps`](RangeSet::gaps)
+    pub fn gaps(&self) -> impl Iterator<Item=Range<T>> + 'a {
+        self.indexed_gaps().map(|(_, gap)| gap)
+    }
+
+    /// The gaps between the ranges in this set together with the index of the range before them, touching ranges,
+    /// e.g. after [`split_boundary_at`](RangeSet::split_boundary_at), leave no gap
+    fn indexed_gaps(&self) -> impl Iterator<Item=(usize, Range<T>)> + 'a {
+        self.as_slice().windows(2)
+            .map(|pair| Range::new(pair[0].end.clone().invert(), pair[1].start.clone().invert()))
+            .enumerate()
+            .filter(|(_, gap)| holds_values(gap.start(), gap.end()))
+    }
+
+    /// See [`RangeSet::gaps_of_at_least`](RangeSet::gaps_of_at_least)
+    pub fn gaps_of_at_least<L: PartialOrd + 'a>(&self, length: L) -> impl Iterator<Item=Range<T>> + 'a
+        where T: Measure<Length=L> {
+        // gaps are always bounded on both sides
+        self.gaps().filter(move |gap| gap.measure().is_some_and(|measure| measure >= length))
+    }
+
+    /// See [`RangeSet::largest_range`](RangeSet::largest_range)
+    pub fn largest_range<L: Ord>(&self) -> Option<&'a Range<T>>
+        where T: Measure<Length=L> {
+        let mut largest: Option<(&Range<T>, Option<L>)> = None;
+
+        for range in self.items() {
+            let measure = range.measure();
+            if largest.as_ref().is_none_or(|(_, current)| is_wider(&measure, current)) {
+                largest = Some((range, measure));
+            }
+        }
+
+        largest.map(|(range, _)| range)
+    }
+
+    /// See [`RangeSet::smallest_range`](RangeSet::smallest_range)
+    pub fn smallest_range<L: Ord>(&self) -> Option<&'a Range<T>>
+        where T: Measure<Length=L> {
+        let mut smallest: Option<(&Range<T>, Option<L>)> = None;
+
+        for range in self.items() {
+            let measure = range.measure();
+            if smallest.as_ref().is_none_or(|(_, current)| is_wider(current, &measure)) {
+                smallest = Some((range, measure));
+            }
+        }
+
+        smallest.map(|(range, _)| range)
+    }
+
+    /// See [`RangeSet::largest_gap`](RangeSet::largest_gap)
+    pub fn largest_gap<L: Ord>(&self) -> Option<(usize, Range<T>)>
+        where T: Measure<Length=L> {
+        let mut largest: Option<(usize, Range<T>, L)> = None;
+
+        for (index, gap) in self.indexed_gaps() {
+            // gaps are always bounded on both sides
+            let Some(measure) = gap.measure() else {
+                continue;
+            };
+
+            if largest.as_ref().is_none_or(|(_, _, current)| measure > *current) {
+                largest = Some((index, gap, measure));
+            }
+        }
+
+        largest.map(|(index, gap, _)| (index, gap))
+    }
+
+    /// See [`RangeSet::measure`](RangeSet::measure)
+    pub fn measure<L>(&self) -> Option<L>
+        where T: Measure<Length=L>, L: Add<Output=L> + Default {
+        self.items().try_fold(L::default(), |total, range| Some(total + range.measure()?))
+    }
+
+    /// See [`RangeSet::checked_measure`](RangeSet::checked_measure)
+    pub fn checked_measure<L>(&self) -> Option<L>
+        where T: Measure<Length=L>, L: Default {
+        self.items().try_fold(L::default(), |total, range| T::checked_add(total, range.checked_measure()?))
+    }
+
+    /// See [`RangeSet::measure_u128`](RangeSet::measure_u128)
+    pub fn measure_u128(&self) -> Option<u128>
+        where T: Discrete {
+        self.items().try_fold(0u128, |total, range| total.checked_add(range.measure_u128()?))
+    }
+
+    /// See [`RangeSet::intersection_measure`](RangeSet::intersection_measure)
+    pub fn intersection_measure<L>(&self, other: RangeSetRef<'_, T>) -> Option<L>
+        where T: Measure<Length=L>, L: Add<Output=L> + Default {
+        Sweep::new([self.ref_items(), other.ref_items()])
+            .filter(|(_, mask)| *mask == 0b11)
+            .try_fold(L::default(), |total, (segment, _)| {
+                let (start, end) = segment.into_inner();
+                Some(total + measure_between(start, end)?)
+            })
+    }
+
+    /// See [`RangeSet::jaccard`](RangeSet::jaccard)
+    pub fn jaccard<L>(&self, other: RangeSetRef<'_, T>) -> Option<f64>
+        where T: Measure<Length=L>, L: Add<Output=L> + Default + ToF64 {
+        let measures = OverlapMeasures::sweep(*self, other);
+        ratio(measures.intersection?, measures.union?)
+    }
+
+    /// See [`RangeSet::overlap_coefficient`](RangeSet::overlap_coefficient)
+    pub fn overlap_coefficient<L>(&self, other: RangeSetRef<'_, T>) -> Option<f64>
+        where T: Measure<Length=L>, L: Add<Output=L> + Default + ToF64 + PartialOrd {
+        let measures = OverlapMeasures::sweep(*self, other);
+        let smallest = match (measures.left, measures.right) {
+            (Some(left), Some(right)) => if left < right { left } else { right },
+            (Some(measure), None) | (None, Some(measure)) => measure,
+            (None, None) => return None,
+        };
+
+        ratio(measures.intersection?, smallest)
+    }
+
+    /// See [`RangeSet::distance_to`](RangeSet::distance_to)
+    pub fn distance_to<L: PartialOrd + Default>(&self, other: RangeSetRef<'_, T>) -> Option<L>
+        where T: Measure<Length=L> {
+        let (mut left, mut right) = (self.items().peekable(), other.items().peekable());
+        let mut smallest: Option<L> = None;
+        while let (Some(&left_range), Some(&right_range)) = (left.peek(), right.peek()) {
+            // The range ending first can't be any closer to the ranges after the other one
+            let (before, after) = if cmp_bounds(left_range.end_pos(), right_range.start_pos()).is_le() {
+                left.next();
+                (left_range, right_range)
+            } else if cmp_bounds(right_range.end_pos(), left_range.start_pos()).is_le() {
+                right.next();
+                (right_range, left_range)
+            } else {
+                return Some(L::default());
+            };
+
+            let gap = measure_between(before.end(), after.start())?;
+            if smallest.as_ref().is_none_or(|smallest| gap < *smallest) {
+                smallest = Some(gap);
+            }
+        }
+
+        smallest
+    }
+
+    /// See [`RangeSet::find_slot`](RangeSet::find_slot)
+    pub fn find_slot<L: PartialOrd>(&self, duration: L, within: impl RangeBounds<T>) -> Option<Range<T>>
+        where T: Measure<Length=L> {
+        let fits = |slot: &Range<T>| slot.start_pos() < slot.end_pos()
+            && measure_between(slot.start(), slot.end()).is_none_or(|measure| measure >= duration);
+
+        let within = Range::from_range(within);
+        let mut start = within.start.clone();
+        for range in self.overlapping_slice(within.as_ref()) {
+            // Nothing comes before a range with an unbounded start, and nothing after one with an unbounded end
+            if range.start != Bound::Unbounded {
+                let slot = Range::new(start, range.start.clone().invert());
+                if fits(&slot) {
+                    return Some(slot);
+                }
+            }
+
+            if range.end == Bound::Unbounded {
+                return None;
+            }
+
+            start = range.end.clone().invert();
+        }
+
+        Some(Range::new(start, within.end.clone())).filter(fits)
+    }
+}
+
+/// The measures of 2 sets, their intersection and their union, `None` means unbounded
+struct OverlapMeasures<L> {
+    left: Option<L>,
+    right: Option<L>,
+    intersection: Option<L>,
+    union: Option<L>,
+}
+
+impl<L: Add<Output=L> + Default> OverlapMeasures<L> {
+    fn sweep<T: Ord + Clone + Debug + Measure<Length=L>>(left: RangeSetRef<'_, T>, right: RangeSetRef<'_, T>) -> Self {
+        let mut measures = OverlapMeasures {
+            left: Some(L::default()),
+            right: Some(L::default()),
+            intersection: Some(L::default()),
+            union: Some(L::default()),
+        };
+
+        for (segment, mask) in Sweep::new([left.ref_items(), right.ref_items()]) {
+            if mask == 0 {
+                continue;
+            }
+
+            let (start, end) = segment.into_inner();
+            let accumulate = |total: Option<L>| Some(total? + measure_between(start, end)?);
+
+            if mask & 0b01 != 0 {
+                measures.left = accumulate(measures.left);
+            }
+
+            if mask & 0b10 != 0 {
+                measures.right = accumulate(measures.right);
+            }
+
+            if mask == 0b11 {
+                measures.intersection = accumulate(measures.intersection);
+            }
+
+            measures.union = accumulate(measures.union);
+        }
+
+        measures
+    }
+}
+
+fn ratio<L: ToF64>(numerator: L, denominator: L) -> Option<f64> {
+    let denominator = denominator.to_f64();
+    if denominator == 0.0 {
+        return None;
+    }
+
+    Some(numerator.to_f64() / denominator)
+}
+
+/// The distance between 2 boundaries, or `None` when either is unbounded
+pub(crate) fn measure_between<T: Measure<Length=L>, L>(start: Bound<&T>, end: Bound<&T>) -> Option<L> {
+    match (start, end) {
+        (Included(start) | Excluded(start), Included(end) | Excluded(end)) => Some(T::distance(start, end)),
+        _ => None,
+    }
+}
+
+/// Compares 2 measures where `None` stands for an unbounded, thus infinite, measure
+fn is_wider<L: Ord>(candidate: &Option<L>, current: &Option<L>) -> bool {
+    match (candidate, current) {
+        (None, Some(_)) => true,
+        (Some(candidate), Some(current)) => candidate > current,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+    use crate::{r, range_set, RangeSet};
+
+    #[test]
+    fn extremes() {
+        let empty: RangeSet<usize> = range_set![];
+        assert_eq!(None, empty.largest_range());
+        assert_eq!(None, empty.smallest_range());
+        assert_eq!(None, empty.largest_gap());
+
+        let set = range_set![r!(0..4), r!(6..10), r!(20..24)];
+        assert_eq!(Some(&r!(0..4)), set.largest_range());
+        assert_eq!(Some(&r!(0..4)), set.smallest_range());
+        assert_eq!(Some((1, r!(10..20))), set.largest_gap());
+
+        let set = range_set![r!(..0), r!(6..10), r!(20..)];
+        assert_eq!(Some(&r!(..0)), set.largest_range());
+        assert_eq!(Some(&r!(6..10)), set.smallest_range());
+        assert_eq!(Some((1, r!(10..20))), set.largest_gap());
+
+        let set: RangeSet<usize> = RangeSet::unbound();
+        assert_eq!(Some(&r!(..)), set.largest_range());
+        assert_eq!(Some(&r!(..)), set.smallest_range());
+        assert_eq!(None, set.largest_gap());
+    }
+
+    #[test]
+    fn intersection_measure() {
+        let empty: RangeSet<usize> = range_set![];
+        assert_eq!(Some(0), empty.measure());
+        assert_eq!(Some(0), empty.intersection_measure(&RangeSet::unbound()));
+
+        let left: RangeSet<i32> = range_set![r!(0..10), r!(20..30)];
+        let right: RangeSet<i32> = range_set![r!(5..25), r!(28..)];
+        assert_eq!(Some(20), left.measure());
+        assert_eq!(None, right.measure());
+        assert_eq!(Some(12), left.intersection_measure(&right));
+        assert_eq!(Some(12), right.intersection_measure(&left));
+        assert_eq!(None, right.intersection_measure(&right));
+        assert_eq!(Some(0), left.intersection_measure(&range_set![r!(10..20)]));
+    }
+
+    #[test]
+    fn similarity() {
+        let empty: RangeSet<u32> = range_set![];
+        assert_eq!(None, empty.jaccard(&empty));
+        assert_eq!(None, empty.overlap_coefficient(&empty));
+
+        let left = range_set![r!(0u32..10), r!(20..30)];
+        let right = range_set![r!(5u32..25)];
+        assert_eq!(Some(10.0 / 30.0), left.jaccard(&right));
+        assert_eq!(Some(10.0 / 30.0), right.jaccard(&left));
+        assert_eq!(Some(0.5), left.overlap_coefficient(&right));
+        assert_eq!(Some(1.0), left.jaccard(&left));
+        assert_eq!(Some(0.0), left.jaccard(&range_set![r!(40..50)]));
+
+        let unbound = range_set![r!(4u32..)];
+        assert_eq!(None, left.jaccard(&unbound));
+        assert_eq!(Some(16.0 / 20.0), left.overlap_coefficient(&unbound));
+        assert_eq!(None, unbound.overlap_coefficient(&unbound));
+
+        let left = range_set![r!(0u64..10), r!(20..30)];
+        assert_eq!(Some(10.0 / 30.0), left.jaccard(&range_set![r!(5u64..25)]));
+        assert_eq!(Some(0.5), left.overlap_coefficient(&range_set![r!(5u64..25)]));
+
+        let secs = Duration::from_secs;
+        let left = range_set![r!((secs(0))..secs(10))];
+        assert_eq!(Some(0.25), left.jaccard(&range_set![r!((secs(6))..secs(16))]));
+        assert_eq!(Some(0.4), left.overlap_coefficient(&range_set![r!((secs(6))..secs(16))]));
+    }
+
+    #[test]
+    fn approx_eq() {
+        let set: RangeSet<i32> = range_set![r!(0..10), r!(20..30)];
+        assert!(set.approx_eq(&set, 0));
+        assert!(set.approx_eq(&range_set![r!(1..10), r!(19..24), r!(25..=30)], 2));
+        assert!(!set.approx_eq(&range_set![r!(1..10), r!(19..24), r!(25..=30)], 1));
+        assert!(set.approx_eq(&range_set![r!(0..10), r!(20..30), r!(40..41)], 2));
+        assert!(!set.approx_eq(&range_set![r!(0..10), r!(20..)], 100));
+        assert!(!set.approx_eq(&RangeSet::empty(), 10));
+        assert!(set.approx_eq(&RangeSet::empty(), 11));
+    }
+
+    #[test]
+    fn distance_to() {
+        let set = range_set![r!(0..10), r!(20..30), r!(50..)];
+        assert_eq!(Some(0), set.distance_to(&set));
+        assert_eq!(Some(5), set.distance_to(&range_set![r!(..-5), r!(35..=42)]));
+        assert_eq!(Some(5), range_set![r!(..-5), r!(35..=42)].distance_to(&set));
+        assert_eq!(Some(2), set.distance_to(&range_set![r!(12..18)]));
+        assert_eq!(Some(0), set.distance_to(&range_set![r!(10..20)]));
+        assert_eq!(Some(0), set.distance_to(&range_set![r!(12..18), r!(40..=50)]));
+        assert_eq!(Some(0), set.distance_to(&range_set![r!(29..31)]));
+        assert_eq!(Some(0), set.distance_to(&RangeSet::unbound()));
+        assert_eq!(Some(8), range_set![r!(..0i32)].distance_to(&range_set![r!(8..), r!(100..)]));
+        assert_eq!(None, set.distance_to(&RangeSet::empty()));
+        assert_eq!(None, RangeSet::<i32>::empty().distance_to(&set));
+    }
+
+    #[test]
+    fn find_slot() {
+        let empty: RangeSet<u32> = range_set![];
+        assert_eq!(Some(r!(0..10)), empty.find_slot(10, r!(0..10)));
+        assert_eq!(None, empty.find_slot(11, r!(0..10)));
+
+        let busy: RangeSet<i32> = range_set![r!(..2), r!(4..=6), r!(8..10), r!(20..)];
+        assert_eq!(Some(r!(2..4)), busy.find_slot(2, r!(..)));
+        assert_eq!(Some(r!(6>..8)), busy.find_slot(2, r!(5..9)));
+        assert_eq!(Some(r!(10..20)), busy.find_slot(3, r!(..)));
+        assert_eq!(Some(r!(10..15)), busy.find_slot(5, r!(10..15)));
+        assert_eq!(None, busy.find_slot(11, r!(..)));
+        assert_eq!(None, busy.find_slot(0, r!(4..=5)));
+    }
+
+    #[test]
+    fn checked() {
+        let set = range_set![r!((i64::MIN)..0), r!(1..i64::MAX)];
+        assert_eq!(Some(u64::MAX - 1), set.checked_measure());
+        assert_eq!(Some(200), range_set![r!(-100i8..100)].measure());
+        assert_eq!(Some(200), range_set![r!(-100i8..100)].checked_measure());
+        assert_eq!(Some(u8::MAX), range_set![r!((i8::MIN)..=i8::MAX)].checked_measure());
+        assert_eq!(Some(u64::MAX as u128 - 1), set.measure_u128());
+        assert_eq!(None, range_set![r!(0u8..2), r!(3..)].measure_u128());
+
+        let empty: RangeSet<u64> = range_set![];
+        assert_eq!(Some(0), empty.checked_measure());
+        assert_eq!(Some(0), empty.measure_u128());
+        assert_eq!(Some(u64::MAX), range_set![r!(0..u64::MAX)].checked_measure());
+        assert_eq!(Some(u64::MAX), range_set![r!(0..u64::MAX), r!((u64::MAX)..=u64::MAX)].checked_measure());
+        assert_eq!(Some(Duration::MAX), range_set![r!((Duration::ZERO)..Duration::MAX)].checked_measure());
+    }
+
+    #[test]
+    fn take_measure() {
+        let set: RangeSet<i32> = range_set![r!(0..10), r!(20>..=30), r!(40..)];
+        assert_eq!(RangeSet::empty(), set.take_measure(0));
+        assert_eq!(range_set![r!(0..4)], set.take_measure(4));
+        assert_eq!(range_set![r!(0..10)], set.take_measure(10));
+        assert_eq!(range_set![r!(0..10), r!(20>..25)], set.take_measure(15));
+        assert_eq!(range_set![r!(0..10), r!(20>..=30), r!(40..1000)], set.take_measure(980));
+
+        let second = Duration::from_secs(1);
+        let bounded = range_set![r!((Duration::ZERO)..second), r!((second * 2)..=second * 4)];
+        assert_eq!(range_set![r!((Duration::ZERO)..second), r!((second * 2)..second * 3)], bounded.take_measure(second * 2));
+        assert_eq!(bounded, bounded.take_measure(second * 10));
+
+        let unbounded: RangeSet<i32> = range_set![r!(..0), r!(10..20)];
+        assert_eq!(range_set![r!(..0)], unbounded.take_measure(5));
+        assert_eq!(RangeSet::<u32>::empty(), RangeSet::empty().take_measure(5));
+    }
+
+    #[test]
+    fn durations() {
+        let second = Duration::from_secs(1);
+        let set = range_set![r!((second)..second * 3), r!((second * 4)..second * 10)];
+        assert_eq!(Some(second * 8), set.measure());
+        assert_eq!(Some(r!((second * 3)..second * 4)), set.gaps_of_at_least(second).next());
+        assert_eq!(None, set.gaps_of_at_least(second * 2).next());
+
+        let now = Instant::now();
+        let set = range_set![r!((now)..now + second), r!((now + second * 3)..)];
+        assert_eq!(Some(second * 2), set.gaps().next().and_then(|gap| gap.measure()));
+        assert_eq!(Some(r!((now + second)..now + second * 3)), set.find_slot(second * 2, r!((now)..)));
+    }
+}
@@ -0,0 +1,118 @@
+use std::fmt::Debug;
+use std::iter::{FusedIterator, Peekable};
+use crate::{Bound, Range};
+
+/// Lazily merge 2 iterators of ranges, both sorted by their start, into a sorted iterator of non-overlapping ranges
+///
+/// Ranges overlapping or touching each other are merged, also when they originate from the same iterator
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{merge_sorted, r};
+///
+/// let left = vec![r!(0..4), r!(3..6), r!(10..12)];
+/// let right = vec![r!(6..8), r!(14..)];
+///
+/// let merged: Vec<_> = merge_sorted(left, right).collect();
+/// assert_eq!(vec![r!(0..8), r!(10..12), r!(14..)], merged);
+/// ```
+pub fn merge_sorted<T, A, B>(left: A, right: B) -> MergeSorted<T, A::IntoIter, B::IntoIter>
+    where T: Ord + Debug,
+          A: IntoIterator<Item=Range<T>>,
+          B: IntoIterator<Item=Range<T>> {
+    MergeSorted {
+        left: left.into_iter().peekable(),
+        right: right.into_iter().peekable(),
+        current: None,
+        done: false,
+    }
+}
+
+/// Iterator created by [`merge_sorted`](merge_sorted)
+#[derive(Debug)]
+pub struct MergeSorted<T: Ord, A: Iterator<Item=Range<T>>, B: Iterator<Item=Range<T>>> {
+    left: Peekable<A>,
+    right: Peekable<B>,
+    current: Option<Range<T>>,
+    done: bool,
+}
+
+impl<T: Ord + Debug, A: Iterator<Item=Range<T>>, B: Iterator<Item=Range<T>>> MergeSorted<T, A, B> {
+    fn next_input(&mut self) -> Option<Range<T>> {
+        match (self.left.peek(), self.right.peek()) {
+            (Some(left), Some(right)) if right.start_pos() < left.start_pos() => self.right.next(),
+            (Some(_), _) => self.left.next(),
+            (None, _) => self.right.next(),
+        }
+    }
+}
+
+impl<T: Ord + Debug, A: Iterator<Item=Range<T>>, B: Iterator<Item=Range<T>>> Iterator for MergeSorted<T, A, B> {
+    type Item = Range<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            let Some(range) = self.next_input() else {
+                self.done = true;
+                return self.current.take();
+            };
+
+            let Some(mut current) = self.current.take() else {
+                self.current = Some(range);
+                continue;
+            };
+
+            debug_assert!(current.start_pos() <= range.start_pos(), "range ({:?}) given to merge_sorted is lower than previous range {:?}", range, current);
+
+            if current.end_pos() < range.start_pos() {
+                self.current = Some(range);
+                return Some(current);
+            }
+
+            if range.end_pos() > current.end_pos() {
+                current.end = range.end;
+            }
+
+            // Nothing can follow a range without end, so stop reading the inputs
+            if current.end == Bound::Unbounded {
+                self.done = true;
+                return Some(current);
+            }
+
+            self.current = Some(current);
+        }
+    }
+}
+
+impl<T: Ord + Debug, A: Iterator<Item=Range<T>>, B: Iterator<Item=Range<T>>> FusedIterator for MergeSorted<T, A, B> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{merge_sorted, r, Range, RangeSet};
+
+    #[test]
+    fn merge() {
+        let empty: Vec<Range<usize>> = vec![];
+        assert_eq!(0, merge_sorted(empty.clone(), empty.clone()).count());
+
+        let merged: Vec<_> = merge_sorted(vec![r!(1..3)], empty).collect();
+        assert_eq!(vec![r!(1..3)], merged);
+
+        let merged: Vec<_> = merge_sorted(vec![r!(1..3), r!(8..9)], vec![r!(..=1), r!(3..5), r!(9>..10)]).collect();
+        assert_eq!(vec![r!(..5), r!(8..9), r!(9>..10)], merged);
+
+        // Stops reading once the merged range is unbounded
+        let merged: Vec<_> = merge_sorted((0..).map(|x| r!((x)..x + 1)), vec![r!(..)]).collect();
+        assert_eq!(vec![r!(..)], merged);
+
+        let left = RangeSet::from([r!(0..4), r!(10..20)]);
+        let right = RangeSet::from([r!(2..12), r!(30..)]);
+        let merged: Vec<_> = merge_sorted(left.items().cloned(), right.items().cloned()).collect();
+        assert_eq!(left.union(&right).items().cloned().collect::<Vec<_>>(), merged);
+    }
+}
@@ -0,0 +1,266 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::ops::RangeBounds;
+use std::path::Path;
+use memmap2::Mmap;
+use crate::{Bound, Range, RangeSet, RangeSetBuilder};
+
+/// The bytes every mapped set starts with
+const MAGIC: [u8; 4] = *b"RSMM";
+/// The version of the format written, sets written in another version are rejected
+const VERSION: u8 = 1;
+/// The magic, version, a byte of flags, two reserved bytes and the amount of ranges
+const HEADER_LEN: usize = 16;
+/// The first and last value of a range
+const RANGE_LEN: usize = 16;
+
+/// A read-only set of `u64` values answering queries directly against a memory-mapped file, written by
+/// [`RangeSet::write_mapped_to`](RangeSet::write_mapped_to)
+///
+/// Only the pages touched by the binary search of a query are read from disk, and the operating system shares
+/// them between processes mapping the same file. Opening a file only validates its header and length, use
+/// [`verify`](MmapRangeSet::verify) to also check that the ranges are sorted
+///
+/// # Example
+///
+/// ```rust
+/// use std::fs::File;
+/// use eater_rangeset::{r, range_set, MmapRangeSet};
+///
+/// let path = std::env::temp_dir().join(format!("eater_rangeset_mmap_example_{}", std::process::id()));
+/// let set = range_set![r!(0u64..4096), r!(16384..=20480)];
+/// set.write_mapped_to(File::create(&path).unwrap()).unwrap();
+///
+/// // SAFETY: Nothing else writes to this file while it's mapped
+/// let mapped = unsafe { MmapRangeSet::open(&path) }.unwrap();
+/// assert!(mapped.contains(20480));
+/// assert!(!mapped.contains(4096));
+/// assert_eq!(vec![r!(16384..=20480)], mapped.overlapping(r!(10000..16385)).collect::<Vec<_>>());
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct MmapRangeSet {
+    map: Mmap,
+    len: usize,
+}
+
+fn invalid_data(reason: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason.to_string())
+}
+
+impl RangeSet<u64> {
+    /// Write this set in the format read by [`MmapRangeSet::open`](MmapRangeSet::open)
+    ///
+    /// The set is written as a header holding the bytes `RSMM`, the version of the format, a byte of flags, two
+    /// reserved bytes and the amount of ranges, followed by the first and last value of every range. All numbers are
+    /// little-endian `u64`s, so every range is aligned to 8 bytes. The writer isn't buffered, so wrap it in a
+    /// [`BufWriter`](std::io::BufWriter) when writing to a file
+    pub fn write_mapped_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let ranges: Vec<_> = self.items()
+            .filter_map(|range| range.first_value().zip(range.last_value()))
+            .filter(|(first, last)| first <= last)
+            .collect();
+
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[VERSION, 0, 0, 0])?;
+        writer.write_all(&(ranges.len() as u64).to_le_bytes())?;
+        for (first, last) in ranges {
+            writer.write_all(&first.to_le_bytes())?;
+            writer.write_all(&last.to_le_bytes())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl MmapRangeSet {
+    /// Map the set in given file into memory
+    ///
+    /// Returns an error with [`InvalidData`](io::ErrorKind::InvalidData) if the file doesn't hold a set in a
+    /// version of the format that can be read, or if its length doesn't match the amount of ranges in its header
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, by this or any other process, while the returned set is alive.
+    /// The set reads the mapped memory directly, so a change to the file is undefined behavior and truncating it can
+    /// crash the process when a removed page is read
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<MmapRangeSet> {
+        let file = File::open(path)?;
+        // SAFETY: The caller guarantees the file isn't modified while it's mapped
+        let map = unsafe { Mmap::map(&file)? };
+        let header: &[u8; HEADER_LEN] = map.get(..HEADER_LEN)
+            .and_then(|header| header.try_into().ok())
+            .ok_or_else(|| invalid_data("not a mapped range set"))?;
+
+        let [magic @ .., version, flags, _, _] = *header.first_chunk::<8>().unwrap();
+        if magic != MAGIC {
+            return Err(invalid_data("not a mapped range set"));
+        }
+
+        if version != VERSION {
+            return Err(invalid_data(format!("mapped range set is written in version {}, only {} is supported", version, VERSION)));
+        }
+
+        if flags != 0 {
+            return Err(invalid_data(format!("mapped range set is written with unknown flags {:#04x}", flags)));
+        }
+
+        let count = u64::from_le_bytes(*header.last_chunk().unwrap());
+        let expected = usize::try_from(count).ok()
+            .and_then(|count| count.checked_mul(RANGE_LEN))
+            .and_then(|len| len.checked_add(HEADER_LEN));
+
+        if expected != Some(map.len()) {
+            return Err(invalid_data(format!("mapped range set holds {} ranges but is {} bytes long", count, map.len())));
+        }
+
+        Ok(MmapRangeSet { map, len: count as usize })
+    }
+
+    /// The amount of ranges in this set
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// If this is an empty set
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The first and last value of the range at given index
+    fn get(&self, index: usize) -> (u64, u64) {
+        let offset = HEADER_LEN + index * RANGE_LEN;
+        let value = |offset: usize| u64::from_le_bytes(self.map[offset..offset + 8].try_into().unwrap());
+        (value(offset), value(offset + 8))
+    }
+
+    /// The index of the first range for which `pred` returns `false`, the ranges are assumed to be partitioned by it
+    fn partition_point(&self, pred: impl Fn((u64, u64)) -> bool) -> usize {
+        let (mut low, mut high) = (0, self.len);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if pred(self.get(middle)) {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+
+        low
+    }
+
+    /// Check that the ranges in the file are sorted and don't overlap, which reads the whole file
+    ///
+    /// Returns an error with [`InvalidData`](io::ErrorKind::InvalidData) for the first range that isn't
+    pub fn verify(&self) -> io::Result<()> {
+        let mut previous: Option<u64> = None;
+        for index in 0..self.len {
+            let (first, last) = self.get(index);
+            if first > last || previous.is_some_and(|previous| previous >= first) {
+                return Err(invalid_data(format!("range {} at index {} isn't sorted", Range::new(Bound::Included(first), Bound::Included(last)), index)));
+            }
+
+            previous = Some(last);
+        }
+
+        Ok(())
+    }
+
+    /// Check if `value` is inside of this set
+    pub fn contains(&self, value: u64) -> bool {
+        let index = self.partition_point(|(_, last)| last < value);
+        index < self.len && self.get(index).0 <= value
+    }
+
+    /// Returns an iterator over the inclusive ranges in this set
+    pub fn items(&self) -> impl Iterator<Item=Range<u64>> + '_ {
+        self.slice(0, self.len)
+    }
+
+    /// Returns an iterator over the inclusive ranges in this set that overlap with given range
+    pub fn overlapping(&self, range: impl RangeBounds<u64>) -> impl Iterator<Item=Range<u64>> + '_ {
+        let range = Range::from_range(range);
+        let (start, end) = match (range.first_value(), range.last_value()) {
+            (Some(first), Some(last)) if first <= last => (
+                self.partition_point(|(_, end)| end < first),
+                self.partition_point(|(start, _)| start <= last),
+            ),
+            _ => (0, 0),
+        };
+
+        self.slice(start, end.max(start))
+    }
+
+    fn slice(&self, start: usize, end: usize) -> impl Iterator<Item=Range<u64>> + '_ {
+        (start..end).map(|index| {
+            let (first, last) = self.get(index);
+            Range::new(Bound::Included(first), Bound::Included(last))
+        })
+    }
+
+    /// Read this set into memory as a [`RangeSet`](RangeSet) of inclusive ranges
+    pub fn to_range_set(&self) -> RangeSet<u64> {
+        let mut builder = RangeSetBuilder::with_capacity(self.len);
+        for range in self.items() {
+            builder.push(range);
+        }
+
+        builder.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::ErrorKind;
+    use crate::{r, range_set, MmapRangeSet, RangeSet};
+
+    #[test]
+    fn mmap() {
+        let path = std::env::temp_dir().join(format!("eater_rangeset_mmap_{}", std::process::id()));
+        let set = range_set![r!(..3u64), r!(5..=5), r!(6>..9), r!(40..600), r!(1000..)];
+        let mut bytes = vec![];
+        set.write_mapped_to(&mut bytes).unwrap();
+        assert_eq!(16 + 5 * 16, bytes.len());
+        fs::write(&path, &bytes).unwrap();
+
+        let mapped = unsafe { MmapRangeSet::open(&path) }.unwrap();
+        mapped.verify().unwrap();
+        assert_eq!(5, mapped.len());
+        for value in (0..1100).chain([u64::MAX]) {
+            assert_eq!(set.contains(&value), mapped.contains(value), "{}", value);
+        }
+
+        assert_eq!(vec![r!(5..=5), r!(7..=8)], mapped.overlapping(r!(4..8)).collect::<Vec<_>>());
+        assert_eq!(vec![r!(1000..=u64::MAX)], mapped.overlapping(r!(600..)).collect::<Vec<_>>());
+        assert_eq!(0, mapped.overlapping(r!(10..40)).count());
+        assert_eq!(0, mapped.overlapping(r!(50..50)).count());
+        assert_eq!(mapped.items().collect::<Vec<_>>(), mapped.overlapping(r!(..)).collect::<Vec<_>>());
+        assert_eq!(range_set![r!(0..=2u64), r!(5..=5), r!(7..=8), r!(40..=599), r!(1000..=u64::MAX)], mapped.to_range_set());
+        // The file may only be rewritten once it isn't mapped anymore
+        drop(mapped);
+
+        let error = |bytes: &[u8]| {
+            fs::write(&path, bytes).unwrap();
+            unsafe { MmapRangeSet::open(&path) }.unwrap_err().kind()
+        };
+
+        assert_eq!(ErrorKind::InvalidData, error(&bytes[..bytes.len() - 1]));
+        assert_eq!(ErrorKind::InvalidData, error(&bytes[..8]));
+        assert_eq!(ErrorKind::InvalidData, error(&[b"RSET", &bytes[4..]].concat()));
+        assert_eq!(ErrorKind::InvalidData, error(&[&bytes[..4], &[2], &bytes[5..]].concat()));
+        assert_eq!(ErrorKind::InvalidData, error(&[&bytes[..5], &[1], &bytes[6..]].concat()));
+
+        // Swapping two ranges is only found by verifying
+        fs::write(&path, [&bytes[..16], &bytes[32..48], &bytes[16..32], &bytes[48..]].concat()).unwrap();
+        assert_eq!(ErrorKind::InvalidData, unsafe { MmapRangeSet::open(&path) }.unwrap().verify().unwrap_err().kind());
+
+        RangeSet::empty().write_mapped_to(fs::File::create(&path).unwrap()).unwrap();
+        let empty = unsafe { MmapRangeSet::open(&path) }.unwrap();
+        assert!(empty.is_empty() && !empty.contains(0));
+        assert_eq!(0, empty.overlapping(r!(..)).count());
+        fs::remove_file(&path).unwrap();
+    }
+}
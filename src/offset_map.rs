@@ -0,0 +1,128 @@
+use std::fmt::Debug;
+use std::ops::{Add, Sub};
+use crate::{Advance, Measure, Range, RangeSet};
+use crate::Bound::{Excluded, Included, Unbounded};
+
+/// Translates between offsets in the logical stream formed by concatenating the ranges of a set and the values
+/// inside of those ranges, created by [`RangeSet::offset_map`](RangeSet::offset_map)
+///
+/// The logical offset at which every range starts is calculated once, so both translations binary search over the
+/// ranges. Like [`measure`](Range::measure), whether boundaries are inclusive or exclusive isn't taken into account
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set};
+///
+/// // The blocks of a sparse file that hold data
+/// let extents = range_set![r!(0u64..4096), r!(16384..20480), r!(65536..)];
+/// let map = extents.offset_map().unwrap();
+///
+/// assert_eq!(Some(100), map.logical_to_physical(100));
+/// assert_eq!(Some(16484), map.logical_to_physical(4196));
+/// assert_eq!(Some(65636), map.logical_to_physical(8292));
+/// assert_eq!(Some(4196), map.physical_to_logical(&16484));
+/// assert_eq!(None, map.physical_to_logical(&8192));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OffsetMap<'a, T: Ord, L> {
+    items: &'a [Range<T>],
+    /// The logical offset at which each range starts
+    offsets: Vec<L>,
+    /// The length of the logical stream, `None` if the last range is unbounded
+    length: Option<L>,
+}
+
+impl<T: Ord + Clone + Debug> RangeSet<T> {
+    /// Returns a map translating between offsets in the concatenated ranges of this set and the values inside of
+    /// them, or `None` when this set starts unbounded so there's no start to count offsets from
+    pub fn offset_map<L>(&self) -> Option<OffsetMap<'_, T, L>>
+        where T: Measure<Length=L>, L: Add<Output=L> + Default + Clone {
+        if self.items.first().is_some_and(|first| first.start == Unbounded) {
+            return None;
+        }
+
+        let mut offsets = Vec::with_capacity(self.items.len());
+        let mut length = Some(L::default());
+        for range in self.items.iter() {
+            let start = length.clone()?;
+            length = range.measure().map(|measure| start.clone() + measure);
+            offsets.push(start);
+        }
+
+        Some(OffsetMap { items: &self.items, offsets, length })
+    }
+}
+
+impl<'a, T: Ord + Clone, L: PartialOrd + Add<Output=L> + Sub<Output=L> + Clone> OffsetMap<'a, T, L> {
+    /// The length of the logical stream, which is the measure of the set, or `None` if it's unbounded
+    pub fn len(&self) -> Option<&L> {
+        self.length.as_ref()
+    }
+
+    /// If the logical stream holds nothing
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the value at given offset in the logical stream, or `None` if the offset is past its end
+    pub fn logical_to_physical(&self, offset: L) -> Option<T>
+        where T: Advance<Length=L> {
+        if self.length.as_ref().is_some_and(|length| offset >= *length) {
+            return None;
+        }
+
+        let index = self.offsets.partition_point(|start| *start <= offset).checked_sub(1)?;
+        let (Included(start) | Excluded(start)) = &self.items[index].start else {
+            return None;
+        };
+
+        Some(T::advance(start, offset - self.offsets[index].clone()))
+    }
+
+    /// Returns the offset of given value in the logical stream, or `None` if it's not inside of the set
+    pub fn physical_to_logical(&self, value: &T) -> Option<L>
+        where T: Measure<Length=L> {
+        let index = self.items.partition_point(|range| range.end_pos() < value);
+        let range = self.items.get(index).filter(|range| range.start_pos() < value)?;
+        let (Included(start) | Excluded(start)) = &range.start else {
+            return None;
+        };
+
+        Some(self.offsets[index].clone() + T::distance(start, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeSet};
+
+    #[test]
+    fn offset_map() {
+        let set = range_set![r!(10u32..20), r!(30..40), r!(50..60)];
+        let map = set.offset_map().unwrap();
+        assert_eq!(Some(&30), map.len());
+        assert_eq!(None, map.logical_to_physical(30));
+        for (logical, physical) in [(0, 10), (9, 19), (10, 30), (19, 39), (20, 50), (29, 59)] {
+            assert_eq!(Some(physical), map.logical_to_physical(logical));
+            assert_eq!(Some(logical), map.physical_to_logical(&physical));
+        }
+
+        assert_eq!(None, map.physical_to_logical(&40));
+        assert_eq!(None, map.physical_to_logical(&9));
+        assert_eq!(None, map.physical_to_logical(&20));
+        assert_eq!(None, map.physical_to_logical(&60));
+
+        let unbounded = range_set![r!(10u32..20), r!(30..)];
+        let map = unbounded.offset_map().unwrap();
+        assert_eq!(None, map.len());
+        assert_eq!(Some(u32::MAX), map.logical_to_physical(u32::MAX - 20));
+        assert_eq!(Some(u32::MAX - 20), map.physical_to_logical(&u32::MAX));
+
+        let empty = RangeSet::<u32>::empty();
+        let map = empty.offset_map().unwrap();
+        assert!(map.is_empty());
+        assert_eq!(None, map.logical_to_physical(0));
+        assert!(range_set![r!(..10u32)].offset_map::<u32>().is_none());
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt::Debug;
+use rayon::iter::plumbing::{bridge_unindexed, Folder, UnindexedConsumer, UnindexedProducer};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use crate::{Discrete, Range, RangeSet, RangeSetRef};
+use crate::values::{value_bounds, Values};
+
+/// Parallel iterator over every value inside of a set, created by [`RangeSet::par_values`](RangeSet::par_values)
+///
+/// The work is split at the boundaries between ranges first, a single range left is split in half at its midpoint
+/// until rayon stops asking, so one huge range is spread over all threads as well
+#[derive(Debug, Clone)]
+pub struct ParValues<'a, T: Ord> {
+    /// The values of a part of a range that have to be produced before the ranges
+    head: Option<(T, T)>,
+    ranges: &'a [Range<T>],
+}
+
+impl<'a, T: Discrete + Clone + Send + Sync> ParallelIterator for ParValues<'a, T> {
+    type Item = T;
+
+    fn drive_unindexed<C: UnindexedConsumer<T>>(self, consumer: C) -> C::Result {
+        bridge_unindexed(self, consumer)
+    }
+}
+
+impl<'a, T: Discrete + Clone + Send + Sync> UnindexedProducer for ParValues<'a, T> {
+    type Item = T;
+
+    fn split(self) -> (Self, Option<Self>) {
+        match (self.head, self.ranges) {
+            (Some(head), ranges @ [_, ..]) => (
+                ParValues { head: Some(head), ranges: &[] },
+                Some(ParValues { head: None, ranges }),
+            ),
+            (None, [range]) => match value_bounds(range) {
+                Some(head) => ParValues { head: Some(head), ranges: &[] }.split(),
+                None => (ParValues { head: None, ranges: &[] }, None),
+            },
+            (None, ranges @ [_, _, ..]) => {
+                let (left, right) = ranges.split_at(ranges.len() / 2);
+                (ParValues { head: None, ranges: left }, Some(ParValues { head: None, ranges: right }))
+            }
+            (Some((first, last)), []) => {
+                let middle = T::steps_between(&first, &last)
+                    .filter(|steps| *steps > 0)
+                    .and_then(|steps| first.forward(steps / 2))
+                    .and_then(|middle| Some((middle.successor()?, middle)));
+
+                match middle {
+                    Some((after, middle)) => (
+                        ParValues { head: Some((first, middle)), ranges: &[] },
+                        Some(ParValues { head: Some((after, last)), ranges: &[] }),
+                    ),
+                    None => (ParValues { head: Some((first, last)), ranges: &[] }, None),
+                }
+            }
+            (None, []) => (ParValues { head: None, ranges: &[] }, None),
+        }
+    }
+
+    fn fold_with<F: Folder<T>>(self, folder: F) -> F {
+        folder.consume_iter(Values::new(self.ranges.iter(), self.head))
+    }
+}
+
+impl<'a, T: Discrete + Clone + Debug + Send + Sync> RangeSetRef<'a, T> {
+    /// Returns a parallel iterator over every value inside of this set, see
+    /// [`RangeSet::par_values`](RangeSet::par_values)
+    pub fn par_values(&self) -> ParValues<'a, T> {
+        ParValues { head: None, ranges: self.as_slice() }
+    }
+}
+
+impl<T: Discrete + Clone + Debug + Send + Sync> RangeSet<T> {
+    /// Returns a parallel iterator over every value inside of this set, which is also what `par_iter` returns
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rayon::prelude::*;
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let ids = range_set![r!(1u64..=1000), r!(5000..6000)];
+    ///
+    /// assert_eq!(ids.values().sum::<u64>(), ids.par_values().sum::<u64>());
+    /// assert_eq!(ids.values().collect::<Vec<_>>(), ids.par_iter().collect::<Vec<_>>());
+    /// ```
+    pub fn par_values(&self) -> ParValues<'_, T> {
+        self.as_ref().par_values()
+    }
+}
+
+impl<'a, T: Discrete + Clone + Debug + Send + Sync> IntoParallelIterator for RangeSetRef<'a, T> {
+    type Iter = ParValues<'a, T>;
+    type Item = T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_values()
+    }
+}
+
+impl<'a, T: Discrete + Clone + Debug + Send + Sync> IntoParallelIterator for &'a RangeSet<T> {
+    type Iter = ParValues<'a, T>;
+    type Item = T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::iter::plumbing::UnindexedProducer;
+    use rayon::prelude::*;
+    use crate::{r, range_set, RangeSet};
+
+    #[test]
+    fn par_values() {
+        let set = range_set![r!(..=-126i8), r!(3>..4), r!(10..12), r!(126..)];
+        assert_eq!(set.values().collect::<Vec<_>>(), set.par_values().collect::<Vec<_>>());
+        assert_eq!(256, RangeSet::<u8>::unbound().par_iter().count());
+        assert_eq!(0, RangeSet::<u8>::empty().par_iter().count());
+
+        let range = range_set![r!(0u32..=10)];
+        let (left, right) = range.par_values().split();
+        assert_eq!(vec![0, 1, 2, 3, 4, 5], left.collect::<Vec<_>>());
+        assert_eq!(vec![6, 7, 8, 9, 10], right.unwrap().collect::<Vec<_>>());
+
+        let single = range_set![r!(7u32..=7)];
+        let (single, rest) = single.par_values().split();
+        assert_eq!(vec![7], single.collect::<Vec<_>>());
+        assert!(rest.is_none());
+
+        let large = range_set![r!(0u64..1_000_000)];
+        assert_eq!(large.values().sum::<u64>(), large.par_values().sum::<u64>());
+    }
+}
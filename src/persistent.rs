@@ -0,0 +1,217 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::ops::RangeBounds;
+use std::sync::Arc;
+use crate::{Range, RangeSet, RangeSetRef};
+
+/// The maximum amount of ranges stored in a single chunk
+const CHUNK_SIZE: usize = 32;
+
+/// An immutable set of ranges, where every modification returns a new set
+///
+/// The ranges are stored in shared chunks, a modification only copies the chunks it touches and shares all other
+/// chunks with the original set, which makes keeping many versions of a large set cheap. Cloning is `O(1)`
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, PersistentRangeSet};
+///
+/// let v1 = PersistentRangeSet::from(range_set![r!(0..10), r!(20..30)]);
+/// let v2 = v1.add(r!(5..25));
+/// let v3 = v2.remove(r!(12..14));
+///
+/// assert_eq!(range_set![r!(0..10), r!(20..30)], v1.to_range_set());
+/// assert_eq!(range_set![r!(0..30)], v2.to_range_set());
+/// assert_eq!(range_set![r!(0..12), r!(14..30)], v3.to_range_set());
+/// ```
+#[derive(Debug)]
+pub struct PersistentRangeSet<T: Ord> {
+    chunks: Arc<[Arc<[Range<T>]>]>,
+}
+
+impl<T: Ord> Clone for PersistentRangeSet<T> {
+    fn clone(&self) -> Self {
+        PersistentRangeSet { chunks: self.chunks.clone() }
+    }
+}
+
+impl<T: Ord + Debug> Default for PersistentRangeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone + Debug> From<RangeSet<T>> for PersistentRangeSet<T> {
+    fn from(value: RangeSet<T>) -> Self {
+        PersistentRangeSet { chunks: chunk(value.items.into_iter()).into() }
+    }
+}
+
+impl<T: Ord + Clone + Debug> From<&PersistentRangeSet<T>> for RangeSet<T> {
+    fn from(value: &PersistentRangeSet<T>) -> Self {
+        value.to_range_set()
+    }
+}
+
+impl<T: Ord + Debug> PartialEq for PersistentRangeSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.items().eq(other.items())
+    }
+}
+
+impl<T: Ord + Debug> Eq for PersistentRangeSet<T> {}
+
+impl<T: Ord + Debug> PersistentRangeSet<T> {
+    /// Create a new empty set
+    pub fn new() -> Self {
+        PersistentRangeSet { chunks: Arc::new([]) }
+    }
+
+    /// If this is an empty set
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Returns an iterator with all ranges inside of this set
+    pub fn items(&self) -> impl Iterator<Item=&Range<T>> {
+        self.chunks.iter().flat_map(|chunk| chunk.iter())
+    }
+
+    /// Check if `other` falls within the ranges defined in this set
+    pub fn contains<Q: Ord + ?Sized>(&self, other: &Q) -> bool where T: Borrow<Q> {
+        let index = self.chunks.partition_point(|chunk| chunk[chunk.len() - 1].as_borrowed::<Q>().end_pos() < &other);
+        self.chunks.get(index).is_some_and(|chunk| RangeSetRef::new(chunk).contains(other))
+    }
+
+    /// The range of chunk indices that need to be rebuilt for an operation with given range, `touching` decides
+    /// if chunks only touching the range are included
+    fn affected(&self, range: &Range<T>, touching: bool) -> std::ops::Range<usize> {
+        let start = self.chunks.partition_point(|chunk| {
+            let end = chunk[chunk.len() - 1].end_pos();
+            if touching { end < range.start_pos() } else { end <= range.start_pos() }
+        });
+
+        let end = start + self.chunks[start..].partition_point(|chunk| {
+            let start = chunk[0].start_pos();
+            if touching { start <= range.end_pos() } else { start < range.end_pos() }
+        });
+
+        start..end
+    }
+}
+
+impl<T: Ord + Clone + Debug> PersistentRangeSet<T> {
+    /// Returns a new set with given range added
+    pub fn add(&self, range: impl Into<Range<T>>) -> Self {
+        let range = range.into();
+        let mut affected = self.affected(&range, true);
+
+        // Nothing to merge with, so add the range to a neighbouring chunk
+        if affected.is_empty() && !self.chunks.is_empty() {
+            affected = if affected.start < self.chunks.len() {
+                affected.start..affected.start + 1
+            } else {
+                affected.start - 1..affected.start
+            };
+        }
+
+        let mut set = self.collect(affected.clone());
+        set.add(range);
+
+        self.replace(affected, set)
+    }
+
+    /// Returns a new set with given range removed
+    pub fn remove(&self, range: impl RangeBounds<T>) -> Self {
+        let range = Range::from_range(range);
+        let affected = self.affected(&range, false);
+        if affected.is_empty() {
+            return self.clone();
+        }
+
+        let set = self.collect(affected.clone());
+        let mut removal = RangeSet::empty();
+        removal.add(range);
+
+        self.replace(affected, set.difference(&removal))
+    }
+
+    /// Returns a new set that is the union of this set and given set
+    pub fn union(&self, other: &RangeSet<T>) -> Self {
+        other.items().fold(self.clone(), |set, range| set.add(range.clone()))
+    }
+
+    /// Copy all ranges into a normal set
+    pub fn to_range_set(&self) -> RangeSet<T> {
+        RangeSet { items: self.items().cloned().collect() }
+    }
+
+    fn collect(&self, chunks: std::ops::Range<usize>) -> RangeSet<T> {
+        RangeSet { items: self.chunks[chunks].iter().flat_map(|chunk| chunk.iter().cloned()).collect() }
+    }
+
+    fn replace(&self, affected: std::ops::Range<usize>, set: RangeSet<T>) -> Self {
+        let mut chunks = Vec::with_capacity(self.chunks.len() + 1);
+        chunks.extend(self.chunks[..affected.start].iter().cloned());
+        chunks.extend(chunk(set.items.into_iter()));
+        chunks.extend(self.chunks[affected.end..].iter().cloned());
+
+        PersistentRangeSet { chunks: chunks.into() }
+    }
+}
+
+fn chunk<T>(items: impl ExactSizeIterator<Item=Range<T>>) -> Vec<Arc<[Range<T>]>> where T: Ord {
+    let mut chunks = Vec::with_capacity(items.len().div_ceil(CHUNK_SIZE));
+    let mut items = items.peekable();
+
+    while items.peek().is_some() {
+        chunks.push(items.by_ref().take(CHUNK_SIZE).collect());
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use crate::{r, range_set, PersistentRangeSet, RangeSet};
+
+    #[test]
+    fn persistent() {
+        let empty: PersistentRangeSet<usize> = PersistentRangeSet::new();
+        assert!(empty.is_empty());
+        assert!(!empty.contains(&0));
+        assert_eq!(range_set![r!(4..8)], empty.add(r!(4..8)).to_range_set());
+        assert_eq!(empty, empty.remove(r!(..)));
+
+        let mut expected = RangeSet::empty();
+        let mut set = PersistentRangeSet::new();
+        for i in 0..200 {
+            expected.add(r!((i * 10)..i * 10 + 5));
+            set = set.add(r!((i * 10)..i * 10 + 5));
+        }
+
+        assert_eq!(expected, set.to_range_set());
+        assert!(set.contains(&1004));
+        assert!(!set.contains(&1005));
+
+        // Only the chunk holding the changed range is copied
+        let changed = set.remove(r!(1002..1003));
+        let shared = changed.chunks.iter().filter(|chunk| set.chunks.iter().any(|old| Arc::ptr_eq(old, chunk))).count();
+        assert_eq!(set.chunks.len() - 1, shared);
+        assert!(!changed.contains(&1002));
+        assert!(set.contains(&1002));
+
+        let merged = set.add(r!(3..1500));
+        expected.add(r!(3..1500));
+        assert_eq!(expected, merged.to_range_set());
+
+        let removed = merged.remove(r!(..1000));
+        assert_eq!(expected.difference(&range_set![r!(..1000)]), removed.to_range_set());
+
+        let other = range_set![r!(1..2), r!(3000..)];
+        assert_eq!(expected.union(&other), merged.union(&other).to_range_set());
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt::Debug;
+use crate::{InvalidRangeError, Range};
+
+/// What to do with an inverted range, a range of which the end comes before its start, when it's added to a set
+///
+/// Inverted ranges hold no values, so dropping them, the default, never changes the resulting set
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, InvalidRangePolicy, RangeSet};
+///
+/// let ranges = vec![r!(0..4), r!(8..6)];
+///
+/// assert_eq!(Ok(range_set![r!(0..4)]), RangeSet::from_vec_with_policy(ranges.clone(), InvalidRangePolicy::Drop));
+/// assert_eq!(&r!(8..6), RangeSet::from_vec_with_policy(ranges, InvalidRangePolicy::Error).unwrap_err().range());
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum InvalidRangePolicy {
+    /// Panic on an inverted range
+    Panic,
+    /// Silently drop inverted ranges
+    #[default]
+    Drop,
+    /// Return an error holding the inverted range
+    Error,
+}
+
+impl InvalidRangePolicy {
+    /// Apply this policy to given range, returns the range if it's valid, `None` if it should be dropped, or an
+    /// error if it should be rejected
+    pub(crate) fn check<T: Ord + Debug>(self, range: Range<T>) -> Result<Option<Range<T>>, InvalidRangeError<T>> {
+        if !range.is_inverted() {
+            return Ok(Some(range));
+        }
+
+        match self {
+            InvalidRangePolicy::Panic => panic!("range {:?} ends before it starts", range),
+            InvalidRangePolicy::Drop => Ok(None),
+            InvalidRangePolicy::Error => Err(InvalidRangeError::new(range)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, InvalidRangePolicy, RangeSet, RangeSetBuilder};
+
+    #[test]
+    fn policy() {
+        let mut set = range_set![r!(0..4)];
+        set.add(r!(10..5));
+        assert_eq!(range_set![r!(0..4)], set);
+        assert_eq!(Ok(()), set.add_with_policy(r!(3..=3), InvalidRangePolicy::Error));
+        assert_eq!(r!(5..=4), set.add_with_policy(r!(5..=4), InvalidRangePolicy::Error).unwrap_err().into_range());
+        assert_eq!(range_set![r!(0..4)], set);
+
+        assert_eq!(range_set![r!(1..2)], RangeSet::from(vec![r!(4..3), r!(1..2), r!(9..=8)]));
+
+        let mut builder = RangeSetBuilder::new().with_policy(InvalidRangePolicy::Error);
+        assert_eq!(InvalidRangePolicy::Error, builder.policy());
+        builder.push(r!(0..2));
+        builder.push(r!(3..1));
+        builder.push(r!(4..6));
+        assert_eq!(r!(3..1), builder.try_finish().unwrap_err().into_range());
+    }
+
+    #[test]
+    #[should_panic]
+    fn policy_panic() {
+        let mut builder = RangeSetBuilder::new().with_policy(InvalidRangePolicy::Panic);
+        builder.push(r!(3..1));
+    }
+}
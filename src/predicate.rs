@@ -0,0 +1,140 @@
+use std::fmt::Debug;
+use std::ops::Not;
+use crate::{Bound, Range, RangeSet};
+
+/// A simple comparison predicate, which can be turned into the set of values matching it with
+/// [`RangeSet::from_predicate`](RangeSet::from_predicate)
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, Cmp, RangeSet};
+///
+/// // age >= 18 AND age NOT IN (21, 30) OR age < 5
+/// let predicate = Cmp::Ge(18).and(Cmp::NotIn(vec![21, 30])).or(Cmp::Lt(5));
+///
+/// assert_eq!(range_set![r!(..5), r!(18..21), r!(21>..30), r!(30>..)], RangeSet::from_predicate(predicate));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Cmp<T> {
+    /// `x == value`
+    Eq(T),
+    /// `x != value`
+    Ne(T),
+    /// `x < value`
+    Lt(T),
+    /// `x <= value`
+    Le(T),
+    /// `x > value`
+    Gt(T),
+    /// `x >= value`
+    Ge(T),
+    /// `low <= x <= high`, alike SQL's `BETWEEN`
+    Between(T, T),
+    /// `x` equals any of the values
+    In(Vec<T>),
+    /// `x` equals none of the values
+    NotIn(Vec<T>),
+    /// Every predicate matches
+    And(Vec<Cmp<T>>),
+    /// Any predicate matches
+    Or(Vec<Cmp<T>>),
+    /// The predicate doesn't match
+    Not(Box<Cmp<T>>),
+}
+
+impl<T> Cmp<T> {
+    /// Combine this predicate with given predicate, both have to match
+    pub fn and(self, other: Cmp<T>) -> Cmp<T> {
+        match self {
+            Cmp::And(mut items) => {
+                items.push(other);
+                Cmp::And(items)
+            }
+            this => Cmp::And(vec![this, other]),
+        }
+    }
+
+    /// Combine this predicate with given predicate, either has to match
+    pub fn or(self, other: Cmp<T>) -> Cmp<T> {
+        match self {
+            Cmp::Or(mut items) => {
+                items.push(other);
+                Cmp::Or(items)
+            }
+            this => Cmp::Or(vec![this, other]),
+        }
+    }
+}
+
+impl<T> Not for Cmp<T> {
+    type Output = Cmp<T>;
+
+    /// Negate this predicate
+    fn not(self) -> Cmp<T> {
+        match self {
+            Cmp::Not(inner) => *inner,
+            this => Cmp::Not(Box::new(this)),
+        }
+    }
+}
+
+impl<T: Ord + Clone + Debug> RangeSet<T> {
+    /// Create the set of values matching given predicate
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, Cmp, RangeSet};
+    ///
+    /// assert_eq!(range_set![r!(5..)], RangeSet::from_predicate(Cmp::Ge(5)));
+    /// assert_eq!(range_set![r!(1..=3), r!(7..=7)], RangeSet::from_predicate(Cmp::Between(1, 3).or(Cmp::Eq(7))));
+    /// ```
+    pub fn from_predicate(predicate: Cmp<T>) -> RangeSet<T> {
+        match predicate {
+            Cmp::Eq(value) => RangeSet::from([Range::new(Bound::Included(value.clone()), Bound::Included(value))]),
+            Cmp::Ne(value) => RangeSet::from([
+                Range::new(Bound::Unbounded, Bound::Excluded(value.clone())),
+                Range::new(Bound::Excluded(value), Bound::Unbounded),
+            ]),
+            Cmp::Lt(value) => RangeSet::from([Range::new(Bound::Unbounded, Bound::Excluded(value))]),
+            Cmp::Le(value) => RangeSet::from([Range::new(Bound::Unbounded, Bound::Included(value))]),
+            Cmp::Gt(value) => RangeSet::from([Range::new(Bound::Excluded(value), Bound::Unbounded)]),
+            Cmp::Ge(value) => RangeSet::from([Range::new(Bound::Included(value), Bound::Unbounded)]),
+            Cmp::Between(low, high) if low > high => RangeSet::empty(),
+            Cmp::Between(low, high) => RangeSet::from([Range::new(Bound::Included(low), Bound::Included(high))]),
+            Cmp::In(values) => RangeSet::from(values.into_iter()
+                .map(|value| Range::new(Bound::Included(value.clone()), Bound::Included(value)))
+                .collect::<Vec<_>>()),
+            Cmp::NotIn(values) => RangeSet::from_predicate(Cmp::In(values)).invert(),
+            Cmp::And(predicates) => predicates.into_iter()
+                .fold(RangeSet::unbound(), |set, predicate| set.intersection(&RangeSet::from_predicate(predicate))),
+            Cmp::Or(predicates) => predicates.into_iter()
+                .fold(RangeSet::empty(), |set, predicate| set.union(&RangeSet::from_predicate(predicate))),
+            Cmp::Not(predicate) => RangeSet::from_predicate(*predicate).invert(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, Cmp, RangeSet};
+
+    #[test]
+    fn from_predicate() {
+        assert_eq!(range_set![r!(4..=4)], RangeSet::from_predicate(Cmp::Eq(4)));
+        assert_eq!(range_set![r!(..4), r!(4>..)], RangeSet::from_predicate(Cmp::Ne(4)));
+        assert_eq!(range_set![r!(..=4)], RangeSet::from_predicate(Cmp::Le(4)));
+        assert_eq!(range_set![r!(4>..)], RangeSet::from_predicate(Cmp::Gt(4)));
+        assert_eq!(RangeSet::empty(), RangeSet::from_predicate(Cmp::Between(5, 4)));
+        assert_eq!(range_set![r!(1..=1), r!(3..=3)], RangeSet::from_predicate(Cmp::In(vec![3, 1, 3])));
+        assert_eq!(RangeSet::unbound(), RangeSet::from_predicate(Cmp::<u32>::And(vec![])));
+        assert_eq!(RangeSet::empty(), RangeSet::from_predicate(Cmp::<u32>::Or(vec![])));
+
+        let predicate = Cmp::Gt(10).and(Cmp::Lt(20)).and(Cmp::Ne(15));
+        assert_eq!(Cmp::And(vec![Cmp::Gt(10), Cmp::Lt(20), Cmp::Ne(15)]), predicate);
+        assert_eq!(range_set![r!(10>..15), r!(15>..20)], RangeSet::from_predicate(predicate.clone()));
+        assert_eq!(range_set![r!(..=10), r!(15..=15), r!(20..)], RangeSet::from_predicate(!predicate.clone()));
+        assert_eq!(predicate.clone(), !!predicate);
+    }
+}
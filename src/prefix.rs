@@ -0,0 +1,147 @@
+use std::fmt::Debug;
+use crate::{Bound, Range, RangeSet};
+
+/// A key type where every key starting with a given prefix can be described as a single range
+///
+/// Implemented for `str` (ordered as [`String`](String)) and byte slices (ordered as `Vec<u8>`)
+pub trait PrefixKey {
+    /// The owned key type that ranges are built from
+    type Owned: Ord;
+
+    /// Returns this prefix as owned key
+    fn to_key(&self) -> Self::Owned;
+
+    /// Returns the smallest key that is larger than every key starting with this prefix, or `None` if every
+    /// larger key starts with this prefix
+    fn prefix_end(&self) -> Option<Self::Owned>;
+}
+
+impl PrefixKey for [u8] {
+    type Owned = Vec<u8>;
+
+    fn to_key(&self) -> Self::Owned {
+        self.to_vec()
+    }
+
+    fn prefix_end(&self) -> Option<Self::Owned> {
+        // Trailing 0xff bytes can't be incremented, so the byte before them is
+        let index = self.iter().rposition(|byte| *byte != u8::MAX)?;
+        let mut end = self[..=index].to_vec();
+        end[index] += 1;
+
+        Some(end)
+    }
+}
+
+impl<const N: usize> PrefixKey for [u8; N] {
+    type Owned = Vec<u8>;
+
+    fn to_key(&self) -> Self::Owned {
+        self.to_vec()
+    }
+
+    fn prefix_end(&self) -> Option<Self::Owned> {
+        self[..].prefix_end()
+    }
+}
+
+impl PrefixKey for str {
+    type Owned = String;
+
+    fn to_key(&self) -> Self::Owned {
+        self.to_string()
+    }
+
+    fn prefix_end(&self) -> Option<Self::Owned> {
+        let mut end = self.to_string();
+
+        while let Some(last) = end.pop() {
+            // Skip over the surrogate range, which aren't valid chars
+            let next = match last {
+                '\u{D7FF}' => Some('\u{E000}'),
+                _ => char::from_u32(last as u32 + 1),
+            };
+
+            if let Some(next) = next {
+                end.push(next);
+                return Some(end);
+            }
+        }
+
+        None
+    }
+}
+
+/// Create the range that covers every key starting with given prefix
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{prefix_range, r};
+///
+/// assert_eq!(r!(("user:".to_string()).."user;".to_string()), prefix_range("user:"));
+/// assert_eq!(r!((vec![1, 0xff])..vec![2]), prefix_range(&[1u8, 0xff]));
+/// assert_eq!(r!((vec![0xff])..), prefix_range(&[0xffu8]));
+/// ```
+pub fn prefix_range<P: PrefixKey + ?Sized>(prefix: &P) -> Range<P::Owned> {
+    Range::new(Bound::Included(prefix.to_key()), prefix.prefix_end().map_or(Bound::Unbounded, Bound::Excluded))
+}
+
+impl<T: Ord + Debug> RangeSet<T> {
+    /// Returns `true` if every key starting with given prefix falls within this set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{prefix_range, r, RangeSet};
+    ///
+    /// let set = RangeSet::from([prefix_range("user:"), r!(("v".to_string())..)]);
+    ///
+    /// assert!(set.contains_prefix("user:42:"));
+    /// assert!(set.contains_prefix("w"));
+    /// assert!(!set.contains_prefix("user"));
+    /// ```
+    pub fn contains_prefix<P: PrefixKey<Owned=T> + ?Sized>(&self, prefix: &P) -> bool {
+        let range = prefix_range(prefix);
+        self.as_ref().covers(range.start(), range.end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{prefix_range, r, PrefixKey, RangeSet};
+
+    #[test]
+    fn prefix_end() {
+        assert_eq!(Some(vec![1, 3]), [1u8, 2].prefix_end());
+        assert_eq!(Some(vec![2]), [1u8, 0xff, 0xff].prefix_end());
+        assert_eq!(None, [0xffu8, 0xff].prefix_end());
+        assert_eq!(None, [0u8; 0].prefix_end());
+
+        assert_eq!(Some("ab".to_string()), "aa".prefix_end());
+        assert_eq!(Some("b".to_string()), "a\u{10FFFF}".prefix_end());
+        assert_eq!(Some("\u{E000}".to_string()), "\u{D7FF}".prefix_end());
+        assert_eq!(None, "".prefix_end());
+    }
+
+    #[test]
+    fn prefix() {
+        let range = prefix_range("ab");
+        assert!(range.contains("ab"));
+        assert!(range.contains("ab\u{10FFFF}"));
+        assert!(!range.contains("ac"));
+        assert!(!range.contains("a"));
+
+        let set = RangeSet::from([prefix_range(b"ab"), r!((vec![0xff])..)]);
+        assert!(set.contains_prefix(b"abc"));
+        assert!(set.contains_prefix(&[0xff, 0xff][..]));
+        assert!(!set.contains_prefix(b"a"));
+        assert!(!set.contains_prefix(b"b"));
+
+        let mut split = RangeSet::from([prefix_range("ab")]);
+        split.split_boundary_at(&"abc".to_string());
+        assert!(split.contains_prefix("ab"));
+        assert!(split.contains_prefix("abc"));
+        assert!(!split.contains_prefix("a"));
+    }
+}
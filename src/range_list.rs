@@ -0,0 +1,181 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::slice::Iter;
+use std::str::FromStr;
+use crate::{ParseError, Range, RangeSet};
+use crate::display::{display_value, fmt_ranges, parse_ranges};
+
+/// A list of ranges kept exactly as they were added, overlapping ranges and duplicates included
+///
+/// Unlike a [`RangeSet`](RangeSet) nothing is merged or sorted, which is needed when the original segments matter,
+/// e.g. for the ranges of a HTTP `Range` header. It's written and parsed in the same notation as a set, and can be
+/// turned into one once only the covered values matter
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, RangeList};
+///
+/// let list: RangeList<u64> = "{500..1000, 0..500, 0..500}".parse().unwrap();
+///
+/// assert_eq!(3, list.len());
+/// assert_eq!("{500..1000, 0..500, 0..500}", list.to_string());
+/// assert_eq!(range_set![r!(0..1000)], list.to_range_set());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RangeList<T: Ord> {
+    items: Vec<Range<T>>,
+}
+
+impl<T: Ord> Default for RangeList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> RangeList<T> {
+    /// Create a new empty list
+    pub fn new() -> Self {
+        RangeList { items: vec![] }
+    }
+
+    /// Create a new list with room for given amount of ranges
+    pub fn with_capacity(capacity: usize) -> Self {
+        RangeList { items: Vec::with_capacity(capacity) }
+    }
+
+    /// Add a range to the end of this list
+    #[inline]
+    pub fn push(&mut self, range: impl Into<Range<T>>) {
+        self.items.push(range.into());
+    }
+
+    /// If this list holds no ranges
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The amount of ranges in this list
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns an iterator over the ranges in this list, in the order they were added
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, Range<T>> {
+        self.items.iter()
+    }
+
+    /// Returns the ranges in this list as slice
+    #[inline]
+    pub fn as_slice(&self) -> &[Range<T>] {
+        &self.items
+    }
+
+    /// Returns the ranges in this list
+    #[inline]
+    pub fn into_vec(self) -> Vec<Range<T>> {
+        self.items
+    }
+
+    /// Returns `true` if given item falls within any range of this list
+    pub fn contains(&self, item: &T) -> bool {
+        self.items.iter().any(|range| range.contains(item))
+    }
+}
+
+impl<T: Ord + Debug> RangeList<T> {
+    /// Merge the ranges of this list into a set
+    pub fn into_range_set(self) -> RangeSet<T> {
+        RangeSet::from(self.items)
+    }
+}
+
+impl<T: Ord + Clone + Debug> RangeList<T> {
+    /// Copy the ranges of this list into a set
+    pub fn to_range_set(&self) -> RangeSet<T> {
+        RangeSet::from(self.items.clone())
+    }
+}
+
+impl<T: Ord> From<Vec<Range<T>>> for RangeList<T> {
+    fn from(items: Vec<Range<T>>) -> Self {
+        RangeList { items }
+    }
+}
+
+impl<T: Ord> From<RangeSet<T>> for RangeList<T> {
+    fn from(set: RangeSet<T>) -> Self {
+        set.items.into_iter().collect()
+    }
+}
+
+impl<T: Ord> FromIterator<Range<T>> for RangeList<T> {
+    fn from_iter<I: IntoIterator<Item=Range<T>>>(iter: I) -> Self {
+        RangeList { items: iter.into_iter().collect() }
+    }
+}
+
+impl<T: Ord> Extend<Range<T>> for RangeList<T> {
+    fn extend<I: IntoIterator<Item=Range<T>>>(&mut self, iter: I) {
+        self.items.extend(iter)
+    }
+}
+
+impl<T: Ord> IntoIterator for RangeList<T> {
+    type Item = Range<T>;
+    type IntoIter = std::vec::IntoIter<Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for &'a RangeList<T> {
+    type Item = &'a Range<T>;
+    type IntoIter = Iter<'a, Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl<T: Ord + Display> Display for RangeList<T> {
+    /// Formats the list as its ranges between braces, like a [`RangeSet`](RangeSet)
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_ranges(&self.items, f, &display_value)
+    }
+}
+
+impl<T: Ord + FromStr> FromStr for RangeList<T> where T::Err: Display {
+    type Err = ParseError;
+
+    /// Parses a list in the notation it's [displayed](Display) in, keeping the ranges as given
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_ranges(input).map(RangeList::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeList};
+
+    #[test]
+    fn range_list() {
+        let mut list = RangeList::new();
+        list.push(r!(10..20));
+        list.extend([r!(..5), r!(10..20), r!(8..6)]);
+
+        assert_eq!(4, list.len());
+        assert!(list.contains(&15) && !list.contains(&7));
+        assert_eq!("{10..20, ..5, 10..20, 8..6}", list.to_string());
+        assert_eq!(Ok(list.clone()), list.to_string().parse());
+        assert_eq!(range_set![r!(..5), r!(10..20)], list.clone().into_range_set());
+        assert_eq!(vec![r!(10..20), r!(..5), r!(10..20), r!(8..6)], list.into_vec());
+
+        assert_eq!(RangeList::from(vec![r!(0..4), r!(6..)]), RangeList::from(range_set![r!(6..), r!(0..4)]));
+        assert_eq!(Ok(RangeList::<u8>::new()), "{}".parse());
+        assert!("0..4".parse::<RangeList<u8>>().is_err());
+    }
+}
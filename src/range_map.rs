@@ -0,0 +1,305 @@
+use std::borrow::Borrow;
+use std::fmt::{Debug, Formatter};
+use std::mem;
+use crate::{holds_values, Bound, BoundExt, PositionalBound, Range, RangeSet, RangeSetBuilder};
+
+/// A map from non-overlapping ranges to values
+///
+/// Created with [`coalescing`](RangeMap::coalescing) the map merges touching or overlapping entries with equal
+/// values, keeping the map as small as possible
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, RangeMap};
+///
+/// let mut map = RangeMap::new();
+/// map.insert(r!(0..10), "a");
+/// map.insert(r!(5..15), "b");
+///
+/// assert_eq!(Some(&"a"), map.get(&4));
+/// assert_eq!(Some(&"b"), map.get(&5));
+/// assert_eq!(None, map.get(&15));
+/// ```
+#[derive(Clone)]
+pub struct RangeMap<T: Ord, V> {
+    items: Vec<(Range<T>, V)>,
+    coalesce: Option<fn(&V, &V) -> bool>,
+}
+
+impl<T: Ord + Debug, V: Debug> Debug for RangeMap<T, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RangeMap")
+            .field("items", &self.items)
+            .field("coalescing", &self.coalesce.is_some())
+            .finish()
+    }
+}
+
+impl<T: Ord, V: PartialEq> PartialEq for RangeMap<T, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items
+    }
+}
+
+impl<T: Ord, V: Eq> Eq for RangeMap<T, V> {}
+
+impl<T: Ord, V> Default for RangeMap<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord, V: PartialEq> RangeMap<T, V> {
+    /// Create a new empty map that merges touching or overlapping entries with equal values
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, RangeMap};
+    ///
+    /// let mut map = RangeMap::coalescing();
+    /// map.insert(r!(0..10), "rw");
+    /// map.insert(r!(10..20), "rw");
+    /// map.insert(r!(4..6), "rw");
+    ///
+    /// assert_eq!(vec![(&r!(0..20), &"rw")], map.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn coalescing() -> Self {
+        RangeMap { items: vec![], coalesce: Some(V::eq) }
+    }
+}
+
+impl<T: Ord, V> RangeMap<T, V> {
+    /// Create a new empty map
+    pub fn new() -> Self {
+        RangeMap { items: vec![], coalesce: None }
+    }
+
+    /// If this map has no entries
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The amount of entries in this map
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if this map merges touching or overlapping entries with equal values
+    #[inline]
+    pub fn is_coalescing(&self) -> bool {
+        self.coalesce.is_some()
+    }
+
+    /// Returns an iterator over all entries in this map, in order
+    pub fn iter(&self) -> impl Iterator<Item=(&Range<T>, &V)> {
+        self.items.iter().map(|(range, value)| (range, value))
+    }
+
+    /// Returns the value of the entry `key` falls in, if any
+    pub fn get<Q: Ord + ?Sized>(&self, key: &Q) -> Option<&V> where T: Borrow<Q> {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    /// Returns the entry `key` falls in, if any
+    pub fn get_key_value<Q: Ord + ?Sized>(&self, key: &Q) -> Option<(&Range<T>, &V)> where T: Borrow<Q> {
+        let index = self.items.partition_point(|(range, _)| range.as_borrowed::<Q>().end_pos() < &key);
+        self.items.get(index)
+            .filter(|(range, _)| range.as_borrowed::<Q>().start_pos() < &key)
+            .map(|(range, value)| (range, value))
+    }
+}
+
+impl<T: Ord + Clone + Debug, V: Clone> RangeMap<T, V> {
+    /// Set the value of every part of given range, overwriting existing values
+    pub fn insert(&mut self, range: impl Into<Range<T>>, value: V) {
+        let range = range.into();
+        if self.replace(&range, |_| None) {
+            let index = self.items.partition_point(|(item, _)| item.start_pos() < range.start_pos());
+            self.items.insert(index, (range, value));
+            self.coalesce();
+        }
+    }
+
+    /// Remove every part of given range from this map
+    pub fn remove(&mut self, range: impl Into<Range<T>>) {
+        self.update(range, |_| None)
+    }
+
+    /// Replace the value of every part of given range with the result of given function, which receives the current
+    /// value, or `None` for parts without a value. Returning `None` removes the part from the map
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, RangeMap};
+    ///
+    /// let mut map = RangeMap::new();
+    /// map.insert(r!(0..10), 1);
+    /// map.update(r!(5..15), |value| Some(value.unwrap_or(&0) + 1));
+    ///
+    /// assert_eq!(vec![(&r!(0..5), &1), (&r!(5..10), &2), (&r!(10..15), &1)], map.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn update(&mut self, range: impl Into<Range<T>>, f: impl FnMut(Option<&V>) -> Option<V>) {
+        let range = range.into();
+        if self.replace(&range, f) {
+            self.coalesce();
+        }
+    }
+
+    /// Replace every part of given range like [`update`](RangeMap::update) without coalescing, returns `false` if the
+    /// range is empty
+    fn replace(&mut self, range: &Range<T>, mut f: impl FnMut(Option<&V>) -> Option<V>) -> bool {
+        if !holds_values(range.start(), range.end()) {
+            return false;
+        }
+
+        let start_pos = PositionalBound::Start(range.start());
+        let end_pos = PositionalBound::End(range.end());
+
+        let start_index = self.items.partition_point(|(item, _)| item.end_pos() <= start_pos);
+        let end_index = start_index + self.items[start_index..].partition_point(|(item, _)| item.start_pos() < end_pos);
+
+        let mut updated = Vec::with_capacity(end_index - start_index + 2);
+        let mut push = |range: Range<T>, value: Option<V>| {
+            if let Some(value) = value {
+                updated.push((range, value));
+            }
+        };
+
+        // The start of the part of `range` that hasn't been handled yet, `None` once all of it is handled
+        let mut cursor = Some(range.start.clone());
+
+        for (item, value) in self.items.drain(start_index..end_index) {
+            let Some(start) = cursor.take() else {
+                break;
+            };
+
+            if item.start_pos() < PositionalBound::Start(start.as_ref()) {
+                push(Range::new(item.start.clone(), start.clone().invert()), Some(value.clone()));
+            } else if item.start != Bound::Unbounded {
+                let gap = Range::new(start, item.start.clone().invert());
+                if gap.start_pos() < gap.end_pos() {
+                    push(gap, f(None));
+                }
+            }
+
+            let middle_start = if item.start_pos() < start_pos { range.start.clone() } else { item.start.clone() };
+            let middle_end = if item.end_pos() > end_pos { range.end.clone() } else { item.end.clone() };
+
+            if item.end_pos() > end_pos {
+                push(Range::new(middle_start, middle_end), f(Some(&value)));
+                push(Range::new(range.end.clone().invert(), item.end), Some(value));
+            } else {
+                cursor = (middle_end != Bound::Unbounded).then(|| middle_end.clone().invert());
+                push(Range::new(middle_start, middle_end), f(Some(&value)));
+            }
+        }
+
+        if let Some(start) = cursor {
+            let gap = Range::new(start, range.end.clone());
+            if gap.start_pos() < gap.end_pos() {
+                push(gap, f(None));
+            }
+        }
+
+        self.items.splice(start_index..start_index, updated);
+        true
+    }
+
+    /// Merge touching or overlapping entries with equal values, if this map is coalescing
+    fn coalesce(&mut self) {
+        let Some(eq) = self.coalesce else {
+            return;
+        };
+
+        let mut items: Vec<(Range<T>, V)> = Vec::with_capacity(self.items.len());
+        for (range, value) in mem::take(&mut self.items) {
+            match items.last_mut() {
+                Some((last, last_value)) if last.end_pos() >= range.start_pos() && eq(last_value, &value) => {
+                    if range.end_pos() > last.end_pos() {
+                        last.end = range.end;
+                    }
+                }
+                _ => items.push((range, value)),
+            }
+        }
+
+        self.items = items;
+    }
+
+    /// Returns the set of ranges that have a value
+    pub fn ranges(&self) -> RangeSet<T> {
+        let mut builder = RangeSetBuilder::with_capacity(self.items.len());
+        for (range, _) in &self.items {
+            builder.push(range.clone());
+        }
+
+        builder.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeMap};
+
+    #[test]
+    fn range_map() {
+        let mut map = RangeMap::new();
+        assert!(map.is_empty());
+        assert!(!map.is_coalescing());
+
+        map.insert(r!(0..10), 'a');
+        map.insert(r!(20..), 'b');
+        map.insert(r!(8..=22), 'c');
+        assert_eq!(vec![(&r!(0..8), &'a'), (&r!(8..=22), &'c'), (&r!(22>..), &'b')], map.iter().collect::<Vec<_>>());
+        assert_eq!(Some(&'a'), map.get(&7));
+        assert_eq!(Some(&'c'), map.get(&22));
+        assert_eq!(Some((&r!(22>..), &'b')), map.get_key_value(&23));
+
+        map.remove(r!(5..25));
+        assert_eq!(vec![(&r!(0..5), &'a'), (&r!(25..), &'b')], map.iter().collect::<Vec<_>>());
+        assert_eq!(range_set![r!(0..5), r!(25..)], map.ranges());
+
+        // Only the gap is filled
+        map.update(r!(..), |value| value.copied().or(Some('z')));
+        assert_eq!(vec![(&r!(..0), &'z'), (&r!(0..5), &'a'), (&r!(5..25), &'z'), (&r!(25..), &'b')], map.iter().collect::<Vec<_>>());
+
+        map.update(r!(3..), |_| None);
+        assert_eq!(vec![(&r!(..0), &'z'), (&r!(0..3), &'a')], map.iter().collect::<Vec<_>>());
+
+        // Empty ranges are never stored
+        let mut map = RangeMap::coalescing();
+        map.insert(r!(0..0), 1);
+        map.insert(r!(0..=3), 2);
+        map.insert(r!(3..3), 3);
+        map.insert(r!(3>..=3), 3);
+        map.insert(r!(3>..5), 2);
+        assert_eq!(vec![(&r!(0..5), &2)], map.iter().collect::<Vec<_>>());
+        assert_eq!(Some(&2), map.get(&0));
+    }
+
+    #[test]
+    fn coalescing() {
+        let mut map = RangeMap::coalescing();
+        map.insert(r!(0..10), 1);
+        map.insert(r!(20..30), 1);
+        map.insert(r!(10..20), 2);
+        assert_eq!(3, map.len());
+
+        map.insert(r!(10..20), 1);
+        assert_eq!(vec![(&r!(0..30), &1)], map.iter().collect::<Vec<_>>());
+
+        map.insert(r!(5..6), 2);
+        map.update(r!(..), |value| value.map(|_| 1));
+        assert_eq!(vec![(&r!(0..30), &1)], map.iter().collect::<Vec<_>>());
+
+        let mut plain = RangeMap::new();
+        plain.insert(r!(0..10), 1);
+        plain.insert(r!(10..20), 1);
+        assert_eq!(2, plain.len());
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use rangemap::StepLite;
+use crate::{Bound, Discrete, Range, RangeSet, UnrepresentableError};
+
+impl<T: Ord + Clone + Debug> From<&rangemap::RangeSet<T>> for RangeSet<T> {
+    /// Convert a half-open `rangemap` set into a set, which is lossless
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, RangeSet};
+    ///
+    /// let set = rangemap::RangeSet::from([0u32..4, 4..6, 8..10]);
+    ///
+    /// assert_eq!(range_set![r!(0..6), r!(8..10)], RangeSet::from(&set));
+    /// ```
+    fn from(value: &rangemap::RangeSet<T>) -> Self {
+        value.iter().cloned().map(Range::from).collect::<Vec<_>>().into()
+    }
+}
+
+impl<T: Ord + Clone + Debug> From<rangemap::RangeSet<T>> for RangeSet<T> {
+    fn from(value: rangemap::RangeSet<T>) -> Self {
+        value.into_iter().map(Range::from).collect::<Vec<_>>().into()
+    }
+}
+
+impl<T: Ord + Clone + StepLite + Debug> From<&rangemap::RangeInclusiveSet<T>> for RangeSet<T> {
+    /// Convert an inclusive `rangemap` set into a set, which is lossless
+    fn from(value: &rangemap::RangeInclusiveSet<T>) -> Self {
+        value.iter().cloned().map(Range::from).collect::<Vec<_>>().into()
+    }
+}
+
+impl<T: Ord + Clone + StepLite + Debug> From<rangemap::RangeInclusiveSet<T>> for RangeSet<T> {
+    fn from(value: rangemap::RangeInclusiveSet<T>) -> Self {
+        value.into_iter().map(Range::from).collect::<Vec<_>>().into()
+    }
+}
+
+impl<T: Discrete + Clone + Debug> TryFrom<&RangeSet<T>> for rangemap::RangeSet<T> {
+    type Error = UnrepresentableError<T>;
+
+    /// Convert a set into a half-open `rangemap` set, excluded starts and included ends are moved to the
+    /// neighbouring value so the same values are covered
+    ///
+    /// Returns an error holding the first range that can't be represented, which is a range that ends unbounded or
+    /// includes the highest value of `T`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(0u8>..=4), r!(8..10)];
+    ///
+    /// assert_eq!(Ok(rangemap::RangeSet::from([1..5, 8..10])), rangemap::RangeSet::try_from(&set));
+    /// assert_eq!(&r!(8u8..), rangemap::RangeSet::try_from(&range_set![r!(8u8..)]).unwrap_err().range());
+    /// ```
+    fn try_from(value: &RangeSet<T>) -> Result<Self, Self::Error> {
+        let mut set = rangemap::RangeSet::new();
+        for range in value.items.iter().filter(|range| !range.is_empty()) {
+            let unrepresentable = || UnrepresentableError::new(range.clone());
+            let end = match &range.end {
+                Bound::Included(end) => end.successor().ok_or_else(unrepresentable)?,
+                Bound::Excluded(end) => end.clone(),
+                Bound::Unbounded => return Err(unrepresentable()),
+            };
+
+            // Ranges like `4>..5` hold no values, which rangemap doesn't accept
+            let start = range.first_value().ok_or_else(unrepresentable)?;
+            if start < end {
+                set.insert(start..end);
+            }
+        }
+
+        Ok(set)
+    }
+}
+
+impl<T: Discrete + Clone + StepLite + Debug> TryFrom<&RangeSet<T>> for rangemap::RangeInclusiveSet<T> {
+    type Error = UnrepresentableError<T>;
+
+    /// Convert a set into an inclusive `rangemap` set, excluded and unbounded sides are moved to the neighbouring
+    /// value or the lowest or highest value of `T` so the same values are covered
+    ///
+    /// Returns an error holding the first range that can't be represented, which is a range that is unbounded on a
+    /// type without a lowest or highest value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(..4u8), r!(8>..)];
+    ///
+    /// assert_eq!(Ok(rangemap::RangeInclusiveSet::from([0..=3, 9..=255])), rangemap::RangeInclusiveSet::try_from(&set));
+    /// ```
+    fn try_from(value: &RangeSet<T>) -> Result<Self, Self::Error> {
+        let mut set = rangemap::RangeInclusiveSet::new();
+        for range in value.items.iter().filter(|range| !range.is_empty()) {
+            let unrepresentable = || UnrepresentableError::new(range.clone());
+            let (start, end) = (range.first_value().ok_or_else(unrepresentable)?, range.last_value().ok_or_else(unrepresentable)?);
+            if start <= end {
+                set.insert(RangeInclusive::new(start, end));
+            }
+        }
+
+        Ok(set)
+    }
+}
+
+impl<T: Discrete + Clone + Debug> RangeSet<T> {
+    /// Convert this set into a half-open `rangemap` set with its `TryFrom` implementation, returning `None` if it
+    /// can't be represented
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// assert_eq!(Some(rangemap::RangeSet::from([1..5])), range_set![r!(0u8>..=4)].to_rangemap());
+    /// assert_eq!(None, range_set![r!(8u8..)].to_rangemap());
+    /// ```
+    #[inline]
+    pub fn to_rangemap(&self) -> Option<rangemap::RangeSet<T>> {
+        rangemap::RangeSet::try_from(self).ok()
+    }
+
+    /// Convert this set into an inclusive `rangemap` set with its `TryFrom` implementation, returning `None` if it
+    /// can't be represented
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// assert_eq!(Some(rangemap::RangeInclusiveSet::from([0..=3])), range_set![r!(..4u8)].to_rangemap_inclusive());
+    /// ```
+    #[inline]
+    pub fn to_rangemap_inclusive(&self) -> Option<rangemap::RangeInclusiveSet<T>> where T: StepLite {
+        rangemap::RangeInclusiveSet::try_from(self).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeSet};
+
+    #[test]
+    fn rangemap() {
+        let set: RangeSet<i32> = range_set![r!(-5..=-3), r!(0>..2), r!(10..20)];
+        let half_open = set.to_rangemap().unwrap();
+        assert_eq!(vec![-5..-2, 1..2, 10..20], half_open.iter().cloned().collect::<Vec<_>>());
+        assert_eq!(range_set![r!(-5..-2), r!(1..2), r!(10..20)], RangeSet::from(half_open));
+
+        let inclusive = set.to_rangemap_inclusive().unwrap();
+        assert_eq!(vec![-5..=-3, 1..=1, 10..=19], inclusive.iter().cloned().collect::<Vec<_>>());
+        assert_eq!(range_set![r!(-5..=-3), r!(1..=1), r!(10..=19)], RangeSet::from(inclusive));
+
+        assert_eq!(None, range_set![r!(0u8..=255)].to_rangemap());
+        assert_eq!(None, RangeSet::<u128>::unbound().to_rangemap());
+        assert_eq!(Some(rangemap::RangeInclusiveSet::from([0..=u128::MAX])), RangeSet::<u128>::unbound().to_rangemap_inclusive());
+        assert_eq!(Some(rangemap::RangeSet::new()), range_set![r!(4>..5)].to_rangemap());
+
+        let error = rangemap::RangeSet::try_from(&range_set![r!(0u8..4), r!(10..=255)]).unwrap_err();
+        assert_eq!(&r!(10..=255), error.range());
+        assert_eq!(Ok(rangemap::RangeSet::from([0..4, 10..20])), rangemap::RangeSet::try_from(&range_set![r!(0..4), r!(10..20)]));
+    }
+}
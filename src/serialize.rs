@@ -0,0 +1,198 @@
+//! Serde support for ranges and sets
+//!
+//! Ranges are serialized as a tuple of their start and end bound and sets as a sequence of ranges. The
+//! [`string`](string) adapter serializes sets in their [displayed](std::fmt::Display) notation instead
+use std::fmt::Debug;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use crate::{Bound, Range, RangeSet};
+
+impl<T: Serialize> Serialize for Range<T> {
+    /// Serializes the range as a tuple of its start and end bound
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (&self.start, &self.end).serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Range<T> {
+    /// Deserializes a range from a tuple of its start and end bound
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (start, end) = <(Bound<T>, Bound<T>)>::deserialize(deserializer)?;
+        Ok(Range::new(start, end))
+    }
+}
+
+impl<T: Ord + Serialize> Serialize for RangeSet<T> {
+    /// Serializes the set as a sequence of ranges
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.items.iter())
+    }
+}
+
+impl<'de, T: Ord + Debug + Deserialize<'de>> Deserialize<'de> for RangeSet<T> {
+    /// Deserializes a set from a sequence of ranges, the ranges don't have to be sorted and may overlap, but may not
+    /// be inverted
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RangeSet::try_from_vec(Vec::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+/// Serde adapter serializing a set as the notation it's [displayed](std::fmt::Display) in for human-readable formats
+/// like JSON, YAML or TOML, e.g. `"{0..4, 6..=10}"`, and as a sequence of ranges for binary formats
+///
+/// Values of which the displayed form can't be parsed back, because it's empty, has surrounding whitespace or holds
+/// part of the notation like `..`, `,`, `{` or `}`, are rejected when serializing
+///
+/// # Example
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use eater_rangeset::{r, range_set, RangeSet};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "eater_rangeset::serialize::string")]
+///     ports: RangeSet<u16>,
+/// }
+///
+/// let config = Config { ports: range_set![r!(80..=80), r!(8000..9000)] };
+/// let json = serde_json::to_string(&config).unwrap();
+///
+/// assert_eq!(r#"{"ports":"{80..=80, 8000..9000}"}"#, json);
+/// assert_eq!(config, serde_json::from_str(&json).unwrap());
+/// ```
+pub mod string {
+    use std::fmt::{Debug, Display};
+    use std::str::FromStr;
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+    use crate::{Bound, RangeSet};
+
+    /// Serialize given set as its displayed notation for human-readable formats, or as a sequence of ranges
+    pub fn serialize<T, S>(set: &RangeSet<T>, serializer: S) -> Result<S::Ok, S::Error>
+        where T: Ord + Display + Serialize, S: Serializer {
+        if !serializer.is_human_readable() {
+            return set.serialize(serializer);
+        }
+
+        for value in set.items.iter().flat_map(|range| [range.start.as_ref(), range.end.as_ref()]).filter_map(bound_value) {
+            let value = value.to_string();
+            if !is_parseable(&value) {
+                return Err(ser::Error::custom(format_args!("value `{}` can't be written in the notation of a set", value)));
+            }
+        }
+
+        serializer.collect_str(set)
+    }
+
+    /// Deserialize a set from the representation [`serialize`](serialize) writes for the format
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<RangeSet<T>, D::Error>
+        where T: Ord + Debug + FromStr + Deserialize<'de>, T::Err: Display, D: Deserializer<'de> {
+        if deserializer.is_human_readable() {
+            String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+        } else {
+            RangeSet::deserialize(deserializer)
+        }
+    }
+
+    fn bound_value<T>(bound: Bound<&T>) -> Option<&T> {
+        match bound {
+            Bound::Included(value) | Bound::Excluded(value) => Some(value),
+            Bound::Unbounded => None,
+        }
+    }
+
+    /// If given displayed value is read back as the same value when it's part of a set
+    fn is_parseable(value: &str) -> bool {
+        !value.is_empty()
+            && value.trim() == value
+            && !value.contains("..")
+            && !value.contains([',', '{', '}'])
+            && !value.starts_with('=')
+            && !value.ends_with('>')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use serde_test::{assert_de_tokens_error, assert_ser_tokens_error, assert_tokens, Configure, Token};
+    use crate::{r, range_set, RangeSet};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Strings(#[serde(with = "crate::serialize::string")] RangeSet<String>);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Bytes(#[serde(with = "crate::serialize::string")] RangeSet<u8>);
+
+    #[test]
+    fn serde() {
+        let set: RangeSet<u8> = range_set![r!(..4), r!(6>..=8)];
+        let tokens = [
+            Token::Seq { len: Some(2) },
+            Token::Tuple { len: 2 },
+            Token::UnitVariant { name: "Bound", variant: "Unbounded" },
+            Token::NewtypeVariant { name: "Bound", variant: "Excluded" },
+            Token::U8(4),
+            Token::TupleEnd,
+            Token::Tuple { len: 2 },
+            Token::NewtypeVariant { name: "Bound", variant: "Excluded" },
+            Token::U8(6),
+            Token::NewtypeVariant { name: "Bound", variant: "Included" },
+            Token::U8(8),
+            Token::TupleEnd,
+            Token::SeqEnd,
+        ];
+
+        assert_tokens(&set.clone().readable(), &tokens);
+        assert_tokens(&set.clone().compact(), &tokens);
+        assert_de_tokens_error::<RangeSet<u8>>(
+            &[
+                Token::Seq { len: Some(1) },
+                Token::Tuple { len: 2 },
+                Token::NewtypeVariant { name: "Bound", variant: "Included" },
+                Token::U8(4),
+                Token::NewtypeVariant { name: "Bound", variant: "Excluded" },
+                Token::U8(2),
+                Token::TupleEnd,
+                Token::SeqEnd,
+            ],
+            "range Range { start: Included(4), end: Excluded(2) } ends before it starts",
+        );
+    }
+
+    #[test]
+    fn string() {
+        let bytes = Bytes(range_set![r!(..4), r!(6>..=8)]);
+        assert_tokens(&bytes.readable(), &[Token::NewtypeStruct { name: "Bytes" }, Token::Str("{..4, 6>..=8}")]);
+        assert_tokens(&Bytes(range_set![r!(4..)]).compact(), &[
+            Token::NewtypeStruct { name: "Bytes" },
+            Token::Seq { len: Some(1) },
+            Token::Tuple { len: 2 },
+            Token::NewtypeVariant { name: "Bound", variant: "Included" },
+            Token::U8(4),
+            Token::UnitVariant { name: "Bound", variant: "Unbounded" },
+            Token::TupleEnd,
+            Token::SeqEnd,
+        ]);
+
+        assert_de_tokens_error::<serde_test::Readable<Bytes>>(
+            &[Token::NewtypeStruct { name: "Bytes" }, Token::Str("{4..2}")],
+            "range Range { start: Included(4), end: Excluded(2) } ends before it starts",
+        );
+
+        let strings = Strings(range_set![r!((String::from("a"))..String::from("c"))]);
+        let json = serde_json::to_string(&strings).unwrap();
+        assert_eq!(r#""{a..c}""#, json);
+        assert_eq!(strings, serde_json::from_str(&json).unwrap());
+
+        for value in ["a, b", "c..d", "{", "", " a", "a>", "=a"] {
+            let strings = Strings(range_set![r!((String::from(value))..String::from("~"))]);
+            assert!(serde_json::to_string(&strings).is_err(), "{:?}", value);
+        }
+
+        assert_ser_tokens_error(
+            &Strings(range_set![r!((String::from("a, b"))..String::from("c..d"))]).readable(),
+            &[Token::NewtypeStruct { name: "Strings" }],
+            "value `a, b` can't be written in the notation of a set",
+        );
+    }
+}
@@ -0,0 +1,355 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::mem;
+use std::slice::Iter;
+use crate::{BoundExt, PositionalBound, Range, RangeVec};
+use crate::Bound::Unbounded;
+
+/// A comparison between 2 values, implemented for every `Fn(&T, &T) -> Ordering`
+pub trait Compare<T: ?Sized> {
+    /// Compare `left` with `right`, alike [`Ord::cmp`](Ord::cmp)
+    fn compare(&self, left: &T, right: &T) -> Ordering;
+}
+
+impl<T: ?Sized, F: Fn(&T, &T) -> Ordering> Compare<T> for F {
+    #[inline]
+    fn compare(&self, left: &T, right: &T) -> Ordering {
+        self(left, right)
+    }
+}
+
+/// A comparison on a key extracted from both values, alike [`slice::sort_by_key`](slice::sort_by_key)
+#[derive(Debug, Clone, Copy)]
+pub struct ByKey<F>(pub F);
+
+impl<T: ?Sized, K: Ord, F: Fn(&T) -> K> Compare<T> for ByKey<F> {
+    #[inline]
+    fn compare(&self, left: &T, right: &T) -> Ordering {
+        (self.0)(left).cmp(&(self.0)(right))
+    }
+}
+
+/// A set of ranges ordered by a key extracted from the bounds, see [`RangeSetBy::by_key`](RangeSetBy::by_key)
+pub type RangeSetByKey<T, F> = RangeSetBy<T, ByKey<F>>;
+
+/// A set of ranges ordered by an explicit comparator, alike [`slice::sort_by`](slice::sort_by)
+///
+/// This allows types without an `Ord` implementation, or types that need a different ordering (e.g. collation),
+/// to be used. The comparator has to be a total order, and sets combined with each other are expected to use the
+/// same ordering
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, RangeSetBy};
+///
+/// let mut set = RangeSetBy::new(f64::total_cmp);
+/// set.add(r!(0.5..1.5));
+/// set.add(r!(1.0..=2.0));
+///
+/// assert!(set.contains(&2.0));
+/// assert!(!set.contains(&0.25));
+/// assert_eq!(vec![&r!(0.5..=2.0)], set.items().collect::<Vec<_>>());
+/// ```
+pub struct RangeSetBy<T, C> {
+    items: RangeVec<Range<T>>,
+    cmp: C,
+}
+
+impl<T: Debug, C> Debug for RangeSetBy<T, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RangeSetBy").field("items", &self.items).finish_non_exhaustive()
+    }
+}
+
+impl<T: Clone, C: Clone> Clone for RangeSetBy<T, C> {
+    fn clone(&self) -> Self {
+        RangeSetBy { items: self.items.clone(), cmp: self.cmp.clone() }
+    }
+}
+
+impl<T: PartialEq, C> PartialEq for RangeSetBy<T, C> {
+    /// Sets are equal when they hold the same ranges, the comparators aren't compared as they're usually closures or
+    /// functions that can't be, and sets compared with each other are expected to use the same ordering
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items
+    }
+}
+
+impl<T: Eq, C> Eq for RangeSetBy<T, C> {}
+
+impl<T, C: Compare<T>> RangeSetBy<T, C> {
+    /// Create a new empty set ordered by given comparator
+    pub fn new(cmp: C) -> Self {
+        RangeSetBy { items: RangeVec::new(), cmp }
+    }
+
+    /// Create a new unbounded set ordered by given comparator
+    pub fn unbound(cmp: C) -> Self {
+        let mut items = RangeVec::with_capacity(1);
+        items.push(Range::unbound());
+
+        RangeSetBy { items, cmp }
+    }
+
+    /// Create a set from given ranges, which may be in any order and overlap
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, RangeSetBy};
+    ///
+    /// let set = RangeSetBy::from_ranges([r!("b"..="c"), r!("A".."B")], |a: &&str, b: &&str| {
+    ///     a.to_lowercase().cmp(&b.to_lowercase())
+    /// });
+    ///
+    /// assert!(set.contains(&"a"));
+    /// assert!(set.contains(&"C"));
+    /// assert!(!set.contains(&"d"));
+    /// ```
+    pub fn from_ranges(ranges: impl IntoIterator<Item=Range<T>>, cmp: C) -> Self {
+        let mut ranges: Vec<_> = ranges.into_iter().collect();
+        ranges.sort_by(|left, right| left.start_pos().cmp_by(&right.start_pos(), |a, b| cmp.compare(a, b)));
+
+        let mut set = RangeSetBy { items: RangeVec::with_capacity(ranges.len()), cmp };
+        for range in ranges {
+            set.push(range);
+        }
+
+        set
+    }
+
+    /// The comparator this set is ordered by
+    #[inline]
+    pub fn comparator(&self) -> &C {
+        &self.cmp
+    }
+
+    /// If this is an empty set
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// If this set is unbounded or infinite
+    #[inline]
+    pub fn is_unbound(&self) -> bool {
+        self.items.len() == 1 && self.items[0].is_unbound()
+    }
+
+    /// Returns an iterator with all ranges inside of this set
+    #[inline]
+    pub fn items(&self) -> Iter<'_, Range<T>> {
+        self.items.iter()
+    }
+
+    /// Check if `other` falls within the ranges defined in this set
+    pub fn contains(&self, other: &T) -> bool {
+        let index = self.items.partition_point(|range| range.end_pos().cmp_value_by(&other, |a, b| self.cmp.compare(a, b)).is_lt());
+        self.items.get(index).is_some_and(|range| range.start_pos().cmp_value_by(&other, |a, b| self.cmp.compare(a, b)).is_lt())
+    }
+
+    /// Add a new range to this set
+    pub fn add(&mut self, range: impl Into<Range<T>>) {
+        let range = range.into();
+        let index = self.items.partition_point(|item| self.cmp_pos(&item.start_pos(), &range.start_pos()).is_le());
+        let capacity = self.items.len() + 1;
+        let mut items = mem::replace(&mut self.items, RangeVec::with_capacity(capacity)).into_iter();
+
+        for item in items.by_ref().take(index) {
+            self.push(item);
+        }
+
+        self.push(range);
+
+        for item in items {
+            self.push(item);
+        }
+    }
+
+    fn cmp_pos(&self, left: &PositionalBound<&T>, right: &PositionalBound<&T>) -> Ordering {
+        left.cmp_by(right, |a, b| self.cmp.compare(a, b))
+    }
+
+    /// Push a range that doesn't start before the last range, merging it if it overlaps or touches
+    fn push(&mut self, range: Range<T>) {
+        let Some(last) = self.items.last() else {
+            self.items.push(range);
+            return;
+        };
+
+        if self.cmp_pos(&last.end_pos(), &range.start_pos()).is_lt() {
+            self.items.push(range);
+        } else if self.cmp_pos(&range.end_pos(), &last.end_pos()).is_gt() {
+            let index = self.items.len() - 1;
+            self.items[index].end = range.end;
+        }
+    }
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> RangeSetByKey<T, F> {
+    /// Create a new empty set ordered by the key given function extracts from the bounds
+    ///
+    /// Bounds with an equal key are treated as the same position, regardless of the rest of their value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, RangeSetBy};
+    ///
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// struct Mark {
+    ///     offset: u64,
+    ///     label: &'static str,
+    /// }
+    ///
+    /// let mut set = RangeSetBy::by_key(|mark: &Mark| mark.offset);
+    /// set.add(r!((Mark { offset: 0, label: "intro" })..Mark { offset: 10, label: "body" }));
+    ///
+    /// assert!(set.contains(&Mark { offset: 5, label: "anything" }));
+    /// assert!(!set.contains(&Mark { offset: 10, label: "body" }));
+    /// ```
+    pub fn by_key(f: F) -> Self {
+        RangeSetBy::new(ByKey(f))
+    }
+}
+
+impl<T: Clone, C: Compare<T> + Clone> RangeSetBy<T, C> {
+    /// Create an union of this set and given set, see [`RangeSet::union`](crate::RangeSet::union)
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = RangeSetBy { items: RangeVec::with_capacity(self.items.len() + other.items.len()), cmp: self.cmp.clone() };
+
+        let mut left = self.items.iter().peekable();
+        let mut right = other.items.iter().peekable();
+
+        loop {
+            let next = match (left.peek(), right.peek()) {
+                (Some(l), Some(r)) if self.cmp_pos(&r.start_pos(), &l.start_pos()).is_lt() => right.next(),
+                (Some(_), _) => left.next(),
+                (None, _) => right.next(),
+            };
+
+            match next {
+                Some(range) => set.push(range.clone()),
+                None => break,
+            }
+        }
+
+        set
+    }
+
+    /// Invert current set, see [`RangeSet::invert`](crate::RangeSet::invert)
+    pub fn invert(&self) -> Self {
+        if self.is_empty() {
+            return RangeSetBy::unbound(self.cmp.clone());
+        }
+
+        let mut items = RangeVec::with_capacity(self.items.len() + 1);
+        let mut start = Unbounded;
+
+        for item in self.items() {
+            let gap = Range::new(start, item.start.clone().invert());
+            // Touching ranges leave a gap holding no values
+            if !matches!(item.start, Unbounded) && self.cmp_pos(&gap.start_pos(), &gap.end_pos()).is_lt() {
+                items.push(gap);
+            }
+
+            start = item.end.clone().invert();
+        }
+
+        if !matches!(start, Unbounded) {
+            items.push(Range::new(start, Unbounded));
+        }
+
+        RangeSetBy { items, cmp: self.cmp.clone() }
+    }
+
+    /// Get the intersection of the 2 sets, see [`RangeSet::intersection`](crate::RangeSet::intersection)
+    pub fn intersection(&self, rhs: &Self) -> Self {
+        self.invert().union(&rhs.invert()).invert()
+    }
+
+    /// Get the difference of this set with given set, see [`RangeSet::difference`](crate::RangeSet::difference)
+    pub fn difference(&self, rhs: &Self) -> Self {
+        self.invert().union(rhs).invert()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+    use crate::{r, range_set, Range, RangeSetBy};
+
+    #[test]
+    fn set_by() {
+        let reversed = |a: &u32, b: &u32| b.cmp(a);
+        let mut set = RangeSetBy::new(reversed);
+        assert!(set.is_empty());
+
+        set.add(r!(10..=5));
+        set.add(r!(20..15));
+        set.add(r!(15..=10));
+        assert_eq!(vec![&r!(20..=5)], set.items().collect::<Vec<_>>());
+        assert!(set.contains(&5));
+        assert!(!set.contains(&4));
+        assert!(set.contains(&20));
+        assert!(!set.contains(&21));
+
+        let other = RangeSetBy::from_ranges([r!(8..2), r!(30..25)], reversed);
+        assert_eq!(RangeSetBy::from_ranges([r!(30..25), r!(20..2)], reversed), set.union(&other));
+        assert_eq!(RangeSetBy::from_ranges([r!(8..=5)], reversed), set.intersection(&other));
+        assert_eq!(RangeSetBy::from_ranges([r!(20..8)], reversed), set.difference(&other));
+        assert_eq!(RangeSetBy::from_ranges([r!(..20), r!(5>..)], reversed), set.invert());
+
+        // Only the ranges are compared, not the comparators
+        let forward: fn(&u32, &u32) -> Ordering = u32::cmp;
+        let backward: fn(&u32, &u32) -> Ordering = |a, b| b.cmp(a);
+        assert_eq!(RangeSetBy::from_ranges([r!(4..=4)], forward), RangeSetBy::from_ranges([r!(4..=4)], backward));
+    }
+
+    #[test]
+    fn matches_ord() {
+        let sets = [
+            range_set![r!(..0), r!(4..8), r!(10>..=20)],
+            range_set![r!(2..5), r!(8..=10), r!(30..)],
+            range_set![],
+            range_set![r!(..)],
+        ];
+
+        for left in &sets {
+            let left_by = RangeSetBy::from_ranges(left.items().cloned(), i32::cmp);
+            assert_eq!(left.invert().items().collect::<Vec<_>>(), left_by.invert().items().collect::<Vec<_>>());
+
+            for right in &sets {
+                let right_by = RangeSetBy::from_ranges(right.items().cloned(), i32::cmp);
+                let items = |set: RangeSetBy<i32, _>| set.items().cloned().collect::<Vec<Range<i32>>>();
+
+                assert_eq!(left.union(right).items().cloned().collect::<Vec<_>>(), items(left_by.union(&right_by)));
+                assert_eq!(left.intersection(right).items().cloned().collect::<Vec<_>>(), items(left_by.intersection(&right_by)));
+                assert_eq!(left.difference(right).items().cloned().collect::<Vec<_>>(), items(left_by.difference(&right_by)));
+            }
+        }
+
+        for x in -5..35 {
+            let set = RangeSetBy::from_ranges(sets[0].items().cloned(), |a: &i32, b: &i32| -> Ordering { a.cmp(b) });
+            assert_eq!(sets[0].contains(&x), set.contains(&x));
+        }
+    }
+
+    #[test]
+    fn by_key() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Offset(u64, &'static str);
+
+        let mut set = RangeSetBy::by_key(|offset: &Offset| offset.0);
+        set.add(r!((Offset(10, "b"))..Offset(20, "b")));
+        set.add(r!((Offset(0, "a"))..=Offset(10, "a")));
+
+        assert_eq!(vec![&r!((Offset(0, "a"))..Offset(20, "b"))], set.items().collect::<Vec<_>>());
+        assert!(set.contains(&Offset(19, "c")));
+        assert!(!set.contains(&Offset(20, "b")));
+
+        let other = RangeSetBy::from_ranges([r!((Offset(15, "c"))..)], *set.comparator());
+        assert_eq!(vec![&r!((Offset(0, "a"))..Offset(15, "c"))], set.difference(&other).items().collect::<Vec<_>>());
+    }
+}
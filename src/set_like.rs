@@ -0,0 +1,174 @@
+use std::fmt::Debug;
+use std::iter;
+use std::ops::RangeBounds;
+use std::slice;
+use crate::{ComplementView, IntersectionView, Range, RangeSet, RangeSetRef, RangeSetView, UnionView};
+
+pub(crate) mod sealed {
+    use crate::RangeSetRef;
+
+    pub trait Sealed<T: Ord> {
+        /// Call given function with the ranges of this set as a borrowed set, lazy sets are built first
+        fn with_ranges<R>(&self, f: impl FnOnce(RangeSetRef<'_, T>) -> R) -> R;
+    }
+}
+
+/// Anything that can be queried like a set of ranges, a [`RangeSet`](RangeSet), a single [`Range`](Range), a
+/// [`RangeSetRef`](RangeSetRef) or one of the views over sets
+///
+/// The binary operations of [`RangeSet`](RangeSet) accept any of these, so a single range or a view doesn't have to
+/// be turned into a set first. This trait is sealed and can't be implemented outside of this crate
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, RangeSetLike};
+///
+/// fn covers_all<S: RangeSetLike<u32>>(set: &S, values: &[u32]) -> bool {
+///     values.iter().all(|value| set.contains(value))
+/// }
+///
+/// let set = range_set![r!(0u32..10), r!(20..30)];
+///
+/// assert!(covers_all(&set, &[4, 25]));
+/// assert!(covers_all(&r!(0u32..5), &[4]));
+/// assert_eq!(range_set![r!(0..5), r!(25..30)], set.intersection(&range_set![r!(..5), r!(25..)]));
+/// assert_eq!(range_set![r!(0..15), r!(20..30)], set.union(&r!(5..15)));
+/// ```
+pub trait RangeSetLike<T: Ord>: sealed::Sealed<T> {
+    /// Check if `value` is inside of this set
+    fn contains(&self, value: &T) -> bool;
+
+    /// Returns an iterator over the ranges in this set
+    fn items<'a>(&'a self) -> impl Iterator<Item=Range<&'a T>> where T: 'a;
+
+    /// Returns an iterator over the ranges in this set that overlap with given range
+    fn overlapping<'b>(&'b self, range: impl RangeBounds<T> + 'b) -> impl Iterator<Item=Range<&'b T>> where T: 'b;
+}
+
+impl<T: Ord + Debug> sealed::Sealed<T> for RangeSet<T> {
+    fn with_ranges<R>(&self, f: impl FnOnce(RangeSetRef<'_, T>) -> R) -> R {
+        f(self.as_ref())
+    }
+}
+
+impl<T: Ord + Debug> RangeSetLike<T> for RangeSet<T> {
+    fn contains(&self, value: &T) -> bool {
+        RangeSet::contains(self, value)
+    }
+
+    fn items<'b>(&'b self) -> impl Iterator<Item=Range<&'b T>> where T: 'b {
+        self.items.iter().map(Range::as_ref)
+    }
+
+    fn overlapping<'b>(&'b self, range: impl RangeBounds<T> + 'b) -> impl Iterator<Item=Range<&'b T>> where T: 'b {
+        self.as_ref().overlapping(range).map(Range::as_ref)
+    }
+}
+
+impl<'a, T: Ord + Debug> sealed::Sealed<T> for RangeSetRef<'a, T> {
+    fn with_ranges<R>(&self, f: impl FnOnce(RangeSetRef<'_, T>) -> R) -> R {
+        f(*self)
+    }
+}
+
+impl<'a, T: Ord + Debug> RangeSetLike<T> for RangeSetRef<'a, T> {
+    fn contains(&self, value: &T) -> bool {
+        RangeSetRef::contains(self, value)
+    }
+
+    fn items<'b>(&'b self) -> impl Iterator<Item=Range<&'b T>> where T: 'b {
+        self.as_slice().iter().map(Range::as_ref)
+    }
+
+    fn overlapping<'b>(&'b self, range: impl RangeBounds<T> + 'b) -> impl Iterator<Item=Range<&'b T>> where T: 'b {
+        RangeSetRef::overlapping(self, range).map(Range::as_ref)
+    }
+}
+
+impl<T: Ord + Debug> sealed::Sealed<T> for Range<T> {
+    fn with_ranges<R>(&self, f: impl FnOnce(RangeSetRef<'_, T>) -> R) -> R {
+        // A set never holds inverted or empty ranges
+        if self.is_inverted() || self.is_empty() {
+            f(RangeSetRef::new(&[]))
+        } else {
+            f(RangeSetRef::new(slice::from_ref(self)))
+        }
+    }
+}
+
+impl<T: Ord + Debug> RangeSetLike<T> for Range<T> {
+    fn contains(&self, value: &T) -> bool {
+        Range::contains(self, value)
+    }
+
+    fn items<'b>(&'b self) -> impl Iterator<Item=Range<&'b T>> where T: 'b {
+        iter::once(self.as_ref()).filter(|range| !range.is_inverted() && !range.is_empty())
+    }
+
+    fn overlapping<'b>(&'b self, range: impl RangeBounds<T> + 'b) -> impl Iterator<Item=Range<&'b T>> where T: 'b {
+        self.items().filter(move |item| {
+            let window = Range::new(range.start_bound(), range.end_bound());
+            item.start_pos() < window.end_pos() && window.start_pos() < item.end_pos()
+        })
+    }
+}
+
+macro_rules! impl_view {
+    ($($view:ident),*) => {
+        $(
+            impl<'a, T: Ord + Clone + Debug> sealed::Sealed<T> for $view<'a, T> {
+                fn with_ranges<R>(&self, f: impl FnOnce(RangeSetRef<'_, T>) -> R) -> R {
+                    f(self.to_range_set().as_ref())
+                }
+            }
+
+            impl<'a, T: Ord + Clone + Debug> RangeSetLike<T> for $view<'a, T> {
+                fn contains(&self, value: &T) -> bool {
+                    $view::contains(self, value)
+                }
+
+                fn items<'b>(&'b self) -> impl Iterator<Item=Range<&'b T>> where T: 'b {
+                    $view::items(self)
+                }
+
+                fn overlapping<'b>(&'b self, range: impl RangeBounds<T> + 'b) -> impl Iterator<Item=Range<&'b T>> where T: 'b {
+                    $view::overlapping(self, range)
+                }
+            }
+        )*
+    };
+}
+
+impl_view!(ComplementView, IntersectionView, RangeSetView, UnionView);
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeSet, RangeSetLike};
+
+    fn collect<T: Ord + Clone, S: RangeSetLike<T>>(set: &S) -> Vec<crate::Range<T>> {
+        set.items().map(|range| crate::Range::new(range.start.cloned(), range.end.cloned())).collect()
+    }
+
+    #[test]
+    fn range_set_like() {
+        let set = range_set![r!(0..10), r!(20..30)];
+        assert_eq!(set.items().cloned().collect::<Vec<_>>(), collect(&set));
+        assert_eq!(set.items().cloned().collect::<Vec<_>>(), collect(&set.as_ref()));
+        assert_eq!(vec![r!(..0), r!(10..20), r!(30..)], collect(&set.complement_view()));
+        assert_eq!(vec![r!(5..10)], collect(&set.view(r!(5..15))));
+        assert_eq!(vec![r!(4..5)], collect(&r!(4..5)));
+        assert_eq!(Vec::<crate::Range<u8>>::new(), collect(&r!(5u8..4)));
+        assert_eq!(vec![r!((&4)..&5)], RangeSetLike::overlapping(&r!(4..5), r!(0..=4)).collect::<Vec<_>>());
+        assert_eq!(0, RangeSetLike::overlapping(&r!(4..5), r!(0..4)).count());
+
+        assert_eq!(range_set![r!(0..30)], set.union(&r!(10..20)));
+        assert_eq!(set, set.union(&r!(15..15)));
+        assert_eq!(range_set![r!(5..10)], set.intersection(&r!(5..15)));
+        assert_eq!(range_set![r!(0..5), r!(20..30)], set.difference(&r!(5..15)));
+        assert_eq!(RangeSet::empty(), set.difference(&set.union_view(&range_set![r!(10..20)])));
+        assert_eq!(set.invert(), RangeSet::unbound().intersection(&set.complement_view()));
+        assert!(set.is_disjoint(&r!(10..20)));
+        assert!(set.is_overlapping(&set.intersection_view(&range_set![r!(9..)])));
+    }
+}
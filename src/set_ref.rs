@@ -0,0 +1,519 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::iter;
+use std::mem;
+use std::ops::RangeBounds;
+use std::slice::Iter;
+use crate::{holds_values, max_end, max_start, min_end, Bound, BoundExt, merge_sorted, PositionalBound, Range, RangeSet, RangeSetBuilder, RangeVec};
+use crate::Bound::Unbounded;
+use crate::macros::trace_span;
+
+/// A borrowed, read-only view over a sorted slice of non-overlapping ranges
+///
+/// Provides the read-only queries of [`RangeSet`](RangeSet) without owning the ranges, e.g. for zero-copy
+/// deserialized or statically embedded data
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, RangeSetRef};
+///
+/// let ranges = [r!(0..4), r!(10..20)];
+/// let set = RangeSetRef::new(&ranges);
+///
+/// assert!(set.contains(&12));
+/// assert!(!set.contains(&4));
+/// ```
+#[derive(Debug, Eq, PartialEq)]
+pub struct RangeSetRef<'a, T: Ord> {
+    items: &'a [Range<T>],
+}
+
+impl<'a, T: Ord> Clone for RangeSetRef<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T: Ord> Copy for RangeSetRef<'a, T> {}
+
+impl<'a, T: Ord + Debug> From<&'a RangeSet<T>> for RangeSetRef<'a, T> {
+    fn from(value: &'a RangeSet<T>) -> Self {
+        RangeSetRef { items: &value.items }
+    }
+}
+
+impl<'a, T: Ord + Debug> RangeSetRef<'a, T> {
+    /// Create a view over given ranges, which have to be sorted by their start and may not overlap, this is only
+    /// checked in debug builds
+    ///
+    /// Ranges may touch, like the ranges kept apart by [`split_boundary_at`](RangeSet::split_boundary_at)
+    pub fn new(items: &'a [Range<T>]) -> Self {
+        debug_assert!(items.windows(2).all(|pair| pair[0].end_pos() <= pair[1].start_pos()), "ranges given to RangeSetRef are not sorted or overlap");
+        RangeSetRef { items }
+    }
+
+    /// If this is an empty set
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// If this set is unbounded or infinite
+    #[inline]
+    pub fn is_unbound(&self) -> bool {
+        self.items.len() == 1 && self.items[0].is_unbound()
+    }
+
+    /// Returns an iterator with all ranges inside of this set
+    #[inline]
+    pub fn items(&self) -> Iter<'a, Range<T>> {
+        self.items.iter()
+    }
+
+    /// Returns the ranges inside of this set as slice
+    #[inline]
+    pub fn as_slice(&self) -> &'a [Range<T>] {
+        self.items
+    }
+
+    /// Check if `other` falls within the ranges defined in this set, see [`RangeSet::contains`](RangeSet::contains)
+    pub fn contains<Q: Ord + ?Sized>(&self, other: &Q) -> bool where T: Borrow<Q> {
+        self.find(other).is_some()
+    }
+
+    /// Check if `other` falls within this set starting the search at a hint, see
+    /// [`RangeSet::contains_hint`](RangeSet::contains_hint)
+    pub fn contains_hint<Q: Ord + ?Sized>(&self, other: &Q, hint: &mut usize) -> bool where T: Borrow<Q> {
+        let before = |range: &Range<T>| range.as_borrowed::<Q>().end_pos() < &other;
+        let mut index = (*hint).min(self.items.len());
+
+        if index > 0 && !before(&self.items[index - 1]) {
+            // Moved backwards, fall back to a search of everything before the hint
+            index = self.items[..index].partition_point(before);
+        } else if index < self.items.len() && before(&self.items[index]) {
+            // Gallop forward, so values close to the previous one are found in a few steps
+            let mut bound = 1;
+            while index + bound < self.items.len() && before(&self.items[index + bound]) {
+                bound *= 2;
+            }
+
+            let low = index + bound / 2 + 1;
+            index = low + self.items[low..(index + bound).min(self.items.len())].partition_point(before);
+        }
+
+        *hint = index;
+        self.items.get(index).is_some_and(|range| range.as_borrowed::<Q>().start_pos() < &other)
+    }
+
+    /// Returns the range in this set that contains `other`, see [`RangeSet::find`](RangeSet::find)
+    pub fn find<Q: Ord + ?Sized>(&self, other: &Q) -> Option<&'a Range<T>> where T: Borrow<Q> {
+        let index = self.items.partition_point(|range| range.as_borrowed::<Q>().end_pos() < &other);
+        self.items.get(index).filter(|range| range.as_borrowed::<Q>().start_pos() < &other)
+    }
+
+    /// Returns an iterator over the ranges in this set that overlap with given range
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(0..4), r!(6..10), r!(12..20)];
+    ///
+    /// let overlapping: Vec<_> = set.as_ref().overlapping(r!(4..=12)).collect();
+    /// assert_eq!(vec![&r!(6..10), &r!(12..20)], overlapping);
+    /// ```
+    pub fn overlapping(&self, range: impl RangeBounds<T>) -> Iter<'a, Range<T>> {
+        let range = Range::new(range.start_bound(), range.end_bound());
+        if !holds_values(range.start, range.end) {
+            return [].iter();
+        }
+
+        self.overlapping_slice(range).iter()
+    }
+
+    /// Returns the amount of ranges in this set that overlap with given window, see
+    /// [`RangeSet::overlap_count`](RangeSet::overlap_count)
+    pub fn overlap_count(&self, window: impl RangeBounds<T>) -> usize {
+        let window = Range::new(window.start_bound(), window.end_bound());
+        if !holds_values(window.start, window.end) {
+            return 0;
+        }
+
+        self.overlapping_slice(window).len()
+    }
+
+    /// Returns `true` if any range in this set shares a value with given range, see
+    /// [`RangeSet::intersects`](RangeSet::intersects)
+    pub fn intersects(&self, range: impl RangeBounds<T>) -> bool {
+        let range = Range::new(range.start_bound(), range.end_bound());
+        holds_values(range.start, range.end) && !self.overlapping_slice(range).is_empty()
+    }
+
+    /// Returns `true` if the ranges in this set hold every value between given bounds, following runs of touching
+    /// ranges instead of only looking at the range holding the start
+    pub(crate) fn covers(&self, start: Bound<&T>, end: Bound<&T>) -> bool {
+        let index = self.items.partition_point(|item| item.start_pos() <= PositionalBound::Start(start));
+        let Some(first) = index.checked_sub(1) else {
+            return false;
+        };
+
+        let end = PositionalBound::End(end);
+        let mut reach = self.items[first].end();
+        for item in &self.items[index..] {
+            if PositionalBound::End(reach) >= end || !PositionalBound::connects(reach, item.start()) {
+                break;
+            }
+
+            reach = max_end(reach, item.end());
+        }
+
+        PositionalBound::End(reach) >= end
+    }
+
+    /// Returns the part of the ranges in this set that overlap with given range
+    pub(crate) fn overlapping_slice(&self, range: Range<&T>) -> &'a [Range<T>] {
+        let (start, end) = (PositionalBound::Start(range.start), PositionalBound::End(range.end));
+        let start_index = self.items.partition_point(|item| item.end_pos() <= start);
+        let end_index = start_index + self.items[start_index..].partition_point(|item| item.start_pos() < end);
+
+        &self.items[start_index..end_index]
+    }
+
+    /// Returns `true` if this set does not overlap in anyway with given set
+    pub fn is_disjoint(&self, rhs: RangeSetRef<'_, T>) -> bool {
+        if self.is_empty() || rhs.is_empty() {
+            return true;
+        }
+
+        if self.is_unbound() || rhs.is_unbound() {
+            return false;
+        }
+
+        let mut left_iter = self.items();
+        let mut right_iter = rhs.items();
+
+        let mut left = left_iter.next();
+        let mut right = right_iter.next();
+
+        while let (Some(l), Some(r)) = (left, right) {
+            if r.start_pos() == l.start_pos() {
+                return false;
+            }
+
+            if r.start_pos() < l.start_pos() {
+                if r.end_pos() >= l.end_pos() {
+                    return false;
+                } else {
+                    right = right_iter.next();
+                }
+            } else if r.start_pos() < l.end_pos() {
+                return false;
+            } else {
+                left = left_iter.next();
+            }
+        }
+
+        true
+    }
+
+    /// Returns `true` if this set overlaps anywhere with given set
+    pub fn is_overlapping(&self, rhs: RangeSetRef<'_, T>) -> bool {
+        if self.is_empty() || rhs.is_empty() {
+            return false;
+        }
+
+        if self.is_unbound() || rhs.is_unbound() {
+            return true;
+        }
+
+        let mut left_iter = self.items();
+        let mut right_iter = rhs.items();
+
+        let mut left = left_iter.next();
+        let mut right = right_iter.next();
+
+        while let (Some(l), Some(r)) = (left, right) {
+            if l.start_pos() >= r.start_pos() {
+                if l.start_pos() < r.end_pos() {
+                    return true;
+                } else {
+                    right = right_iter.next();
+                }
+            } else if l.end_pos() > r.start_pos() {
+                return true;
+            } else {
+                left = left_iter.next();
+            }
+        }
+
+        false
+    }
+
+    /// Returns an iterator over every pair of overlapping ranges between this set and given set, see
+    /// [`RangeSet::join`](RangeSet::join)
+    pub fn join(&self, other: RangeSetRef<'a, T>) -> impl Iterator<Item=(&'a Range<T>, &'a Range<T>, Range<&'a T>)> + 'a {
+        let (mut left, mut right) = (self.items.iter().peekable(), other.items.iter().peekable());
+        iter::from_fn(move || loop {
+            let (l, r) = (*left.peek()?, *right.peek()?);
+
+            // The range ending first can't overlap with anything after the other range
+            if l.end_pos() <= r.end_pos() {
+                left.next();
+            } else {
+                right.next();
+            }
+
+            let (start, end) = (max_start(l.start(), r.start()), min_end(l.end(), r.end()));
+            if holds_values(start, end) {
+                return Some((l, r, Range::new(start, end)));
+            }
+        })
+    }
+}
+
+impl<'a, T: Ord + Clone + Debug> RangeSetRef<'a, T> {
+    /// Copy the ranges in this view into an owned set
+    pub fn to_range_set(&self) -> RangeSet<T> {
+        RangeSet { items: self.items.iter().cloned().collect() }
+    }
+
+    /// Returns a lazy iterator over the union of this set and given set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let left = range_set![r!(0..4), r!(10..20)];
+    /// let right = range_set![r!(2..12)];
+    ///
+    /// let union: Vec<_> = left.as_ref().union_iter(right.as_ref()).collect();
+    /// assert_eq!(vec![r!(0..20)], union);
+    /// ```
+    pub fn union_iter(&self, other: RangeSetRef<'a, T>) -> impl Iterator<Item=Range<T>> + 'a {
+        merge_sorted(self.items().cloned(), other.items().cloned())
+    }
+
+    /// Create an union of this set and given set, see [`RangeSet::union`](RangeSet::union)
+    pub fn union(&self, other: RangeSetRef<'_, T>) -> RangeSet<T> {
+        trace_span!("union", left = self.items.len(), right = other.items.len());
+        if other.is_empty() {
+            return self.to_range_set();
+        }
+
+        if self.is_empty() {
+            return other.to_range_set();
+        }
+
+        let mut left_iter = self.items();
+        let mut right_iter = other.items();
+
+        let mut left = left_iter.next();
+        let mut right = right_iter.next();
+
+        let mut builder = RangeSetBuilder::new();
+
+        loop {
+            match (left, right) {
+                (None, None) => break,
+                (Some(l), Some(r)) => {
+                    if r.start_pos() < l.start_pos() {
+                        if builder.push(r.clone()) {
+                            break;
+                        }
+
+                        right = right_iter.next();
+                    } else {
+                        if builder.push(l.clone()) {
+                            break;
+                        }
+
+                        left = left_iter.next();
+                    }
+                }
+                (Some(l), None) => {
+                    if builder.push(l.clone()) {
+                        break;
+                    }
+
+                    left = left_iter.next();
+                }
+
+                (None, Some(r)) => {
+                    if builder.push(r.clone()) {
+                        break;
+                    }
+
+                    right = right_iter.next();
+                }
+            }
+        }
+
+        builder.finish()
+    }
+
+    /// Invert current set, see [`RangeSet::invert`](RangeSet::invert)
+    pub fn invert(&self) -> RangeSet<T> {
+        trace_span!("invert", ranges = self.items.len());
+        if self.is_empty() {
+            return RangeSet::unbound();
+        }
+
+        if self.is_unbound() {
+            return RangeSet::empty();
+        }
+
+        let mut items = RangeVec::with_capacity(self.items.len() + 2);
+        let mut current = Range::unbound();
+
+        for item in self.items() {
+            if item.start == Unbounded {
+                current.start = item.end.clone().invert();
+                continue;
+            }
+
+            current.end = item.start.clone().invert();
+            let last = mem::replace(&mut current, Range::new(item.end.clone().invert(), Unbounded));
+            // Touching ranges, e.g. after `split_boundary_at`, leave a gap holding no values
+            if holds_values(last.start(), last.end()) {
+                items.push(last);
+            }
+
+            if item.end == Unbounded {
+                return RangeSet { items: items.into() };
+            }
+        }
+
+        items.push(current);
+        RangeSet { items: items.into() }
+    }
+
+    /// Get the intersection of the 2 sets, see [`RangeSet::intersection`](RangeSet::intersection)
+    pub fn intersection(&self, rhs: RangeSetRef<'_, T>) -> RangeSet<T> {
+        trace_span!("intersection", left = self.items.len(), right = rhs.items.len());
+        let left = self.invert();
+        let right = rhs.invert();
+        let inter = left.union(&right);
+
+        inter.invert()
+    }
+
+    /// Get the difference of this set with given set, see [`RangeSet::difference`](RangeSet::difference)
+    pub fn difference(&self, rhs: RangeSetRef<'_, T>) -> RangeSet<T> {
+        trace_span!("difference", left = self.items.len(), right = rhs.items.len());
+        let left = self.invert();
+        let mid = left.as_ref().union(rhs);
+        mid.invert()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeSet, RangeSetRef};
+    use crate::Bound::{Excluded, Included, Unbounded};
+
+    #[test]
+    fn set_ref() {
+        let items = [r!(..0), r!(4..8), r!(10>..=20)];
+        let set = RangeSetRef::new(&items);
+        let owned = set.to_range_set();
+
+        assert_eq!(set, owned.as_ref());
+        assert!(set.contains(&-5));
+        assert!(!set.contains(&0));
+        assert!(set.contains(&4));
+        assert!(!set.contains(&10));
+        assert!(set.contains(&20));
+        assert!(!set.contains(&21));
+
+        let empty = RangeSetRef::<usize>::new(&[]);
+        assert!(empty.is_empty());
+        assert!(!empty.contains(&0));
+        assert_eq!(RangeSet::unbound(), empty.invert());
+        assert_eq!(owned.invert(), set.invert());
+
+        let other = range_set![r!(6..12)];
+        assert_eq!(owned.union(&other), set.union(other.as_ref()));
+        assert_eq!(owned.intersection(&other), set.intersection(other.as_ref()));
+        assert_eq!(owned.difference(&other), set.difference(other.as_ref()));
+        assert!(set.is_overlapping(other.as_ref()));
+        assert!(!set.is_disjoint(other.as_ref()));
+    }
+
+    #[test]
+    fn touching() {
+        let mut split = range_set![r!(0..10)];
+        split.split_boundary_at(&5);
+        let set = RangeSetRef::new(split.as_ref().as_slice());
+        assert!(set.contains(&5) && set.contains(&4));
+        assert!(set.covers(Included(&2), Excluded(&8)));
+        assert!(!set.covers(Unbounded, Excluded(&10)));
+        assert!(!set.covers(Included(&2), Included(&10)));
+        assert_eq!(range_set![r!(..0), r!(10..)], set.invert());
+    }
+
+    #[test]
+    fn join() {
+        let left = range_set![r!(..0), r!(4..8), r!(10>..=20), r!(30..)];
+        let right = range_set![r!(-5..5), r!(6..=10), r!(12..14), r!(16..=16), r!(18..35)];
+        assert_eq!(vec![
+            (&r!(..0), &r!(-5..5), r!((&-5)..&0)),
+            (&r!(4..8), &r!(-5..5), r!((&4)..&5)),
+            (&r!(4..8), &r!(6..=10), r!((&6)..&8)),
+            (&r!(10>..=20), &r!(12..14), r!((&12)..&14)),
+            (&r!(10>..=20), &r!(16..=16), r!((&16)..=&16)),
+            (&r!(10>..=20), &r!(18..35), r!((&18)..=&20)),
+            (&r!(30..), &r!(18..35), r!((&30)..&35)),
+        ], left.join(&right).collect::<Vec<_>>());
+
+        let swapped: Vec<_> = right.join(&left).map(|(r, l, overlap)| (l, r, overlap)).collect();
+        assert_eq!(left.join(&right).collect::<Vec<_>>(), swapped);
+        assert_eq!(0, left.join(&RangeSet::empty()).count());
+        assert_eq!(4, left.join(&RangeSet::unbound()).count());
+    }
+
+    #[test]
+    fn contains_hint() {
+        let items: Vec<_> = (0..100).map(|i| r!((i * 10)..i * 10 + 5)).collect();
+        let set = RangeSetRef::new(&items);
+
+        let mut hint = 0;
+        for value in (-5..1010).chain((-5..1010).rev()).chain((0..1000).step_by(97)) {
+            assert_eq!(set.contains(&value), set.contains_hint(&value, &mut hint), "{}", value);
+        }
+
+        let mut hint = usize::MAX;
+        assert!(set.contains_hint(&992, &mut hint));
+        assert_eq!(99, hint);
+        assert!(!RangeSetRef::<i32>::new(&[]).contains_hint(&0, &mut hint));
+        assert_eq!(0, hint);
+    }
+
+    #[test]
+    fn overlapping() {
+        let set = range_set![r!(..0), r!(4..8), r!(10>..=20)];
+        let set = set.as_ref();
+
+        assert_eq!(0, set.overlapping(r!(0..4)).count());
+        assert_eq!(vec![&r!(..0), &r!(4..8)], set.overlapping(r!(..=4)).collect::<Vec<_>>());
+        assert_eq!(vec![&r!(10>..=20)], set.overlapping(r!(8..11)).collect::<Vec<_>>());
+        assert_eq!(0, set.overlapping(r!(8..=10)).count());
+        assert_eq!(3, set.overlapping(r!(..)).count());
+
+        for window in [r!(0..4), r!(..=4), r!(8..11), r!(8..=10), r!(..), r!(20..), r!(20>..), r!(5..5), r!(6..5)] {
+            assert_eq!(set.overlapping(window.clone()).count(), set.overlap_count(window.clone()), "{}", window);
+            assert_eq!(set.overlapping(window.clone()).count() > 0, set.intersects(window.clone()), "{}", window);
+        }
+
+        assert!(set.intersects(5..6) && set.intersects(..=-10) && set.intersects(20..=20));
+
+        for window in [r!(5..5), r!(8..2), r!(6>..=6)] {
+            assert_eq!(0, set.overlap_count(window.clone()), "{}", window);
+            assert!(!set.intersects(window.clone()), "{}", window);
+        }
+
+        assert!(!set.intersects(r!(20>..=20)) && !set.intersects(6..6) && !set.intersects(8..=10) && !set.intersects(30..));
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt::Debug;
+use std::ops::Deref;
+use std::sync::Arc;
+use crate::{Range, RangeSet};
+
+/// A [`RangeSet`](RangeSet) behind an [`Arc`](Arc), cloning is `O(1)` and the ranges are only copied once a
+/// shared set is modified
+///
+/// All read-only operations are available through [`Deref`](Deref)
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, SharedRangeSet};
+///
+/// let set = SharedRangeSet::from(range_set![r!(0..10)]);
+/// let mut copy = set.clone();
+/// assert!(SharedRangeSet::ptr_eq(&set, &copy));
+///
+/// copy.add(r!(20..30));
+/// assert!(!SharedRangeSet::ptr_eq(&set, &copy));
+/// assert_eq!(range_set![r!(0..10)], *set);
+/// assert_eq!(range_set![r!(0..10), r!(20..30)], *copy);
+/// ```
+#[derive(Debug, Eq, PartialEq)]
+pub struct SharedRangeSet<T: Ord> {
+    inner: Arc<RangeSet<T>>,
+}
+
+impl<T: Ord> Clone for SharedRangeSet<T> {
+    fn clone(&self) -> Self {
+        SharedRangeSet { inner: self.inner.clone() }
+    }
+}
+
+impl<T: Ord + Debug> Default for SharedRangeSet<T> {
+    fn default() -> Self {
+        Self::from(RangeSet::default())
+    }
+}
+
+impl<T: Ord> From<RangeSet<T>> for SharedRangeSet<T> {
+    fn from(value: RangeSet<T>) -> Self {
+        SharedRangeSet { inner: Arc::new(value) }
+    }
+}
+
+impl<T: Ord> Deref for SharedRangeSet<T> {
+    type Target = RangeSet<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T: Ord> SharedRangeSet<T> {
+    /// Returns `true` if both sets share the same ranges
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.inner, &other.inner)
+    }
+}
+
+impl<T: Ord + Clone + Debug> SharedRangeSet<T> {
+    /// Returns a mutable reference to the set, the ranges are copied first if they're shared with another set
+    pub fn make_mut(&mut self) -> &mut RangeSet<T> {
+        Arc::make_mut(&mut self.inner)
+    }
+
+    /// Add a new range to this set, see [`RangeSet::add`](RangeSet::add)
+    pub fn add(&mut self, range: impl Into<Range<T>>) {
+        self.make_mut().add(range)
+    }
+
+    /// Returns the set, the ranges are only copied if they're shared with another set
+    pub fn into_inner(self) -> RangeSet<T> {
+        Arc::unwrap_or_clone(self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, SharedRangeSet};
+
+    #[test]
+    fn shared() {
+        let set = SharedRangeSet::from(range_set![r!(0..10), r!(20..30)]);
+        let mut copy = set.clone();
+        assert!(SharedRangeSet::ptr_eq(&set, &copy));
+        assert!(copy.contains(&5));
+
+        copy.add(r!(10..20));
+        assert!(!SharedRangeSet::ptr_eq(&set, &copy));
+        assert_eq!(range_set![r!(0..10), r!(20..30)], *set);
+
+        // Modifying an unshared set doesn't copy
+        let before = &*copy as *const _;
+        copy.make_mut().add(r!(40..));
+        assert_eq!(before, &*copy as *const _);
+
+        assert_eq!(range_set![r!(0..30), r!(40..)], copy.into_inner());
+        assert_eq!(range_set![r!(0..10), r!(20..30)], set.into_inner());
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt::Debug;
+use std::ops::{Add, Sub};
+use crate::{Bound, Range, RangeSet};
+
+/// A bounded integer type that can be moved by a signed delta without overflowing silently
+pub trait Shift: Ord + Sized {
+    /// The signed type a value can be moved by
+    type Delta;
+
+    /// Move this value by `delta`, or `None` if that overflows
+    fn checked_shift(&self, delta: Self::Delta) -> Option<Self>;
+
+    /// Returns `true` if moving this value by `delta` overflows past the highest value, rather than below the
+    /// lowest value
+    fn overflows_up(delta: &Self::Delta) -> bool;
+
+    /// The lowest value of this type
+    fn lowest() -> Self;
+
+    /// The highest value of this type
+    fn highest() -> Self;
+}
+
+macro_rules! impl_shift {
+    ($add:ident: $($ty:ty => $delta:ty),*) => {
+        $(
+            impl Shift for $ty {
+                type Delta = $delta;
+
+                #[inline]
+                fn checked_shift(&self, delta: $delta) -> Option<Self> {
+                    self.$add(delta)
+                }
+
+                #[inline]
+                fn overflows_up(delta: &$delta) -> bool {
+                    *delta > 0
+                }
+
+                #[inline]
+                fn lowest() -> Self {
+                    <$ty>::MIN
+                }
+
+                #[inline]
+                fn highest() -> Self {
+                    <$ty>::MAX
+                }
+            }
+        )*
+    };
+}
+
+impl_shift!(checked_add: i8 => i8, i16 => i16, i32 => i32, i64 => i64, i128 => i128, isize => isize);
+impl_shift!(checked_add_signed: u8 => i8, u16 => i16, u32 => i32, u64 => i64, u128 => i128, usize => isize);
+
+impl<T: Add<Output=T> + Clone> Add<T> for Range<T> {
+    type Output = Range<T>;
+
+    /// Move both boundaries of this range forward by `delta`, unbounded sides stay unbounded
+    ///
+    /// This overflows like adding the values does, see [`checked_shift_by`](Range::checked_shift_by) for a checked
+    /// variant
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// assert_eq!(r!(14..=18), r!(4..=8) + 10);
+    /// assert_eq!(r!(..18), r!(..8) + 10);
+    /// ```
+    fn add(self, delta: T) -> Range<T> {
+        Range::new(self.start.map(|value| value + delta.clone()), self.end.map(|value| value + delta))
+    }
+}
+
+impl<T: Sub<Output=T> + Clone> Sub<T> for Range<T> {
+    type Output = Range<T>;
+
+    /// Move both boundaries of this range back by `delta`, unbounded sides stay unbounded
+    ///
+    /// This overflows like subtracting the values does, see [`checked_shift_by`](Range::checked_shift_by) for a
+    /// checked variant
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// assert_eq!(r!(0>..4), r!(10>..14) - 10);
+    /// ```
+    fn sub(self, delta: T) -> Range<T> {
+        Range::new(self.start.map(|value| value - delta.clone()), self.end.map(|value| value - delta))
+    }
+}
+
+impl<T: Shift<Delta=D> + Clone, D: Copy> Range<T> {
+    /// Move both boundaries of this range by `delta`, or `None` if either would overflow
+    ///
+    /// Unbounded sides stay unbounded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::r;
+    ///
+    /// assert_eq!(Some(r!(5u8..15)), r!(10u8..20).checked_shift_by(-5));
+    /// assert_eq!(Some(r!(137u8..)), r!(10u8..).checked_shift_by(127));
+    /// assert_eq!(None, r!(10u8..=250).checked_shift_by(6));
+    /// ```
+    pub fn checked_shift_by(&self, delta: D) -> Option<Range<T>> {
+        let shift = |bound: &Bound<T>| match bound {
+            Bound::Included(value) => value.checked_shift(delta).map(Bound::Included),
+            Bound::Excluded(value) => value.checked_shift(delta).map(Bound::Excluded),
+            Bound::Unbounded => Some(Bound::Unbounded),
+        };
+
+        Some(Range::new(shift(&self.start)?, shift(&self.end)?))
+    }
+}
+
+impl<T: Shift<Delta=D> + Clone + Debug, D: Copy> RangeSet<T> {
+    /// Move every range in this set by `delta`, or `None` if any boundary would overflow
+    ///
+    /// Unbounded sides stay unbounded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(10u8..20), r!(250..)];
+    ///
+    /// assert_eq!(Some(range_set![r!(5..15), r!(245..)]), set.checked_shift_by(-5));
+    /// assert_eq!(None, set.checked_shift_by(10));
+    /// ```
+    pub fn checked_shift_by(&self, delta: D) -> Option<RangeSet<T>> {
+        let items = self.items()
+            .map(|range| range.checked_shift_by(delta))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(RangeSet::from(items))
+    }
+
+    /// Move every range in this set by `delta`, clamping the ranges at the lowest and highest value of the type
+    ///
+    /// Ranges that end up entirely outside of the type are dropped, unbounded sides stay unbounded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(10u8..20), r!(240..=250)];
+    ///
+    /// assert_eq!(range_set![r!(20..30), r!(250..=255)], set.shift_by_saturating(10));
+    /// assert_eq!(range_set![r!(0..5), r!(225..=235)], set.shift_by_saturating(-15));
+    /// assert_eq!(range_set![], set.shift_by_saturating(-128).shift_by_saturating(-128));
+    /// ```
+    pub fn shift_by_saturating(&self, delta: D) -> RangeSet<T> {
+        let up = T::overflows_up(&delta);
+        let items: Vec<Range<T>> = self.items()
+            .filter_map(|range| {
+                let start = match &range.start {
+                    Bound::Included(value) | Bound::Excluded(value) => match value.checked_shift(delta) {
+                        Some(shifted) => range.start.clone().map(|_| shifted),
+                        // Everything after the start is past the highest value
+                        None if up => return None,
+                        None => Bound::Included(T::lowest()),
+                    },
+                    Bound::Unbounded => Bound::Unbounded,
+                };
+
+                let end = match &range.end {
+                    Bound::Included(value) | Bound::Excluded(value) => match value.checked_shift(delta) {
+                        Some(shifted) => range.end.clone().map(|_| shifted),
+                        None if up => Bound::Included(T::highest()),
+                        // Everything before the end is below the lowest value
+                        None => return None,
+                    },
+                    Bound::Unbounded => Bound::Unbounded,
+                };
+
+                Some(Range::new(start, end))
+            })
+            .collect();
+
+        RangeSet::from(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeSet};
+
+    #[test]
+    fn shift() {
+        let set = range_set![r!(..-100i8), r!(-10..=10), r!(100>..120)];
+        assert_eq!(Some(range_set![r!(..-93i8), r!(-3..=17), r!(107>..127)]), set.checked_shift_by(7));
+        assert_eq!(None, set.checked_shift_by(8));
+        assert_eq!(None, set.checked_shift_by(-119));
+
+        assert_eq!(range_set![r!(..-80i8), r!(10..=30), r!(120>..=127)], set.shift_by_saturating(20));
+        assert_eq!(range_set![r!(-128i8..=-118), r!(-28>..-8)], set.shift_by_saturating(-128));
+        assert_eq!(range_set![r!(..)], RangeSet::<u64>::unbound().shift_by_saturating(i64::MIN));
+
+        let ports = range_set![r!(0u16..1024), r!(65000..)];
+        assert_eq!(range_set![r!(0..24), r!(64000..)], ports.shift_by_saturating(-1000));
+    }
+
+    #[test]
+    fn range_offset() {
+        let window = r!(0u32..64);
+        assert_eq!(r!(64..128), window.clone() + 64);
+        assert_eq!(window, window.clone() + 64 - 64);
+        assert_eq!(r!(..), crate::Range::<u32>::unbound() + 5);
+        assert_eq!(r!(1.5>..=3.0), r!(0.5>..=2.0) + 1.0);
+
+        assert_eq!(Some(r!(-128i8..=119)), r!(-120i8..=127).checked_shift_by(-8));
+        assert_eq!(None, r!(-120i8..=127).checked_shift_by(1));
+        assert_eq!(None, r!(-120i8..).checked_shift_by(-9));
+        assert_eq!(Some(r!(..=-128i8)), r!(..=-120i8).checked_shift_by(-8));
+    }
+}
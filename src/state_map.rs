@@ -0,0 +1,105 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use crate::{Range, RangeMap, RangeSet, RangeSetBuilder};
+
+/// Tracks which one of a number of mutually exclusive states every part of a domain is in
+///
+/// Every part of the domain is in exactly one state, so moving a range to a state takes it out of its previous one.
+/// The states are kept in a coalescing [`RangeMap`](RangeMap), so neighbouring parts in the same state are merged
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, RangeStateMap};
+///
+/// #[derive(Debug, Clone, PartialEq, Eq)]
+/// enum Chunk { Missing, InFlight, Done }
+///
+/// let mut download = RangeStateMap::new(0..1000, Chunk::Missing);
+/// download.set(300..400, Chunk::Done);
+///
+/// // Only the parts that were still missing are claimed
+/// assert_eq!(range_set![r!(200..300), r!(400..500)], download.transition(200..500, &Chunk::Missing, Chunk::InFlight));
+/// download.transition(200..500, &Chunk::InFlight, Chunk::Done);
+///
+/// assert_eq!(Some(&Chunk::Done), download.state(&250));
+/// assert_eq!(range_set![r!(0..200), r!(500..1000)], download.in_state(&Chunk::Missing));
+/// assert_eq!(None, download.state(&1000));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RangeStateMap<T: Ord, S> {
+    states: RangeMap<T, S>,
+}
+
+impl<T: Ord, S> RangeStateMap<T, S> {
+    /// Returns an iterator over the parts of the domain and their states, in order
+    pub fn iter(&self) -> impl Iterator<Item=(&Range<T>, &S)> {
+        self.states.iter()
+    }
+
+    /// Returns the state `value` is in, or `None` if it's outside of the domain
+    pub fn state<Q: Ord + ?Sized>(&self, value: &Q) -> Option<&S> where T: Borrow<Q> {
+        self.states.get(value)
+    }
+}
+
+impl<T: Ord + Clone + Debug, S: Eq + Clone> RangeStateMap<T, S> {
+    /// Create a tracker over given domain of which every part is in `initial`
+    pub fn new(domain: impl Into<Range<T>>, initial: S) -> Self {
+        let mut states = RangeMap::coalescing();
+        states.insert(domain, initial);
+        RangeStateMap { states }
+    }
+
+    /// Returns the parts of the domain that are in given state
+    pub fn in_state(&self, state: &S) -> RangeSet<T> {
+        let mut builder = RangeSetBuilder::new();
+        for (range, _) in self.states.iter().filter(|(_, current)| *current == state) {
+            builder.push(range.clone());
+        }
+
+        builder.finish()
+    }
+
+    /// Move every part of given range to `state`, parts outside of the domain are ignored
+    pub fn set(&mut self, range: impl Into<Range<T>>, state: S) {
+        self.states.update(range, |current| current.map(|_| state.clone()));
+    }
+
+    /// Move the parts of given range that are in state `from` to state `to`, returns the parts that were moved
+    pub fn transition(&mut self, range: impl Into<Range<T>>, from: &S, to: S) -> RangeSet<T> {
+        let range = range.into();
+        let moved = self.in_state(from).intersection(&range);
+        self.states.update(range, |current| current.map(|current| if current == from { to.clone() } else { current.clone() }));
+        moved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeSet, RangeStateMap};
+
+    #[test]
+    fn state_map() {
+        let mut replica = RangeStateMap::new(0..100, 'm');
+        assert_eq!(vec![(&r!(0..100), &'m')], replica.iter().collect::<Vec<_>>());
+
+        replica.set(r!(90..200), 'd');
+        assert_eq!(None, replica.state(&150));
+        assert_eq!(range_set![r!(90..100)], replica.in_state(&'d'));
+
+        assert_eq!(range_set![r!(0..10)], replica.transition(r!(0..10), &'m', 'f'));
+        assert_eq!(range_set![r!(20..30)], replica.transition(r!(20..30), &'m', 'f'));
+        assert_eq!(range_set![r!(5..10)], replica.transition(r!(5..15), &'f', 'd'));
+        assert_eq!(RangeSet::empty(), replica.transition(r!(5..15), &'f', 'd'));
+        assert_eq!(vec![(&r!(0..5), &'f'), (&r!(5..10), &'d'), (&r!(10..20), &'m'), (&r!(20..30), &'f'), (&r!(30..90), &'m'), (&r!(90..100), &'d')], replica.iter().collect::<Vec<_>>());
+
+        // Moving everything back merges the parts again
+        replica.set(r!(..), 'm');
+        assert_eq!(vec![(&r!(0..100), &'m')], replica.iter().collect::<Vec<_>>());
+        assert_eq!(RangeSet::empty(), replica.in_state(&'d'));
+
+        replica.set(r!(50..50), 'd');
+        assert_eq!(vec![(&r!(0..100), &'m')], replica.iter().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,269 @@
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
+use std::{mem, slice};
+use crate::RangeVec;
+
+/// The storage of the ranges in a set
+///
+/// Most sets hold zero or one range, those are stored inline so they never allocate and clone or compare without
+/// going through a list, only sets with more ranges use a [`RangeVec`](RangeVec)
+#[derive(Debug, Clone, Default)]
+pub(crate) enum Ranges<T> {
+    #[default]
+    Empty,
+    Single(T),
+    Many(RangeVec<T>),
+}
+
+impl<T> Ranges<T> {
+    /// Create new storage with room for given amount of items, only allocating when that's more than one
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        if capacity > 1 {
+            Ranges::Many(RangeVec::with_capacity(capacity))
+        } else {
+            Ranges::Empty
+        }
+    }
+
+    pub(crate) fn push(&mut self, item: T) {
+        match self {
+            Ranges::Empty => *self = Ranges::Single(item),
+            Ranges::Single(_) => {
+                let Ranges::Single(first) = mem::take(self) else {
+                    unreachable!()
+                };
+
+                let mut items = RangeVec::with_capacity(2);
+                items.push(first);
+                items.push(item);
+                *self = Ranges::Many(items);
+            }
+            Ranges::Many(items) => items.push(item),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, index: usize, item: T) {
+        match self {
+            Ranges::Many(items) => items.insert(index, item),
+            _ => {
+                let mut items: RangeVec<T> = self.take().into_iter().collect();
+                items.insert(index, item);
+                *self = Ranges::Many(items);
+            }
+        }
+    }
+
+    /// Remove all items, keeping the allocated list if there is one
+    pub(crate) fn clear(&mut self) {
+        match self {
+            Ranges::Many(items) => items.clear(),
+            _ => *self = Ranges::Empty,
+        }
+    }
+
+    /// Move a list that holds one or no items inline, dropping its allocation
+    pub(crate) fn compact(self) -> Self {
+        match self {
+            Ranges::Many(items) if items.len() <= 1 => items.into(),
+            ranges => ranges,
+        }
+    }
+
+    /// The amount of bytes the list of items has allocated on the heap, inline items don't count
+    pub(crate) fn heap_bytes(&self) -> usize {
+        match self {
+            Ranges::Many(items) if allocated(items) => items.capacity() * mem::size_of::<T>(),
+            _ => 0,
+        }
+    }
+
+    /// Remove all items and return them
+    pub(crate) fn take(&mut self) -> Self {
+        mem::take(self)
+    }
+
+    pub(crate) fn try_reserve(&mut self, additional: usize) -> Result<(), ()> {
+        match self {
+            Ranges::Many(items) => items.try_reserve(additional).map_err(|_| ()),
+            _ if self.len() + additional <= 1 => Ok(()),
+            _ => {
+                let mut items = RangeVec::new();
+                items.try_reserve(self.len() + additional).map_err(|_| ())?;
+                items.extend(self.take());
+                *self = Ranges::Many(items);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "smallvec")]
+fn allocated<T>(items: &RangeVec<T>) -> bool {
+    items.spilled()
+}
+
+#[cfg(not(feature = "smallvec"))]
+fn allocated<T>(items: &RangeVec<T>) -> bool {
+    items.capacity() > 0
+}
+
+impl<T> Deref for Ranges<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        match self {
+            Ranges::Empty => &[],
+            Ranges::Single(item) => slice::from_ref(item),
+            Ranges::Many(items) => items,
+        }
+    }
+}
+
+impl<T> DerefMut for Ranges<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        match self {
+            Ranges::Empty => &mut [],
+            Ranges::Single(item) => slice::from_mut(item),
+            Ranges::Many(items) => items,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Ranges<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Ranges::Single(left), Ranges::Single(right)) => left == right,
+            _ => **self == **other,
+        }
+    }
+}
+
+impl<T: Eq> Eq for Ranges<T> {}
+
+impl<T: Hash> Hash for Ranges<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<T> From<RangeVec<T>> for Ranges<T> {
+    fn from(mut items: RangeVec<T>) -> Self {
+        match items.len() {
+            0 => Ranges::Empty,
+            1 => Ranges::Single(items.pop().unwrap()),
+            _ => Ranges::Many(items),
+        }
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Ranges<T> {
+    fn from(items: [T; N]) -> Self {
+        items.into_iter().collect()
+    }
+}
+
+impl<T> FromIterator<T> for Ranges<T> {
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let mut iter = iter.into_iter();
+        let Some(first) = iter.next() else {
+            return Ranges::Empty;
+        };
+
+        let Some(second) = iter.next() else {
+            return Ranges::Single(first);
+        };
+
+        let mut items = RangeVec::with_capacity(iter.size_hint().0 + 2);
+        items.push(first);
+        items.push(second);
+        items.extend(iter);
+        Ranges::Many(items)
+    }
+}
+
+impl<T> Extend<T> for Ranges<T> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+/// An owning iterator over the items in [`Ranges`](Ranges)
+pub(crate) enum IntoIter<T> {
+    Single(Option<T>),
+    Many(<RangeVec<T> as IntoIterator>::IntoIter),
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        match self {
+            IntoIter::Single(item) => item.take(),
+            IntoIter::Many(items) => items.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            IntoIter::Single(item) => (item.is_some() as usize, Some(item.is_some() as usize)),
+            IntoIter::Many(items) => items.size_hint(),
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for Ranges<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        match self {
+            Ranges::Empty => IntoIter::Single(None),
+            Ranges::Single(item) => IntoIter::Single(Some(item)),
+            Ranges::Many(items) => IntoIter::Many(items.into_iter()),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Ranges<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeSet};
+    use crate::storage::Ranges;
+
+    #[test]
+    fn storage() {
+        let mut ranges = Ranges::default();
+        assert!(matches!(ranges, Ranges::Empty));
+
+        ranges.push(1);
+        assert!(matches!(ranges, Ranges::Single(1)));
+
+        ranges.push(2);
+        assert_eq!([1, 2], *ranges);
+        assert_eq!(Ranges::from([1, 2]), ranges);
+        assert_eq!(vec![1, 2], ranges.take().into_iter().collect::<Vec<_>>());
+
+        assert!(ranges.try_reserve(1).is_ok());
+        assert!(matches!(ranges, Ranges::Empty));
+
+        assert!(matches!(RangeSet::<u32>::new().items, Ranges::Empty));
+        assert!(matches!(range_set![r!(0..4)].items, Ranges::Single(_)));
+        assert!(matches!(range_set![r!(0..4), r!(2..6)].items, Ranges::Single(_)));
+        assert!(matches!(range_set![r!(0..4)].union(&range_set![r!(6..8)]).difference(&range_set![r!(..5)]).items, Ranges::Single(_)));
+    }
+}
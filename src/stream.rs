@@ -0,0 +1,84 @@
+use std::fmt::Debug;
+use std::future;
+use std::slice;
+use futures_util::stream::{self, Iter, Stream, StreamExt};
+use crate::{BufferedBuilder, Discrete, Range, RangeSet, Values};
+
+impl<T: Ord + Debug> RangeSet<T> {
+    /// Returns a stream yielding the ranges in this set, which are always ready
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use futures_util::StreamExt;
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// # futures_executor::block_on(async {
+    /// let set = range_set![r!(0..4), r!(10..)];
+    ///
+    /// assert_eq!(vec![&r!(0..4), &r!(10..)], set.range_stream().collect::<Vec<_>>().await);
+    /// # });
+    /// ```
+    pub fn range_stream(&self) -> Iter<slice::Iter<'_, Range<T>>> {
+        stream::iter(self.items.iter())
+    }
+
+    /// Build a set from the ranges yielded by given stream, which can come in any order
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use futures_util::stream;
+    /// use eater_rangeset::{r, range_set, RangeSet};
+    ///
+    /// # futures_executor::block_on(async {
+    /// let chunks = stream::iter([r!(4..8), r!(0..4), r!(10..12)]);
+    ///
+    /// assert_eq!(range_set![r!(0..8), r!(10..12)], RangeSet::from_stream(chunks).await);
+    /// # });
+    /// ```
+    pub async fn from_stream<S: Stream<Item=Range<T>>>(stream: S) -> RangeSet<T> {
+        let mut builder = BufferedBuilder::new();
+        builder.push_stream(stream).await;
+        builder.finish()
+    }
+}
+
+impl<T: Discrete + Clone + Debug> RangeSet<T> {
+    /// Returns a stream yielding every value inside of this set, see [`values`](RangeSet::values)
+    pub fn value_stream(&self) -> Iter<Values<'_, T>> {
+        stream::iter(self.values())
+    }
+}
+
+impl<T: Ord + Debug> BufferedBuilder<T> {
+    /// Push every range yielded by given stream to the set being built
+    pub async fn push_stream<S: Stream<Item=Range<T>>>(&mut self, stream: S) {
+        stream.for_each(|range| {
+            self.push(range);
+            future::ready(())
+        }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_executor::block_on;
+    use futures_util::stream;
+    use futures_util::StreamExt;
+    use crate::{r, range_set, BufferedBuilder, RangeSet};
+
+    #[test]
+    fn stream() {
+        let set = range_set![r!(..=-126i8), r!(3>..4), r!(10..12), r!(126..)];
+        assert_eq!(set.items().collect::<Vec<_>>(), block_on(set.range_stream().collect::<Vec<_>>()));
+        assert_eq!(set.values().collect::<Vec<_>>(), block_on(set.value_stream().collect::<Vec<_>>()));
+
+        let mut builder = BufferedBuilder::new();
+        block_on(builder.push_stream(stream::iter([r!(10..20), r!(..0)])));
+        block_on(builder.push_stream(stream::iter([r!(15..30)])));
+        assert_eq!(range_set![r!(..0), r!(10..30)], builder.finish());
+
+        assert_eq!(RangeSet::<u8>::empty(), block_on(RangeSet::from_stream(stream::empty())));
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt::Write;
+use crate::{Bound, RangeSet};
+
+/// Renders the covered segments of one or more sets within a window as an SVG timeline, one row per set
+///
+/// Values are placed on the timeline by a function mapping them to a position, so any type can be drawn, e.g.
+/// timestamps as seconds since the epoch. Unbounded ends and ranges reaching outside of the window are cut off at
+/// the edges of the window
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, Timeline};
+///
+/// let buffered = range_set![r!(0u32..30), r!(45..60)];
+/// let played = range_set![r!(0u32..20)];
+///
+/// let svg = Timeline::new(0, 100, |value: &u32| *value as f64)
+///     .with_width(200)
+///     .layer(&buffered, "steelblue")
+///     .layer(&played, "orange")
+///     .render();
+///
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.contains(r#"<rect x="90" y="0" width="30" height="20"/>"#));
+/// ```
+pub struct Timeline<'a, T: Ord, F> {
+    start: T,
+    end: T,
+    position: F,
+    width: u32,
+    row_height: u32,
+    background: String,
+    layers: Vec<(&'a RangeSet<T>, String)>,
+}
+
+impl<'a, T: Ord, F: Fn(&T) -> f64> Timeline<'a, T, F> {
+    /// Create a new timeline showing the window from `start` to `end`, placing values with given function
+    pub fn new(start: T, end: T, position: F) -> Self {
+        Timeline {
+            start,
+            end,
+            position,
+            width: 800,
+            row_height: 20,
+            background: "#eee".to_string(),
+            layers: vec![],
+        }
+    }
+
+    /// Set the width of the image, defaults to 800
+    pub fn with_width(mut self, width: u32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the height of every row, defaults to 20
+    pub fn with_row_height(mut self, row_height: u32) -> Self {
+        self.row_height = row_height;
+        self
+    }
+
+    /// Set the color of the part of a row that isn't covered, defaults to `#eee`
+    pub fn with_background(mut self, color: impl Into<String>) -> Self {
+        self.background = color.into();
+        self
+    }
+
+    /// Add a row showing given set in given color, which can be any SVG color
+    pub fn layer(mut self, set: &'a RangeSet<T>, color: impl Into<String>) -> Self {
+        self.layers.push((set, color.into()));
+        self
+    }
+
+    /// Render the timeline as SVG document
+    pub fn render(&self) -> String {
+        let height = self.row_height * self.layers.len() as u32;
+        let mut svg = String::new();
+        let _ = write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            self.width, height,
+        );
+
+        for (row, (set, color)) in self.layers.iter().enumerate() {
+            let y = self.row_height * row as u32;
+            let _ = write!(
+                svg,
+                r#"<rect x="0" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                y, self.width, self.row_height, escape(&self.background),
+            );
+
+            let _ = write!(svg, r#"<g fill="{}">"#, escape(color));
+            for (x, width) in set.items.iter().filter_map(|range| self.span(&range.start, &range.end)) {
+                let _ = write!(svg, r#"<rect x="{}" y="{}" width="{}" height="{}"/>"#, x, y, width, self.row_height);
+            }
+
+            svg.push_str("</g>");
+        }
+
+        svg.push_str("</svg>");
+        svg
+    }
+
+    /// The x coordinate and width of the part of a range inside of the window, rounded to 2 decimals
+    fn span(&self, start: &Bound<T>, end: &Bound<T>) -> Option<(f64, f64)> {
+        let window_start = (self.position)(&self.start);
+        let window_end = (self.position)(&self.end);
+        let scale = self.width as f64 / (window_end - window_start);
+
+        let start = match start {
+            Bound::Included(value) | Bound::Excluded(value) => (self.position)(value).max(window_start),
+            Bound::Unbounded => window_start,
+        };
+
+        let end = match end {
+            Bound::Included(value) | Bound::Excluded(value) => (self.position)(value).min(window_end),
+            Bound::Unbounded => window_end,
+        };
+
+        let round = |value: f64| (value * 100.0).round() / 100.0;
+        (end > start).then(|| (round((start - window_start) * scale), round((end - start) * scale)))
+    }
+}
+
+/// Escapes given text for use in an attribute
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, Timeline};
+
+    #[test]
+    fn timeline() {
+        let set = range_set![r!(..-5i64), r!(10..=15), r!(90..200)];
+        let svg = Timeline::new(0, 100, |value: &i64| *value as f64)
+            .with_width(50)
+            .with_row_height(10)
+            .with_background("none")
+            .layer(&set, "\"red\"")
+            .layer(&range_set![r!(..)], "blue")
+            .render();
+
+        assert_eq!(concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="50" height="20" viewBox="0 0 50 20">"#,
+            r#"<rect x="0" y="0" width="50" height="10" fill="none"/>"#,
+            r#"<g fill="&quot;red&quot;"><rect x="5" y="0" width="2.5" height="10"/><rect x="45" y="0" width="5" height="10"/></g>"#,
+            r#"<rect x="0" y="10" width="50" height="10" fill="none"/>"#,
+            r#"<g fill="blue"><rect x="0" y="10" width="50" height="10"/></g>"#,
+            "</svg>",
+        ), svg);
+
+        assert_eq!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="800" height="0" viewBox="0 0 800 0"></svg>"#, Timeline::new(0, 1, |value: &i64| *value as f64).render());
+    }
+}
@@ -0,0 +1,261 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::fmt::Debug;
+use std::iter::Peekable;
+use crate::{Bound, BoundExt, PositionalBound, Range, RangeSet, RangeSetBuilder, RangeSetRef};
+
+/// Split the combined span of given sets into maximal segments in which the membership doesn't change
+///
+/// Every segment is yielded together with a bitmask of which sets cover it, bit `n` being set when the `n`th set
+/// covers the segment, segments in between covered by none of the sets are included with an empty mask
+///
+/// # Panics
+///
+/// If more than 64 sets are given
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, refine};
+///
+/// let a = range_set![r!(0..10)];
+/// let b = range_set![r!(5..15)];
+/// let c = range_set![r!(20..25)];
+///
+/// let segments: Vec<_> = refine([&a, &b, &c]).collect();
+/// assert_eq!(vec![
+///     (r!((&0)..&5), 0b001),
+///     (r!((&5)..&10), 0b011),
+///     (r!((&10)..&15), 0b010),
+///     (r!((&15)..&20), 0b000),
+///     (r!((&20)..&25), 0b100),
+/// ], segments);
+/// ```
+pub fn refine<'a, T: Ord + Debug + 'a>(sets: impl IntoIterator<Item=&'a RangeSet<T>>) -> impl Iterator<Item=(Range<&'a T>, u64)> {
+    Sweep::new(sets.into_iter().map(|set| set.as_ref().ref_items()))
+}
+
+/// Returns the set of values covered by at least `k` of given sets
+///
+/// The boundaries of all sets are swept once in order using a heap, so there's no limit on the amount of sets
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{covered_by_at_least, r, range_set};
+///
+/// let a = range_set![r!(0..10)];
+/// let b = range_set![r!(5..15)];
+/// let c = range_set![r!(8..20)];
+///
+/// assert_eq!(range_set![r!(5..15)], covered_by_at_least(&[&a, &b, &c], 2));
+/// assert_eq!(range_set![r!(8..10)], covered_by_at_least(&[&a, &b, &c], 3));
+/// ```
+pub fn covered_by_at_least<T: Ord + Clone + Debug>(sets: &[&RangeSet<T>], k: usize) -> RangeSet<T> {
+    if k == 0 {
+        return RangeSet::unbound();
+    }
+
+    let mut inputs: Vec<_> = sets.iter()
+        .map(|set| Boundaries { iter: set.as_ref().ref_items(), end: None })
+        .collect();
+
+    let mut heap = BinaryHeap::with_capacity(inputs.len());
+    for (index, input) in inputs.iter_mut().enumerate() {
+        if let Some(bound) = input.next() {
+            heap.push(entry(bound, index));
+        }
+    }
+
+    let mut builder = RangeSetBuilder::new();
+    let mut depth = 0;
+    let mut start = None;
+
+    while let Some(Reverse((bound, _, index))) = heap.pop() {
+        match bound {
+            PositionalBound::Start(bound) => {
+                depth += 1;
+                if depth == k {
+                    start = Some(bound.cloned());
+                }
+            }
+            PositionalBound::End(bound) => {
+                if depth == k {
+                    let start = start.take().expect("a range is open once the depth is reached");
+                    builder.push(Range::new(start, bound.cloned()));
+                }
+
+                depth -= 1;
+            }
+        }
+
+        if let Some(bound) = inputs[index].next() {
+            heap.push(entry(bound, index));
+        }
+    }
+
+    builder.finish()
+}
+
+/// A heap entry for given boundary of the input at given index
+///
+/// Ends are taken before starts at the same position, as a range ending where another starts doesn't overlap it
+#[allow(clippy::type_complexity)]
+fn entry<T>(bound: PositionalBound<&T>, index: usize) -> Reverse<(PositionalBound<&T>, bool, usize)> {
+    let is_start = matches!(bound, PositionalBound::Start(_));
+    Reverse((bound, is_start, index))
+}
+
+/// Iterator over the ranges of a set as `Range<&T>`
+pub(crate) type RefItems<'a, T> = std::iter::Map<std::slice::Iter<'a, Range<T>>, fn(&'a Range<T>) -> Range<&'a T>>;
+
+impl<'a, T: Ord + Debug> RangeSetRef<'a, T> {
+    /// Iterate over the ranges of this set, borrowing the boundaries
+    pub(crate) fn ref_items(&self) -> RefItems<'a, T> {
+        self.as_slice().iter().map(Range::as_ref as fn(&Range<T>) -> Range<&T>)
+    }
+}
+
+/// Turns a sorted iterator of ranges into a sorted iterator of boundaries
+pub(crate) struct Boundaries<'a, T: Ord, I> {
+    iter: I,
+    end: Option<Bound<&'a T>>,
+}
+
+impl<'a, T: Ord, I: Iterator<Item=Range<&'a T>>> Iterator for Boundaries<'a, T, I> {
+    type Item = PositionalBound<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(end) = self.end.take() {
+            return Some(PositionalBound::End(end));
+        }
+
+        let (start, end) = self.iter.next()?.into_inner();
+        self.end = Some(end);
+        Some(PositionalBound::Start(start))
+    }
+}
+
+/// A sweep over the boundaries of multiple sorted lists of ranges
+///
+/// Yields every maximal segment between the first and the last boundary in which the membership doesn't change,
+/// together with a bitmask of which inputs cover that segment, segments covered by no input are included
+pub(crate) struct Sweep<'a, T: Ord, I: Iterator<Item=Range<&'a T>>> {
+    inputs: Vec<Peekable<Boundaries<'a, T, I>>>,
+    position: Option<PositionalBound<&'a T>>,
+    mask: u64,
+}
+
+impl<'a, T: Ord + Debug, I: Iterator<Item=Range<&'a T>>> Sweep<'a, T, I> {
+    pub fn new(inputs: impl IntoIterator<Item=I>) -> Self {
+        let inputs: Vec<_> = inputs.into_iter()
+            .map(|iter| Boundaries { iter, end: None }.peekable())
+            .collect();
+
+        assert!(inputs.len() <= 64, "a sweep supports at most 64 inputs");
+
+        Sweep {
+            inputs,
+            position: None,
+            mask: 0,
+        }
+    }
+}
+
+impl<'a, T: Ord + Debug, I: Iterator<Item=Range<&'a T>>> Iterator for Sweep<'a, T, I> {
+    type Item = (Range<&'a T>, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut next: Option<PositionalBound<&'a T>> = None;
+            for input in &mut self.inputs {
+                if let Some(bound) = input.peek() {
+                    if next.as_ref().is_none_or(|next| bound < next) {
+                        next = Some(*bound);
+                    }
+                }
+            }
+
+            let next = next?;
+            let mask = self.mask;
+
+            for (index, input) in self.inputs.iter_mut().enumerate() {
+                while input.next_if(|bound| bound.cmp(&next) == Ordering::Equal).is_some() {
+                    self.mask ^= 1 << index;
+                }
+            }
+
+            let Some(previous) = self.position.replace(next) else {
+                continue;
+            };
+
+            let start = match previous {
+                PositionalBound::Start(bound) => bound,
+                PositionalBound::End(bound) => bound.invert(),
+            };
+
+            let end = match next {
+                PositionalBound::Start(bound) => bound.invert(),
+                PositionalBound::End(bound) => bound,
+            };
+
+            return Some((Range::new(start, end), mask));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{covered_by_at_least, r, range_set, refine, RangeSet};
+    use crate::sweep::Sweep;
+
+    #[test]
+    fn sweep() {
+        let left: RangeSet<usize> = range_set![r!(..4), r!(6..=10)];
+        let right = range_set![r!(2..6), r!(10..12)];
+
+        let segments: Vec<_> = Sweep::new([left.as_ref().ref_items(), right.as_ref().ref_items()]).collect();
+        assert_eq!(vec![
+            (r!(..&2), 0b01),
+            (r!((&2)..&4), 0b11),
+            (r!((&4)..&6), 0b10),
+            (r!((&6)..&10), 0b01),
+            (r!((&10)..=&10), 0b11),
+            (r!((&10)>..&12), 0b10),
+        ], segments);
+
+        let empty: RangeSet<usize> = range_set![];
+        assert_eq!(0, Sweep::new([empty.as_ref().ref_items(), empty.as_ref().ref_items()]).count());
+    }
+
+    #[test]
+    fn refine_sets() {
+        let a: RangeSet<u32> = range_set![r!(..=3), r!(8..)];
+        let b = range_set![r!(2..9)];
+
+        let segments: Vec<_> = refine([&a, &b]).collect();
+        assert_eq!(vec![
+            (r!(..&2), 0b01),
+            (r!((&2)..=&3), 0b11),
+            (r!((&3)>..&8), 0b10),
+            (r!((&8)..&9), 0b11),
+            (r!((&9)..), 0b01),
+        ], segments);
+
+        assert_eq!(0, refine::<u32>([]).count());
+    }
+
+    #[test]
+    fn at_least() {
+        let a: RangeSet<u32> = range_set![r!(..=3), r!(8..)];
+        let b = range_set![r!(2..9)];
+        let c = range_set![r!(3..4), r!(6..8)];
+
+        assert_eq!(RangeSet::unbound(), covered_by_at_least(&[&a, &b, &c], 0));
+        assert_eq!(range_set![r!(..)], covered_by_at_least(&[&a, &b, &c], 1));
+        assert_eq!(range_set![r!(2..4), r!(6..9)], covered_by_at_least(&[&a, &b, &c], 2));
+        assert_eq!(range_set![r!(3..=3)], covered_by_at_least(&[&a, &b, &c], 3));
+        assert_eq!(RangeSet::empty(), covered_by_at_least(&[&a, &b, &c], 4));
+        assert_eq!(a, covered_by_at_least(&[&a], 1));
+    }
+}
@@ -0,0 +1,97 @@
+use std::time::Duration;
+use crate::{Bound, Range, RangeSet};
+
+/// A view over a set of times mirroring the `TimeRanges` interface of HTML media elements, created by
+/// [`RangeSet::time_ranges`](RangeSet::time_ranges)
+///
+/// Times are given in seconds, an unbounded start counts as zero and an unbounded end as infinity like the end of a
+/// live stream. Whether boundaries are inclusive or exclusive isn't taken into account
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use eater_rangeset::{r, range_set};
+///
+/// let second = Duration::from_secs(1);
+/// let buffered = range_set![r!((Duration::ZERO)..second * 10), r!((second * 30)..second * 45)];
+/// let ranges = buffered.time_ranges();
+///
+/// assert_eq!(2, ranges.length());
+/// assert_eq!(Some(30.0), ranges.start(1));
+/// assert_eq!(Some(45.0), ranges.end(1));
+/// assert_eq!(None, ranges.end(2));
+/// assert_eq!(25.0, ranges.buffered_percentage(second * 100));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TimeRanges<'a> {
+    set: &'a RangeSet<Duration>,
+}
+
+impl RangeSet<Duration> {
+    /// Returns a view over this set with the interface of HTML `TimeRanges`
+    pub fn time_ranges(&self) -> TimeRanges<'_> {
+        TimeRanges { set: self }
+    }
+}
+
+impl<'a> TimeRanges<'a> {
+    /// The amount of ranges
+    pub fn length(&self) -> usize {
+        self.set.items.len()
+    }
+
+    /// The start of the range at given index in seconds, or `None` if there's no range at that index
+    pub fn start(&self, index: usize) -> Option<f64> {
+        let range = self.set.items.get(index)?;
+        Some(match range.start() {
+            Bound::Included(start) | Bound::Excluded(start) => start.as_secs_f64(),
+            Bound::Unbounded => 0.0,
+        })
+    }
+
+    /// The end of the range at given index in seconds, or `None` if there's no range at that index
+    pub fn end(&self, index: usize) -> Option<f64> {
+        let range = self.set.items.get(index)?;
+        Some(match range.end() {
+            Bound::Included(end) | Bound::Excluded(end) => end.as_secs_f64(),
+            Bound::Unbounded => f64::INFINITY,
+        })
+    }
+
+    /// The percentage of a media of given duration that's covered, parts past the duration don't count
+    pub fn buffered_percentage(&self, duration: Duration) -> f64 {
+        if duration.is_zero() {
+            return 0.0;
+        }
+
+        let covered: Duration = self.set.intersection(&Range::new(Bound::Included(Duration::ZERO), Bound::Excluded(duration)))
+            .measure()
+            .unwrap_or_default();
+
+        covered.as_secs_f64() / duration.as_secs_f64() * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::{r, range_set, RangeSet};
+
+    #[test]
+    fn time_ranges() {
+        let millis = Duration::from_millis;
+        let set = range_set![r!(..millis(500)), r!((millis(1500))>..=millis(2500)), r!((millis(4000))..)];
+        let ranges = set.time_ranges();
+        assert_eq!(3, ranges.length());
+        let bounds: Vec<_> = (0..ranges.length()).map(|index| (ranges.start(index).unwrap(), ranges.end(index).unwrap())).collect();
+        assert_eq!(vec![(0.0, 0.5), (1.5, 2.5), (4.0, f64::INFINITY)], bounds);
+        assert_eq!(None, ranges.start(3));
+
+        assert_eq!(37.5, ranges.buffered_percentage(millis(4000)));
+        assert_eq!(50.0, ranges.buffered_percentage(millis(5000)));
+        assert_eq!(100.0, ranges.buffered_percentage(millis(400)));
+        assert_eq!(0.0, ranges.buffered_percentage(Duration::ZERO));
+        assert_eq!(0.0, RangeSet::empty().time_ranges().buffered_percentage(millis(1000)));
+    }
+}
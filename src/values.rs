@@ -0,0 +1,534 @@
+use std::fmt::Debug;
+use std::slice::Iter;
+use crate::{Bound, Discrete, Range, RangeSet, RangeSetRef, RangeVec};
+
+/// The first and last value inside of given range, or `None` if it holds no values
+pub(crate) fn value_bounds<T: Discrete + Clone>(range: &Range<T>) -> Option<(T, T)> {
+    let first = range.first_value()?;
+    let last = range.last_value()?;
+    (first <= last).then_some((first, last))
+}
+
+/// Iterator over every value inside of a set, created by [`RangeSet::values`](RangeSet::values)
+#[derive(Debug, Clone)]
+pub struct Values<'a, T: Ord> {
+    ranges: Iter<'a, Range<T>>,
+    /// The next value and the last value of the range being iterated over
+    current: Option<(T, T)>,
+    /// The amount of values left saturating at `u128::MAX`, `None` if the distance between values is unknown
+    remaining: Option<u128>,
+}
+
+impl<'a, T: Discrete + Clone> Values<'a, T> {
+    /// Create an iterator over the values from `current` followed by the values inside of given ranges
+    pub(crate) fn new(ranges: Iter<'a, Range<T>>, current: Option<(T, T)>) -> Self {
+        let count = |(first, last): &(T, T)| Some(T::steps_between(first, last)?.saturating_add(1));
+        let remaining = ranges.clone()
+            .filter_map(value_bounds)
+            .fold(current.as_ref().map_or(Some(0), count), |total, bounds| Some(total?.saturating_add(count(&bounds)?)));
+
+        Values { ranges, current, remaining }
+    }
+
+    /// The amount of values left, or `None` if there are more than `usize::MAX` or they can't be counted because
+    /// [`steps_between`](Discrete::steps_between) returns `None`
+    ///
+    /// The amount is counted from the bounds of the ranges when the iterator is created, so this takes constant time
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, RangeSet};
+    ///
+    /// assert_eq!(Some(5), range_set![r!(1..=3), r!(7>..10)].values().exact_len());
+    /// assert_eq!(None, RangeSet::<u128>::unbound().values().exact_len());
+    /// ```
+    pub fn exact_len(&self) -> Option<usize> {
+        usize::try_from(self.remaining?).ok()
+    }
+
+    /// Turn this into an iterator implementing [`ExactSizeIterator`](ExactSizeIterator), or `None` if the amount of
+    /// values left isn't known, see [`exact_len`](Values::exact_len)
+    ///
+    /// `Values` itself can't implement it, as an unbounded set of a type like `u64` holds more values than fit in a
+    /// `usize`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set, RangeSet};
+    ///
+    /// let set = range_set![r!(1..=3), r!(7>..10)];
+    ///
+    /// assert_eq!(5, set.values().exact().unwrap().len());
+    /// assert!(RangeSet::<u64>::unbound().values().exact().is_none());
+    /// ```
+    pub fn exact(self) -> Option<ExactValues<'a, T>> {
+        let remaining = self.exact_len()?;
+        Some(ExactValues { values: self, remaining })
+    }
+}
+
+impl<'a, T: Discrete + Clone> Iterator for Values<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if let Some((next, last)) = self.current.take() {
+                if next < last {
+                    self.current = next.successor().map(|after| (after, last));
+                }
+
+                self.remaining = self.remaining.map(|remaining| remaining.saturating_sub(1));
+                return Some(next);
+            }
+
+            self.current = value_bounds(self.ranges.next()?);
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining.map(usize::try_from) {
+            Some(Ok(remaining)) => (remaining, Some(remaining)),
+            Some(Err(_)) => (usize::MAX, None),
+            None => (self.current.is_some() as usize, None),
+        }
+    }
+}
+
+/// Iterator over every value inside of a set holding no more than `usize::MAX` values, created by
+/// [`Values::exact`](Values::exact)
+#[derive(Debug, Clone)]
+pub struct ExactValues<'a, T: Ord> {
+    values: Values<'a, T>,
+    remaining: usize,
+}
+
+impl<'a, T: Discrete + Clone> Iterator for ExactValues<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let next = self.values.next()?;
+        self.remaining -= 1;
+        Some(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T: Discrete + Clone> ExactSizeIterator for ExactValues<'a, T> {}
+
+/// Iterator over every value inside of a single range, created by iterating over a [`Range`](Range)
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::r;
+///
+/// let mut values = Vec::new();
+/// for value in r!(3>..=7) {
+///     values.push(value);
+/// }
+///
+/// assert_eq!(vec![4, 5, 6, 7], values);
+/// assert_eq!(vec![255, 254], r!(254u8..).into_iter().rev().collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RangeValues<T> {
+    /// The first and last value that haven't been yielded yet
+    bounds: Option<(T, T)>,
+}
+
+impl<T: Discrete + Clone> Iterator for RangeValues<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let (next, last) = self.bounds.take()?;
+        if next < last {
+            self.bounds = next.successor().map(|after| (after, last));
+        }
+
+        Some(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let Some((first, last)) = &self.bounds else {
+            return (0, Some(0));
+        };
+
+        match T::steps_between(first, last).map(|steps| usize::try_from(steps).ok()?.checked_add(1)) {
+            Some(Some(remaining)) => (remaining, Some(remaining)),
+            Some(None) => (usize::MAX, None),
+            None => (1, None),
+        }
+    }
+}
+
+impl<T: Discrete + Clone> DoubleEndedIterator for RangeValues<T> {
+    fn next_back(&mut self) -> Option<T> {
+        let (first, previous) = self.bounds.take()?;
+        if first < previous {
+            self.bounds = previous.predecessor().map(|before| (first, before));
+        }
+
+        Some(previous)
+    }
+}
+
+impl<T: Discrete + Clone> IntoIterator for Range<T> {
+    type Item = T;
+    type IntoIter = RangeValues<T>;
+
+    fn into_iter(self) -> RangeValues<T> {
+        RangeValues { bounds: value_bounds(&self) }
+    }
+}
+
+/// Iterator over the values inside of a set in blocks of up to a fixed size, created by
+/// [`RangeSet::values_chunks`](RangeSet::values_chunks)
+#[derive(Debug, Clone)]
+pub struct ValuesChunks<'a, T: Ord> {
+    values: Values<'a, T>,
+    size: usize,
+}
+
+impl<'a, T: Discrete + Clone> ValuesChunks<'a, T> {
+    /// The amount of chunks left, or `None` if the amount of values left can't be counted, see
+    /// [`Values::exact_len`](Values::exact_len)
+    pub fn exact_len(&self) -> Option<usize> {
+        Some(self.values.exact_len()?.div_ceil(self.size))
+    }
+}
+
+impl<'a, T: Discrete + Clone> Iterator for ValuesChunks<'a, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let chunk: Vec<T> = self.values.by_ref().take(self.size).collect();
+        (!chunk.is_empty()).then_some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.values.size_hint();
+        (lower.div_ceil(self.size), upper.map(|upper| upper.div_ceil(self.size)))
+    }
+}
+
+/// A cursor over the values inside of a set that can move in both directions and seek, created by
+/// [`RangeSet::cursor`](RangeSet::cursor)
+///
+/// Like the cursors of [`LinkedList`](std::collections::LinkedList) there is a "ghost" position between the last
+/// and first value, which is where the cursor starts, moving forward from it goes to the first value and moving
+/// backward to the last value
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set};
+///
+/// let ids = range_set![r!(1..=3), r!(10..20), r!(30..)];
+/// let mut cursor = ids.cursor();
+///
+/// assert_eq!(Some(1), cursor.next());
+/// assert_eq!(Some(&10), cursor.seek_to(&4));
+/// assert_eq!(Some(3), cursor.prev());
+/// assert_eq!(Some(&19), cursor.seek_to(&19));
+/// assert_eq!(Some(30), cursor.next());
+/// assert_eq!(Some(&u64::MAX), cursor.seek_to(&u64::MAX));
+/// assert_eq!(None, cursor.next());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ValueCursor<'a, T: Ord> {
+    items: &'a [Range<T>],
+    /// The index of the range holding the current value and the value itself, `None` at the ghost position
+    current: Option<(usize, T)>,
+}
+
+impl<'a, T: Discrete + Clone> ValueCursor<'a, T> {
+    /// Returns the value the cursor is at, or `None` at the ghost position
+    #[inline]
+    pub fn current(&self) -> Option<&T> {
+        self.current.as_ref().map(|(_, value)| value)
+    }
+
+    /// Move to the first value in the set that's not lower than `target`, skipping gaps with a binary search
+    ///
+    /// Returns the new value, or `None` when there is no such value, in which case the cursor is moved to the ghost
+    /// position
+    pub fn seek_to(&mut self, target: &T) -> Option<&T> {
+        let index = self.items.partition_point(|range| range.end_pos() < target);
+        self.current = match self.items.get(index) {
+            Some(range) if range.start_pos() < target => Some((index, target.clone())),
+            _ => self.first_from(index),
+        };
+
+        self.current()
+    }
+
+    /// Move to the next value and return it, or `None` when moving past the last value to the ghost position
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<T> {
+        self.current = match self.current.take() {
+            None => self.first_from(0),
+            Some((index, value)) => {
+                let in_range = value_bounds(&self.items[index]).is_some_and(|(_, last)| value < last);
+                match value.successor() {
+                    Some(next) if in_range => Some((index, next)),
+                    _ => self.first_from(index + 1),
+                }
+            }
+        };
+
+        self.current.as_ref().map(|(_, value)| value.clone())
+    }
+
+    /// Move to the previous value and return it, or `None` when moving before the first value to the ghost position
+    pub fn prev(&mut self) -> Option<T> {
+        self.current = match self.current.take() {
+            None => self.last_before(self.items.len()),
+            Some((index, value)) => {
+                let in_range = value_bounds(&self.items[index]).is_some_and(|(first, _)| value > first);
+                match value.predecessor() {
+                    Some(prev) if in_range => Some((index, prev)),
+                    _ => self.last_before(index),
+                }
+            }
+        };
+
+        self.current.as_ref().map(|(_, value)| value.clone())
+    }
+
+    /// The first value in the ranges starting at given index
+    fn first_from(&self, index: usize) -> Option<(usize, T)> {
+        self.items.iter().enumerate().skip(index)
+            .find_map(|(index, range)| value_bounds(range).map(|(first, _)| (index, first)))
+    }
+
+    /// The last value in the ranges before given index
+    fn last_before(&self, index: usize) -> Option<(usize, T)> {
+        self.items[..index].iter().enumerate().rev()
+            .find_map(|(index, range)| value_bounds(range).map(|(_, last)| (index, last)))
+    }
+}
+
+impl<'a, T: Discrete + Clone + Debug> RangeSetRef<'a, T> {
+    /// Returns an iterator over every value inside of this set, see [`RangeSet::values`](RangeSet::values)
+    pub fn values(&self) -> Values<'a, T> {
+        Values::new(self.items(), None)
+    }
+
+    /// Returns an iterator over the values inside of this set in blocks of up to `size` values, see
+    /// [`RangeSet::values_chunks`](RangeSet::values_chunks)
+    pub fn values_chunks(&self, size: usize) -> ValuesChunks<'a, T> {
+        assert!(size > 0, "chunk size must be greater than zero");
+        ValuesChunks { values: self.values(), size }
+    }
+
+    /// Returns a cursor over the values inside of this set, see [`RangeSet::cursor`](RangeSet::cursor)
+    pub fn cursor(&self) -> ValueCursor<'a, T> {
+        ValueCursor { items: self.as_slice(), current: None }
+    }
+}
+
+impl<T: Discrete + Clone + Debug> RangeSet<T> {
+    /// Returns an iterator over every value inside of this set, in order
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let set = range_set![r!(1..=3), r!(7>..10)];
+    ///
+    /// assert_eq!(vec![1, 2, 3, 8, 9], set.values().collect::<Vec<_>>());
+    /// ```
+    pub fn values(&self) -> Values<'_, T> {
+        self.as_ref().values()
+    }
+
+    /// Returns an iterator over the values inside of this set in blocks of up to `size` values, every block but the
+    /// last is full and can hold values from multiple ranges
+    ///
+    /// # Panics
+    ///
+    /// If `size` is 0
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let ids = range_set![r!(1..=3), r!(10..13), r!(20..=20)];
+    ///
+    /// assert_eq!(vec![vec![1, 2, 3, 10], vec![11, 12, 20]], ids.values_chunks(4).collect::<Vec<_>>());
+    /// ```
+    pub fn values_chunks(&self, size: usize) -> ValuesChunks<'_, T> {
+        self.as_ref().values_chunks(size)
+    }
+
+    /// Returns a cursor over the values inside of this set, that can seek to a value and move in both directions
+    pub fn cursor(&self) -> ValueCursor<'_, T> {
+        self.as_ref().cursor()
+    }
+
+    /// Remove every value from this set for which given function returns `false`, splitting ranges around removed
+    /// values
+    ///
+    /// This calls the function for every value inside of this set, so it takes time proportional to the amount of
+    /// values rather than the amount of ranges and never ends for unbounded sets of large types
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eater_rangeset::{r, range_set};
+    ///
+    /// let blocked = [3, 4, 12];
+    /// let mut ids = range_set![r!(0..10), r!(10..=12)];
+    /// ids.retain_values(|id| !blocked.contains(id));
+    ///
+    /// assert_eq!(range_set![r!(0..=2), r!(5..10), r!(10..=11)], ids);
+    /// ```
+    pub fn retain_values(&mut self, mut keep: impl FnMut(&T) -> bool) {
+        let mut items = RangeVec::with_capacity(self.items.len());
+        for range in self.items.iter() {
+            let Some((first, last)) = value_bounds(range) else {
+                continue;
+            };
+
+            // Runs touching the edges of the range keep its original bounds
+            let bounds = |(start, end): (T, T)| Range::new(
+                if start == first { range.start.clone() } else { Bound::Included(start) },
+                if end == last { range.end.clone() } else { Bound::Included(end) },
+            );
+
+            let mut run: Option<(T, T)> = None;
+            for value in (RangeValues { bounds: Some((first.clone(), last.clone())) }) {
+                if keep(&value) {
+                    run = Some(match run.take() {
+                        Some((start, _)) => (start, value),
+                        None => (value.clone(), value),
+                    });
+                } else if let Some(run) = run.take() {
+                    items.push(bounds(run));
+                }
+            }
+
+            if let Some(run) = run {
+                items.push(bounds(run));
+            }
+        }
+
+        self.items = items.into();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeSet};
+
+    #[test]
+    fn values() {
+        let set = range_set![r!(..=-126i8), r!(3>..4), r!(10..12), r!(126..)];
+        assert_eq!(vec![-128, -127, -126, 10, 11, 126, 127], set.values().collect::<Vec<_>>());
+        assert_eq!(0, RangeSet::<u8>::empty().values().count());
+        assert_eq!(256, RangeSet::<u8>::unbound().values().count());
+
+        let mut values = set.values();
+        assert_eq!(Some(7), values.exact_len());
+        values.nth(3);
+        assert_eq!((3, Some(3)), values.size_hint());
+        assert_eq!(3, values.by_ref().count());
+        assert_eq!(Some(0), values.exact_len());
+
+        assert_eq!((usize::MAX, None), RangeSet::<u128>::unbound().values().size_hint());
+        assert_eq!(Some(0), RangeSet::<u128>::empty().values().exact_len());
+        assert_eq!(None, RangeSet::<u64>::unbound().values().exact_len());
+        assert_eq!(Some(256), RangeSet::<u8>::unbound().values().exact_len());
+
+        let mut values = set.values().exact().unwrap();
+        assert_eq!(7, values.len());
+        values.nth(3);
+        assert_eq!(3, values.len());
+        assert_eq!(vec![11, 126, 127], values.by_ref().collect::<Vec<_>>());
+        assert_eq!(0, values.len());
+        assert_eq!(256, RangeSet::<u8>::unbound().values().exact().unwrap().len());
+        assert!(RangeSet::<u64>::unbound().values().exact().is_none());
+    }
+
+    #[test]
+    fn values_chunks() {
+        let set = range_set![r!(..=-126i8), r!(3>..4), r!(10..12), r!(126..)];
+        let mut chunks = set.values_chunks(3);
+        assert_eq!(Some(3), chunks.exact_len());
+        assert_eq!(Some(vec![-128, -127, -126]), chunks.next());
+        assert_eq!(vec![vec![10, 11, 126], vec![127]], chunks.collect::<Vec<_>>());
+
+        assert_eq!(vec![set.values().collect::<Vec<_>>()], set.values_chunks(100).collect::<Vec<_>>());
+        assert_eq!(0, RangeSet::<u8>::empty().values_chunks(1).count());
+        assert_eq!(Some(256), RangeSet::<u8>::unbound().values_chunks(1).exact_len());
+        assert_eq!(None, RangeSet::<u64>::unbound().values_chunks(1).exact_len());
+    }
+
+    #[test]
+    fn range_values() {
+        assert_eq!(vec![-128, -127, -126], r!(..=-126i8).into_iter().collect::<Vec<_>>());
+        assert_eq!(vec![126, 127], r!(125i8>..).into_iter().collect::<Vec<_>>());
+        assert_eq!(Vec::<u8>::new(), r!(3u8>..4).into_iter().collect::<Vec<_>>());
+        assert_eq!(Vec::<u8>::new(), r!(4u8..3).into_iter().collect::<Vec<_>>());
+        assert_eq!(256, crate::Range::<u8>::unbound().into_iter().count());
+
+        let mut values = r!(0u32..10).into_iter();
+        assert_eq!((10, Some(10)), values.size_hint());
+        assert_eq!(Some(0), values.next());
+        assert_eq!(Some(9), values.next_back());
+        assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8], values.by_ref().collect::<Vec<_>>());
+        assert_eq!((0, Some(0)), values.size_hint());
+        assert_eq!(None, values.next_back());
+        assert_eq!((usize::MAX, None), crate::Range::<u128>::unbound().into_iter().size_hint());
+    }
+
+    #[test]
+    fn retain_values() {
+        let mut set = range_set![r!(..=-126i8), r!(3>..4), r!(10..20), r!(126..)];
+        set.retain_values(|value| value % 2 == 0);
+        assert_eq!(range_set![r!(..=-128i8), r!(-126..=-126), r!(10..=10), r!(12..=12), r!(14..=14), r!(16..=16), r!(18..=18), r!(126..=126)], set);
+
+        let mut set = range_set![r!(0u8>..20)];
+        set.retain_values(|value| *value != 10);
+        assert_eq!(range_set![r!(0u8>..=9), r!(11..20)], set);
+        set.retain_values(|_| true);
+        assert_eq!(range_set![r!(0u8>..=9), r!(11..20)], set);
+        set.retain_values(|_| false);
+        assert_eq!(RangeSet::empty(), set);
+
+        let mut set = RangeSet::<u8>::unbound();
+        set.retain_values(|value| *value != 0 && *value != 255);
+        assert_eq!(range_set![r!(1u8..=254)], set);
+    }
+
+    #[test]
+    fn cursor() {
+        let set = range_set![r!(..=-126i8), r!(3>..4), r!(10..12), r!(126..)];
+        let values: Vec<_> = set.values().collect();
+
+        let mut cursor = set.cursor();
+        let forward: Vec<_> = std::iter::from_fn(|| cursor.next()).collect();
+        assert_eq!(values, forward);
+        assert_eq!(None, cursor.current());
+
+        let mut backward: Vec<_> = std::iter::from_fn(|| cursor.prev()).collect();
+        backward.reverse();
+        assert_eq!(values, backward);
+
+        assert_eq!(Some(&10), cursor.seek_to(&0));
+        assert_eq!(Some(-126), cursor.prev());
+        assert_eq!(Some(&11), cursor.seek_to(&11));
+        assert_eq!(Some(126), cursor.next());
+        assert_eq!(Some(&127), cursor.seek_to(&127));
+        assert_eq!(None, cursor.next());
+        assert_eq!(Some(-128), cursor.next());
+        assert_eq!(None, range_set![r!(0..10u8)].cursor().seek_to(&10));
+    }
+}
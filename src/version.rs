@@ -0,0 +1,198 @@
+use semver::{BuildMetadata, Comparator, Op, Prerelease, Version, VersionReq};
+use crate::{Bound, BoundExt, Range, RangeSet};
+
+impl From<&VersionReq> for RangeSet<Version> {
+    /// Convert a version requirement into the set of versions it spans, following cargo's semantics for each
+    /// operator
+    ///
+    /// Pre-release versions are treated like any other version, the requirement's extra rule that pre-releases only
+    /// match comparators on the same `major.minor.patch` can't be expressed as ranges
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use semver::{Version, VersionReq};
+    /// use eater_rangeset::RangeSet;
+    ///
+    /// let left = RangeSet::from(&VersionReq::parse("^1.2").unwrap());
+    /// let right = RangeSet::from(&VersionReq::parse(">=1.4, <3").unwrap());
+    /// let both = left.intersection(&right);
+    ///
+    /// assert!(both.contains(&Version::parse("1.9.0").unwrap()));
+    /// assert!(!both.contains(&Version::parse("1.3.0").unwrap()));
+    /// assert_eq!(">=1.4.0, <2.0.0-0", both.to_version_req().unwrap().to_string());
+    /// ```
+    fn from(value: &VersionReq) -> Self {
+        value.comparators.iter()
+            .fold(RangeSet::unbound(), |set, comparator| match comparator_range(comparator) {
+                Some(range) => set.intersection(&RangeSet::from([range])),
+                None => RangeSet::empty(),
+            })
+    }
+}
+
+impl From<VersionReq> for RangeSet<Version> {
+    fn from(value: VersionReq) -> Self {
+        RangeSet::from(&value)
+    }
+}
+
+impl RangeSet<Version> {
+    /// Convert this set back into a version requirement, the reverse of converting a `VersionReq` into a set
+    ///
+    /// Returns `None` if the set can't be represented, which is the case for empty sets, sets with more than a
+    /// single range and ranges bounded by versions with build metadata
+    pub fn to_version_req(&self) -> Option<VersionReq> {
+        let [range] = &*self.items else {
+            return None;
+        };
+
+        let comparators = match (&range.start, &range.end) {
+            (Bound::Included(start), Bound::Included(end)) if start == end => vec![comparator(Op::Exact, start)?],
+            (start, end) => {
+                let start = match start {
+                    Bound::Included(start) => Some(comparator(Op::GreaterEq, start)?),
+                    Bound::Excluded(start) => Some(comparator(Op::Greater, start)?),
+                    Bound::Unbounded => None,
+                };
+
+                let end = match end {
+                    Bound::Included(end) => Some(comparator(Op::LessEq, end)?),
+                    Bound::Excluded(end) => Some(comparator(Op::Less, end)?),
+                    Bound::Unbounded => None,
+                };
+
+                start.into_iter().chain(end).collect()
+            }
+        };
+
+        Some(VersionReq { comparators })
+    }
+}
+
+fn comparator(op: Op, version: &Version) -> Option<Comparator> {
+    if !version.build.is_empty() {
+        return None;
+    }
+
+    Some(Comparator {
+        op,
+        major: version.major,
+        minor: Some(version.minor),
+        patch: Some(version.patch),
+        pre: version.pre.clone(),
+    })
+}
+
+/// The range of versions matched by a single comparator, or `None` if it matches no version at all
+fn comparator_range(comparator: &Comparator) -> Option<Range<Version>> {
+    let Comparator { major, minor, patch, .. } = *comparator;
+    let is_full = patch.is_some();
+    let version = Version {
+        major,
+        minor: minor.unwrap_or(0),
+        patch: patch.unwrap_or(0),
+        pre: comparator.pre.clone(),
+        build: BuildMetadata::EMPTY,
+    };
+
+    // The first version after all versions matching the precision of the comparator, e.g. `1.3.0-0` for `1.2`, a
+    // part that overflows carries over into the part before it
+    let next = match (minor, patch) {
+        (Some(minor), Some(patch)) if patch < u64::MAX => lowest(major, minor, patch + 1),
+        (Some(minor), _) if minor < u64::MAX => lowest(major, minor + 1, 0),
+        _ => lowest(major.checked_add(1), 0, 0),
+    };
+
+    let range = match comparator.op {
+        Op::Exact if is_full => Range::new(Bound::Included(version.clone()), Bound::Included(version)),
+        Op::Exact | Op::Wildcard => Range::new(Bound::Included(version), next),
+        Op::Greater if is_full => Range::new(Bound::Excluded(version), Bound::Unbounded),
+        // No version comes after the highest version of a part, e.g. `>18446744073709551615`
+        Op::Greater if next == Bound::Unbounded => return None,
+        Op::Greater => Range::new(next.invert(), Bound::Unbounded),
+        Op::GreaterEq => Range::new(Bound::Included(version), Bound::Unbounded),
+        Op::Less if is_full => Range::new(Bound::Unbounded, Bound::Excluded(version)),
+        Op::Less => Range::new(Bound::Unbounded, lowest(major, version.minor, 0)),
+        Op::LessEq if is_full => Range::new(Bound::Unbounded, Bound::Included(version)),
+        Op::LessEq => Range::new(Bound::Unbounded, next),
+        Op::Tilde => {
+            let end = match minor {
+                Some(minor) => lowest(major, minor.checked_add(1), 0),
+                None => lowest(major.checked_add(1), 0, 0),
+            };
+
+            Range::new(Bound::Included(version), end)
+        }
+        Op::Caret => {
+            let end = match (major, minor, patch) {
+                (0, Some(0), Some(patch)) => lowest(0, 0, patch.checked_add(1)),
+                (0, Some(minor), _) => lowest(0, minor.checked_add(1), 0),
+                _ => lowest(major.checked_add(1), 0, 0),
+            };
+
+            Range::new(Bound::Included(version), end)
+        }
+        // Operators added in the future are treated as matching everything rather than nothing
+        _ => Range::unbound(),
+    };
+
+    Some(range)
+}
+
+/// The exclusive bound right before the lowest pre-release of given version, or unbounded if a part overflowed
+fn lowest(major: impl Into<Option<u64>>, minor: impl Into<Option<u64>>, patch: impl Into<Option<u64>>) -> Bound<Version> {
+    match (major.into(), minor.into(), patch.into()) {
+        (Some(major), Some(minor), Some(patch)) => Bound::Excluded(Version {
+            major,
+            minor,
+            patch,
+            pre: Prerelease::new("0").expect("0 is a valid pre-release"),
+            build: BuildMetadata::EMPTY,
+        }),
+        _ => Bound::Unbounded,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use semver::{Version, VersionReq};
+    use crate::{r, RangeSet};
+
+    fn set(req: &str) -> RangeSet<Version> {
+        RangeSet::from(&VersionReq::parse(req).unwrap())
+    }
+
+    fn v(version: &str) -> Version {
+        Version::parse(version).unwrap()
+    }
+
+    #[test]
+    fn version_req() {
+        assert_eq!(RangeSet::from([r!((v("1.2.3"))..v("2.0.0-0"))]), set("^1.2.3"));
+        assert_eq!(RangeSet::from([r!((v("0.2.3"))..v("0.3.0-0"))]), set("^0.2.3"));
+        assert_eq!(RangeSet::from([r!((v("0.0.3"))..v("0.0.4-0"))]), set("^0.0.3"));
+        assert_eq!(RangeSet::from([r!((v("0.0.0"))..v("0.1.0-0"))]), set("^0.0"));
+        assert_eq!(RangeSet::from([r!((v("1.2.0"))..v("1.3.0-0"))]), set("~1.2"));
+        assert_eq!(RangeSet::from([r!((v("1.0.0"))..v("2.0.0-0"))]), set("1.*"));
+        assert_eq!(RangeSet::from([r!((v("1.2.3-rc.1"))..=v("1.2.3-rc.1"))]), set("=1.2.3-rc.1"));
+        assert_eq!(RangeSet::from([r!((v("1.3.0-0"))..)]), set(">1.2"));
+        assert_eq!(RangeSet::from([r!(..v("1.2.0-0"))]), set("<1.2"));
+        assert_eq!(RangeSet::from([r!(..v("1.3.0-0"))]), set("<=1.2"));
+        assert_eq!(RangeSet::unbound(), set("*"));
+        assert_eq!(RangeSet::empty(), set(">=2, <1"));
+        assert_eq!(RangeSet::empty(), set(">18446744073709551615"));
+        assert_eq!(RangeSet::empty(), set(">1.18446744073709551615, <2"));
+        assert_eq!(RangeSet::from([r!((v("1.0.0-0"))..)]), set(">0.18446744073709551615"));
+        assert_eq!(RangeSet::from([r!((v("1.18446744073709551615.0"))..v("2.0.0-0"))]), set("=1.18446744073709551615"));
+        assert_eq!(RangeSet::unbound(), set("<=18446744073709551615"));
+
+        for req in ["^1.2.3", ">=1.0.0, <1.5.0", "=1.2.3-rc.1", "<=3.0.0", "*"] {
+            let back = set(req).to_version_req().unwrap();
+            assert_eq!(set(req), RangeSet::from(&back));
+        }
+
+        assert_eq!(None, set(">=2, <1").to_version_req());
+        assert_eq!(None, set("<1").union(&set(">2")).to_version_req());
+    }
+}
@@ -0,0 +1,132 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::ops::RangeBounds;
+use crate::{Range, RangeSet, RangeSetRef};
+
+/// A read-only view over the part of a set that falls inside a window
+///
+/// Ranges crossing the edges of the window are clipped lazily, so creating a view doesn't copy any ranges
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set};
+///
+/// let set = range_set![r!(0..10), r!(20..30), r!(40..50)];
+/// let view = set.view(r!(5..25));
+///
+/// assert!(view.contains(&22));
+/// assert!(!view.contains(&2));
+/// assert_eq!(range_set![r!(5..10), r!(20..25)], view.to_range_set());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RangeSetView<'a, T: Ord> {
+    items: RangeSetRef<'a, T>,
+    window: Range<T>,
+}
+
+impl<T: Ord + Debug> RangeSet<T> {
+    /// Returns a view over the part of this set that falls inside given window
+    pub fn view(&self, window: impl Into<Range<T>>) -> RangeSetView<'_, T> {
+        self.as_ref().view(window)
+    }
+}
+
+impl<'a, T: Ord + Debug> RangeSetRef<'a, T> {
+    /// Returns a view over the part of this set that falls inside given window
+    pub fn view(&self, window: impl Into<Range<T>>) -> RangeSetView<'a, T> {
+        let window = window.into();
+        RangeSetView {
+            items: RangeSetRef::new(self.overlapping_slice(window.as_ref())),
+            window,
+        }
+    }
+}
+
+impl<'a, T: Ord + Debug> RangeSetView<'a, T> {
+    /// The window of this view
+    #[inline]
+    pub fn window(&self) -> &Range<T> {
+        &self.window
+    }
+
+    /// If no part of the set falls inside the window
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns an iterator over the ranges inside the window, clipped to the window
+    pub fn items(&self) -> impl Iterator<Item=Range<&T>> + '_ {
+        self.items.items().map(|range| self.clip(range))
+    }
+
+    /// Check if `other` falls within the window and the ranges of the set
+    pub fn contains<Q: Ord + ?Sized>(&self, other: &Q) -> bool where T: Borrow<Q> {
+        self.window.contains(other) && self.items.contains(other)
+    }
+
+    /// Returns an iterator over the ranges inside the window that overlap with given range, clipped to the window
+    pub fn overlapping(&self, range: impl RangeBounds<T>) -> impl Iterator<Item=Range<&T>> + '_ {
+        self.items.overlapping(range).map(|range| self.clip(range))
+    }
+
+    /// Returns `true` if the part of the set inside the window overlaps anywhere with given set
+    pub fn is_overlapping(&self, rhs: RangeSetRef<'_, T>) -> bool {
+        self.items().any(|range| !rhs.overlapping_slice(range).is_empty())
+    }
+
+    fn clip<'b>(&'b self, range: &'b Range<T>) -> Range<&'b T> {
+        let start = if range.start_pos() < self.window.start_pos() { self.window.start() } else { range.start() };
+        let end = if range.end_pos() > self.window.end_pos() { self.window.end() } else { range.end() };
+
+        Range::new(start, end)
+    }
+}
+
+impl<'a, T: Ord + Clone + Debug> RangeSetView<'a, T> {
+    /// Copy the part of the set inside the window into an owned set
+    pub fn to_range_set(&self) -> RangeSet<T> {
+        RangeSet {
+            items: self.items()
+                .map(|range| Range::new(range.start.cloned(), range.end.cloned()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeSet};
+
+    #[test]
+    fn view() {
+        let set = range_set![r!(..0), r!(4..8), r!(10>..=20)];
+
+        let view = set.view(r!(..));
+        assert_eq!(set, view.to_range_set());
+
+        let mut split = range_set![r!(0..10)];
+        split.split_boundary_at(&5);
+        assert_eq!(split, split.view(r!(..)).to_range_set());
+        assert!(split.view(r!(4..6)).contains(&5));
+
+        let view = set.view(r!(0..4));
+        assert!(view.is_empty());
+        assert!(!view.contains(&-1));
+        assert_eq!(RangeSet::empty(), view.to_range_set());
+
+        let view = set.view(r!(6..=15));
+        assert!(!view.is_empty());
+        assert!(view.contains(&6));
+        assert!(!view.contains(&8));
+        assert!(view.contains(&15));
+        assert!(!view.contains(&16));
+        assert_eq!(vec![r!((&6)..&8), r!((&10)>..=&15)], view.items().collect::<Vec<_>>());
+        assert_eq!(vec![r!((&10)>..=&15)], view.overlapping(r!(9..)).collect::<Vec<_>>());
+
+        assert!(view.is_overlapping(range_set![r!(15..)].as_ref()));
+        assert!(!view.is_overlapping(range_set![r!(15>..)].as_ref()));
+        assert!(!view.is_overlapping(range_set![r!(..6), r!(8..=10)].as_ref()));
+    }
+}
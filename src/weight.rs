@@ -0,0 +1,118 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::ops::Add;
+use crate::{Range, RangeMap};
+
+/// Sums weights added over ranges, like a [`RangeMap`](RangeMap) where overlapping values add up
+///
+/// Segments with the same weight are merged and segments where the weight adds up to the default (zero) are
+/// dropped, so the profile only holds the extents that carry weight
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, WeightProfile};
+///
+/// let mut load = WeightProfile::new();
+/// load.add(r!(0..10), 5);
+/// load.add(r!(5..15), 3);
+///
+/// assert_eq!(5, load.weight_at(&2));
+/// assert_eq!(8, load.weight_at(&7));
+/// assert_eq!(0, load.weight_at(&20));
+/// assert_eq!(vec![(&r!(0..5), &5), (&r!(5..10), &8), (&r!(10..15), &3)], load.segments().collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct WeightProfile<T: Ord, W> {
+    map: RangeMap<T, W>,
+}
+
+impl<T: Ord, W: PartialEq> Default for WeightProfile<T, W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord, W: PartialEq> WeightProfile<T, W> {
+    /// Create a new profile without any weight
+    pub fn new() -> Self {
+        WeightProfile { map: RangeMap::coalescing() }
+    }
+}
+
+impl<T: Ord, W> WeightProfile<T, W> {
+    /// If no part of this profile carries weight
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns an iterator over all segments with a constant weight, in order
+    ///
+    /// Extents without weight are skipped
+    pub fn segments(&self) -> impl Iterator<Item=(&Range<T>, &W)> {
+        self.map.iter()
+    }
+
+    /// Returns the map of segments to their weight
+    #[inline]
+    pub fn as_map(&self) -> &RangeMap<T, W> {
+        &self.map
+    }
+}
+
+impl<T: Ord, W: Default + Clone> WeightProfile<T, W> {
+    /// Returns the weight at given point
+    pub fn weight_at<Q: Ord + ?Sized>(&self, point: &Q) -> W where T: Borrow<Q> {
+        self.map.get(point).cloned().unwrap_or_default()
+    }
+}
+
+impl<T: Ord + Clone + Debug, W: Add<Output=W> + Default + PartialEq + Clone> WeightProfile<T, W> {
+    /// Add given weight over every part of given range
+    ///
+    /// Adding a negative weight takes weight away again
+    pub fn add(&mut self, range: impl Into<Range<T>>, weight: W) {
+        let zero = W::default();
+        self.map.update(range, |current| {
+            let weight = match current {
+                Some(current) => current.clone() + weight.clone(),
+                None => weight.clone(),
+            };
+
+            (weight != zero).then_some(weight)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, WeightProfile};
+
+    #[test]
+    fn weight_profile() {
+        let mut profile = WeightProfile::new();
+        assert!(profile.is_empty());
+
+        profile.add(r!(0..10), 2i64);
+        profile.add(r!(..), 1);
+        profile.add(r!(4..6), -3);
+        assert_eq!(vec![(&r!(..0), &1), (&r!(0..4), &3), (&r!(6..10), &3), (&r!(10..), &1)], profile.segments().collect::<Vec<_>>());
+        assert_eq!(0, profile.weight_at(&5));
+
+        profile.add(r!(0..10), -2);
+        assert_eq!(vec![(&r!(..4), &1), (&r!(4..6), &-2), (&r!(6..), &1)], profile.segments().collect::<Vec<_>>());
+
+        let mut profile = WeightProfile::new();
+        profile.add(r!(0..10), 0.5);
+        profile.add(r!(5..=10), 0.25);
+        assert_eq!(0.75, profile.weight_at(&5));
+        assert_eq!(0.25, profile.weight_at(&10));
+
+        let mut profile = WeightProfile::new();
+        profile.add(r!(0..0), 1);
+        profile.add(r!(0..=3), 2);
+        assert_eq!(vec![(&r!(0..=3), &2)], profile.segments().collect::<Vec<_>>());
+        assert_eq!(2, profile.weight_at(&0));
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt::Debug;
+use std::mem;
+use crate::{holds_values, merge_sorted, BoundExt, Range, RangeSet, RangeVec};
+use crate::Bound::Unbounded;
+use crate::macros::trace_span;
+use crate::storage::Ranges;
+
+/// Reusable scratch buffers for chaining set operations without allocating at every step
+///
+/// The operations give the same results as their [`RangeSet`](RangeSet) counterparts, but the intermediate results
+/// are written into buffers owned by the workspace, and the storage of sets handed back with
+/// [`recycle`](Workspace::recycle) is reused for later results
+///
+/// # Example
+///
+/// ```rust
+/// use eater_rangeset::{r, range_set, Workspace};
+///
+/// let (a, b, c, d) = (range_set![r!(0..10)], range_set![r!(20..30)], range_set![r!(5..25)], range_set![r!(..28)]);
+/// let mut ws = Workspace::new();
+///
+/// // (a ∪ b) ∖ (c ∩ d)
+/// let union = ws.union(&a, &b);
+/// let inter = ws.intersection(&c, &d);
+/// let result = ws.difference(&union, &inter);
+/// ws.recycle(union);
+/// ws.recycle(inter);
+///
+/// assert_eq!(range_set![r!(0..5), r!(25..30)], result);
+/// ```
+#[derive(Debug)]
+pub struct Workspace<T: Ord> {
+    left: RangeVec<Range<T>>,
+    right: RangeVec<Range<T>>,
+    merged: RangeVec<Range<T>>,
+    pool: Vec<RangeVec<Range<T>>>,
+}
+
+impl<T: Ord> Default for Workspace<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Workspace<T> {
+    /// Create a new workspace without any buffers, they're allocated as the operations need them
+    pub fn new() -> Self {
+        Workspace {
+            left: RangeVec::new(),
+            right: RangeVec::new(),
+            merged: RangeVec::new(),
+            pool: vec![],
+        }
+    }
+
+    /// Hand the storage of a set that's no longer needed back, so it can be reused for the result of a later
+    /// operation
+    pub fn recycle(&mut self, set: RangeSet<T>) {
+        if let Ranges::Many(mut items) = set.items {
+            items.clear();
+            self.pool.push(items);
+        }
+    }
+
+    /// Returns an empty buffer, reusing recycled storage if there is any
+    fn buffer(&mut self) -> RangeVec<Range<T>> {
+        self.pool.pop().unwrap_or_default()
+    }
+
+    /// Turn a buffer holding a result into a set, the buffer goes back to the pool when the set stores its range
+    /// inline
+    fn finish(&mut self, mut out: RangeVec<Range<T>>) -> RangeSet<T> {
+        if out.len() > 1 {
+            return RangeSet { items: Ranges::Many(out) };
+        }
+
+        let items = out.pop().map_or(Ranges::Empty, Ranges::Single);
+        self.pool.push(out);
+        RangeSet { items }
+    }
+}
+
+impl<T: Ord + Clone + Debug> Workspace<T> {
+    /// Create an union of given sets, see [`RangeSet::union`](RangeSet::union)
+    pub fn union(&mut self, left: &RangeSet<T>, right: &RangeSet<T>) -> RangeSet<T> {
+        trace_span!("union", left = left.items.len(), right = right.items.len());
+        let mut out = self.buffer();
+        union_into(&left.items, &right.items, &mut out);
+        self.finish(out)
+    }
+
+    /// Invert given set, see [`RangeSet::invert`](RangeSet::invert)
+    pub fn invert(&mut self, set: &RangeSet<T>) -> RangeSet<T> {
+        trace_span!("invert", ranges = set.items.len());
+        let mut out = self.buffer();
+        invert_into(&set.items, &mut out);
+        self.finish(out)
+    }
+
+    /// Get the intersection of given sets, see [`RangeSet::intersection`](RangeSet::intersection)
+    pub fn intersection(&mut self, left: &RangeSet<T>, right: &RangeSet<T>) -> RangeSet<T> {
+        trace_span!("intersection", left = left.items.len(), right = right.items.len());
+        self.left.clear();
+        self.right.clear();
+        self.merged.clear();
+        invert_into(&left.items, &mut self.left);
+        invert_into(&right.items, &mut self.right);
+        union_into(&self.left, &self.right, &mut self.merged);
+
+        let mut out = self.buffer();
+        invert_into(&self.merged, &mut out);
+        self.finish(out)
+    }
+
+    /// Get the difference of given sets, see [`RangeSet::difference`](RangeSet::difference)
+    pub fn difference(&mut self, left: &RangeSet<T>, right: &RangeSet<T>) -> RangeSet<T> {
+        trace_span!("difference", left = left.items.len(), right = right.items.len());
+        self.left.clear();
+        self.merged.clear();
+        invert_into(&left.items, &mut self.left);
+        union_into(&self.left, &right.items, &mut self.merged);
+
+        let mut out = self.buffer();
+        invert_into(&self.merged, &mut out);
+        self.finish(out)
+    }
+}
+
+/// Write the union of given sorted ranges into `out`
+fn union_into<T: Ord + Clone + Debug>(left: &[Range<T>], right: &[Range<T>], out: &mut RangeVec<Range<T>>) {
+    out.extend(merge_sorted(left.iter().cloned(), right.iter().cloned()));
+}
+
+/// Write the inverse of given sorted ranges into `out`
+fn invert_into<T: Ord + Clone>(items: &[Range<T>], out: &mut RangeVec<Range<T>>) {
+    match items {
+        [] => {
+            out.push(Range::unbound());
+            return;
+        }
+        [range] if range.is_unbound() => return,
+        _ => {}
+    }
+
+    let mut current = Range::unbound();
+    for item in items {
+        if item.start == Unbounded {
+            current.start = item.end.clone().invert();
+            continue;
+        }
+
+        current.end = item.start.clone().invert();
+        let last = mem::replace(&mut current, Range::new(item.end.clone().invert(), Unbounded));
+        // Touching ranges, e.g. after `split_boundary_at`, leave a gap holding no values
+        if holds_values(last.start(), last.end()) {
+            out.push(last);
+        }
+
+        if item.end == Unbounded {
+            return;
+        }
+    }
+
+    out.push(current);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{r, range_set, RangeSet, Workspace};
+
+    #[test]
+    fn workspace() {
+        let sets: [RangeSet<i32>; 6] = [
+            range_set![],
+            range_set![r!(..)],
+            range_set![r!(..0), r!(4..8), r!(10>..=20)],
+            range_set![r!(6..12)],
+            range_set![r!(0..=4), r!(8..)],
+            range_set![r!(..=-5), r!(15..16)],
+        ];
+
+        let mut ws = Workspace::new();
+        for left in &sets {
+            assert_eq!(left.invert(), ws.invert(left));
+
+            for right in &sets {
+                let union = ws.union(left, right);
+                let intersection = ws.intersection(left, right);
+                let difference = ws.difference(left, right);
+                assert_eq!(left.union(right), union);
+                assert_eq!(left.intersection(right), intersection);
+                assert_eq!(left.difference(right), difference);
+
+                ws.recycle(union);
+                ws.recycle(intersection);
+                ws.recycle(difference);
+            }
+        }
+
+        let mut split = range_set![r!(0..10)];
+        split.split_boundary_at(&4);
+        assert_eq!(vec![&r!(..0), &r!(10..)], ws.invert(&split).items().collect::<Vec<_>>());
+
+        // Results without storage of their own leave the buffer in the pool
+        let mut ws = Workspace::new();
+        ws.recycle(range_set![r!(0..1), r!(2..3)]);
+        let union = ws.union(&range_set![r!(0..4)], &range_set![r!(2..6)]);
+        assert_eq!(range_set![r!(0..6)], union);
+        assert_eq!(1, ws.pool.len());
+        ws.recycle(union);
+        assert_eq!(1, ws.pool.len());
+    }
+}